@@ -0,0 +1,36 @@
+-- Emoji reactions on posts and comments
+CREATE TABLE reactions (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    target_type     VARCHAR(20) NOT NULL CHECK (target_type IN ('post', 'comment')),
+    target_id       UUID NOT NULL,
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    emoji           VARCHAR(32) NOT NULL,
+    created_at      TIMESTAMPTZ DEFAULT now(),
+    UNIQUE(target_type, target_id, user_id, emoji)
+);
+
+CREATE INDEX idx_reactions_target ON reactions(target_type, target_id);
+
+-- target_id can't carry a foreign key, so clean up when the target goes away
+-- (also fires for comments removed by the posts -> comments cascade)
+CREATE FUNCTION delete_post_reactions() RETURNS trigger AS $$
+BEGIN
+    DELETE FROM reactions WHERE target_type = 'post' AND target_id = OLD.id;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION delete_comment_reactions() RETURNS trigger AS $$
+BEGIN
+    DELETE FROM reactions WHERE target_type = 'comment' AND target_id = OLD.id;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER posts_delete_reactions
+    AFTER DELETE ON posts
+    FOR EACH ROW EXECUTE FUNCTION delete_post_reactions();
+
+CREATE TRIGGER comments_delete_reactions
+    AFTER DELETE ON comments
+    FOR EACH ROW EXECUTE FUNCTION delete_comment_reactions();
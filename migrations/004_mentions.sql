@@ -0,0 +1,27 @@
+-- Notifications delivered to users (mentions for now)
+CREATE TABLE notifications (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    actor_id        UUID REFERENCES users(id) ON DELETE SET NULL,
+    kind            VARCHAR(30) NOT NULL,
+    post_id         UUID REFERENCES posts(id) ON DELETE CASCADE,
+    comment_id      UUID REFERENCES comments(id) ON DELETE CASCADE,
+    read_at         TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ DEFAULT now()
+);
+
+-- @mentions of org members in post descriptions (comment_id NULL) and comments.
+-- Offsets are character positions of the `@name` span in the stored text.
+CREATE TABLE mentions (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    post_id         UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    comment_id      UUID REFERENCES comments(id) ON DELETE CASCADE,
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    start_offset    INT NOT NULL,
+    end_offset      INT NOT NULL,
+    created_at      TIMESTAMPTZ DEFAULT now()
+);
+
+CREATE INDEX idx_notifications_user_id ON notifications(user_id, created_at DESC);
+CREATE INDEX idx_mentions_post_id ON mentions(post_id);
+CREATE INDEX idx_mentions_comment_id ON mentions(comment_id);
@@ -0,0 +1,33 @@
+-- File attachments on posts (comment_id NULL) and comments
+CREATE TABLE attachments (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id          UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    post_id         UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    comment_id      UUID REFERENCES comments(id) ON DELETE CASCADE,
+    uploader_id     UUID REFERENCES users(id) ON DELETE SET NULL,
+    filename        VARCHAR(255) NOT NULL,
+    content_type    VARCHAR(100) NOT NULL,
+    size_bytes      BIGINT NOT NULL,
+    storage_key     TEXT NOT NULL UNIQUE,
+    created_at      TIMESTAMPTZ DEFAULT now()
+);
+
+CREATE INDEX idx_attachments_post_id ON attachments(post_id);
+CREATE INDEX idx_attachments_comment_id ON attachments(comment_id);
+
+-- Per-org storage accounting. Uploads reserve space explicitly (so the quota
+-- check is atomic); every delete, including cascades, releases it here.
+ALTER TABLE organizations ADD COLUMN storage_used_bytes BIGINT NOT NULL DEFAULT 0;
+
+CREATE FUNCTION release_attachment_storage() RETURNS trigger AS $$
+BEGIN
+    UPDATE organizations
+    SET storage_used_bytes = GREATEST(storage_used_bytes - OLD.size_bytes, 0)
+    WHERE id = OLD.org_id;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER attachments_release_storage
+    AFTER DELETE ON attachments
+    FOR EACH ROW EXECUTE FUNCTION release_attachment_storage();
@@ -0,0 +1,11 @@
+-- Pending email address changes, confirmed via a token sent to the new address
+CREATE TABLE email_change_requests (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    new_email       VARCHAR(255) NOT NULL,
+    token_hash      TEXT NOT NULL UNIQUE,
+    expires_at      TIMESTAMPTZ NOT NULL,
+    created_at      TIMESTAMPTZ DEFAULT now()
+);
+
+CREATE INDEX idx_email_change_requests_user_id ON email_change_requests(user_id);
@@ -0,0 +1,14 @@
+-- Audit log of GDPR data exports and erasures. user_id has no foreign key so
+-- the record outlives the account it describes.
+CREATE TABLE data_requests (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id         UUID NOT NULL,
+    kind            VARCHAR(20) NOT NULL CHECK (kind IN ('export', 'erasure')),
+    status          VARCHAR(20) NOT NULL DEFAULT 'pending'
+                        CHECK (status IN ('pending', 'completed', 'failed')),
+    error           TEXT,
+    requested_at    TIMESTAMPTZ DEFAULT now(),
+    completed_at    TIMESTAMPTZ
+);
+
+CREATE INDEX idx_data_requests_user_id ON data_requests(user_id);
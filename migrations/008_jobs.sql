@@ -0,0 +1,24 @@
+-- Durable background jobs. Workers claim due rows with FOR UPDATE SKIP LOCKED;
+-- failures are retried with backoff until max_attempts, then left as 'dead'.
+CREATE TABLE jobs (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    kind            VARCHAR(50) NOT NULL,
+    payload         JSONB NOT NULL DEFAULT '{}',
+    -- Organization the job works for, if any; scopes the admin endpoint
+    org_id          UUID REFERENCES organizations(id) ON DELETE CASCADE,
+    status          VARCHAR(20) NOT NULL DEFAULT 'pending'
+                        CHECK (status IN ('pending', 'running', 'completed', 'dead')),
+    attempts        INT NOT NULL DEFAULT 0,
+    max_attempts    INT NOT NULL DEFAULT 5,
+    run_at          TIMESTAMPTZ NOT NULL DEFAULT now(),
+    locked_at       TIMESTAMPTZ,
+    last_error      TEXT,
+    -- At most one pending job per key (recurring jobs reschedule themselves)
+    unique_key      TEXT,
+    created_at      TIMESTAMPTZ DEFAULT now(),
+    completed_at    TIMESTAMPTZ
+);
+
+CREATE INDEX idx_jobs_due ON jobs(run_at) WHERE status IN ('pending', 'running');
+CREATE INDEX idx_jobs_org_id ON jobs(org_id, created_at DESC);
+CREATE UNIQUE INDEX idx_jobs_unique_key ON jobs(unique_key) WHERE status = 'pending';
@@ -0,0 +1,13 @@
+-- Scheduled changelog publishing. An entry is public once it is not a draft
+-- and published_at has passed; announced_at records that the publish-time
+-- side effects (completing linked posts, notifying voters) have run.
+ALTER TABLE changelog_entries
+    ADD COLUMN complete_posts BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN announced_at TIMESTAMPTZ;
+
+CREATE INDEX idx_changelog_entries_org_published ON changelog_entries(org_id, published_at DESC);
+CREATE INDEX idx_changelog_posts_post_id ON changelog_posts(post_id);
+
+-- Changelog announcements in the notification feed
+ALTER TABLE notifications
+    ADD COLUMN changelog_id UUID REFERENCES changelog_entries(id) ON DELETE CASCADE;
@@ -0,0 +1,27 @@
+-- History of post status changes, for board activity feeds. Recorded by a
+-- trigger so every path (API, imports, changelog publishing) is covered.
+CREATE TABLE post_status_changes (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    post_id         UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    from_status     VARCHAR(30),
+    to_status       VARCHAR(30),
+    changed_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_post_status_changes_post_id ON post_status_changes(post_id, changed_at DESC);
+
+CREATE FUNCTION record_post_status_change() RETURNS trigger AS $$
+BEGIN
+    INSERT INTO post_status_changes (post_id, from_status, to_status)
+    VALUES (NEW.id, OLD.status, NEW.status);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER posts_record_status_change
+    AFTER UPDATE OF status ON posts
+    FOR EACH ROW
+    WHEN (OLD.status IS DISTINCT FROM NEW.status)
+    EXECUTE FUNCTION record_post_status_change();
+
+CREATE INDEX idx_posts_board_created ON posts(board_id, created_at DESC);
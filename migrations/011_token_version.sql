@@ -0,0 +1,3 @@
+-- Bumped whenever a user's existing sessions must end (password change or
+-- reset, account erasure). Tokens carry the version they were issued at.
+ALTER TABLE users ADD COLUMN token_version INT NOT NULL DEFAULT 0;
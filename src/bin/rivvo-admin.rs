@@ -0,0 +1,450 @@
+//! Operator commands for a self-hosted Rivvo instance.
+//!
+//! Reads the same configuration as the server (`--config` or `rivvo.toml`,
+//! `.env`, environment variables and a secret store).
+//! Run `rivvo-admin --help` for the list of commands.
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use sqlx::PgPool;
+use tracing_subscriber::EnvFilter;
+use validator::Validate;
+
+use rivvo::config::Config;
+use rivvo::db;
+use rivvo::handlers::auth::RegisterRequest;
+use rivvo::importers::{self, Format, Source};
+use rivvo::models::board_transfer::ImportReport;
+use rivvo::services::{
+    auth_service, board_service, board_transfer_service, counter_service, gdpr_service,
+    job_service, org_service,
+};
+use rivvo::vault;
+
+type CliResult = Result<(), Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(name = "rivvo-admin", about = "Administer a Rivvo instance")]
+struct Cli {
+    /// TOML configuration file, as for the server
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Apply pending database migrations
+    Migrate,
+    /// Create a user along with their personal workspace
+    CreateUser {
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        name: String,
+        /// Prompted for when omitted
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Create an organization with an existing user as its admin
+    CreateOrg {
+        #[arg(long)]
+        name: String,
+        /// Email of the user who becomes admin
+        #[arg(long)]
+        owner: String,
+    },
+    /// Make a user an admin of an organization, adding them if needed
+    Promote {
+        /// Organization id or slug
+        #[arg(long)]
+        org: String,
+        #[arg(long)]
+        email: String,
+    },
+    /// Set a new password for a user
+    ResetPassword {
+        #[arg(long)]
+        email: String,
+        /// Prompted for when omitted
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Queue erasure of a user's account, as if they had deleted it
+    /// themselves
+    EraseUser {
+        #[arg(long)]
+        email: String,
+    },
+    /// Recompute vote and comment counts of every post
+    Recount {
+        /// Only report posts whose counts drifted
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List background jobs of every organization, newest first
+    Jobs {
+        /// `pending`, `running`, `completed` or `dead`
+        #[arg(long)]
+        status: Option<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: i64,
+    },
+    /// Requeue a dead job, or run a pending one without waiting
+    RetryJob {
+        #[arg(long)]
+        id: uuid::Uuid,
+    },
+    /// Export a board's posts as JSON or CSV
+    ExportBoard {
+        /// Organization id or slug
+        #[arg(long)]
+        org: String,
+        /// Board slug
+        #[arg(long)]
+        board: String,
+        /// `json` or `csv`
+        #[arg(long, default_value = "json")]
+        format: String,
+        /// Written to stdout when omitted
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Import posts into a board from a Rivvo JSON or CSV file
+    ImportBoard {
+        /// Organization id or slug
+        #[arg(long)]
+        org: String,
+        /// Board slug
+        #[arg(long)]
+        board: String,
+        #[arg(long)]
+        file: PathBuf,
+        /// `json` or `csv`; taken from the file extension when omitted
+        #[arg(long)]
+        format: Option<String>,
+        /// Validate and report without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Import a Canny or UserVoice export into an organization
+    Import {
+        /// Organization id or slug
+        #[arg(long)]
+        org: String,
+        /// `canny` or `uservoice`
+        #[arg(long)]
+        source: String,
+        #[arg(long)]
+        file: PathBuf,
+        /// `json` or `csv`; taken from the file extension when omitted
+        #[arg(long)]
+        format: Option<String>,
+        /// Validate and report without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Generate a new JWT secret. Everyone is signed out once the server
+    /// restarts with it.
+    RotateJwtSecret {
+        /// Replace `JWT_SECRET` in this file instead of printing the secret
+        #[arg(long)]
+        env_file: Option<PathBuf>,
+    },
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    let cli = Cli::parse();
+    match run(cli.command, cli.config.as_deref()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(command: Command, config_path: Option<&Path>) -> CliResult {
+    if let Command::RotateJwtSecret { env_file } = command {
+        return rotate_jwt_secret(env_file.as_deref());
+    }
+
+    vault::fetch_secrets(&Config::load_secrets(config_path)?).await;
+    let config = Config::load(config_path)?;
+    let pool = db::create_pool(&config.database.url, 2).await?;
+
+    match command {
+        Command::Migrate => {
+            db::run_migrations(&pool).await?;
+            println!("Migrations applied");
+        }
+        Command::CreateUser {
+            email,
+            name,
+            password,
+        } => {
+            let password = password_or_prompt(password)?;
+            RegisterRequest {
+                email: email.clone(),
+                name: name.clone(),
+                password: password.clone(),
+            }
+            .validate()?;
+            let user = auth_service::register_user(&pool, &email, &name, &password).await?;
+            println!("Created user {} ({})", user.email, user.id);
+        }
+        Command::CreateOrg { name, owner } => {
+            let owner = auth_service::get_user_by_email(&pool, &owner).await?;
+            let org = org_service::create_org(&pool, &name, owner.id).await?;
+            println!("Created organization {} ({})", org.slug, org.id);
+        }
+        Command::Promote { org, email } => {
+            let org = org_service::find_org(&pool, &org).await?;
+            let user = auth_service::get_user_by_email(&pool, &email).await?;
+            org_service::set_member_role(&pool, org.id, user.id, "admin").await?;
+            println!("{} is now an admin of {}", user.email, org.slug);
+        }
+        Command::ResetPassword { email, password } => {
+            let user = auth_service::get_user_by_email(&pool, &email).await?;
+            let password = password_or_prompt(password)?;
+            check_password_length(&password)?;
+            auth_service::set_password(&pool, user.id, &password).await?;
+            println!("Password updated for {}", user.email);
+        }
+        Command::EraseUser { email } => {
+            let user = auth_service::get_user_by_email(&pool, &email).await?;
+            gdpr_service::request_erasure(&pool, user.id).await?;
+            println!(
+                "Erasure of {} ({}) queued; see `rivvo-admin jobs`",
+                user.email, user.id
+            );
+        }
+        Command::Recount { dry_run } => {
+            let report = counter_service::reconcile(&pool, None, !dry_run).await?;
+            for drift in &report.drifted {
+                println!(
+                    "{}: votes {} -> {}, comments {} -> {}",
+                    drift.post_id,
+                    drift.stored_vote_count.unwrap_or_default(),
+                    drift.actual_vote_count,
+                    drift.stored_comment_count.unwrap_or_default(),
+                    drift.actual_comment_count
+                );
+            }
+            println!(
+                "{} of {} post(s) drifted{}",
+                report.drifted.len(),
+                report.posts_checked,
+                if report.fixed { "; fixed" } else { "" }
+            );
+        }
+        Command::Jobs { status, limit } => {
+            for job in job_service::list_jobs(&pool, None, status.as_deref(), limit).await? {
+                println!(
+                    "{}  {:<9}  {:<18}  attempts {}/{}  run at {}{}",
+                    job.id,
+                    job.status,
+                    job.kind,
+                    job.attempts,
+                    job.max_attempts,
+                    job.run_at.format("%Y-%m-%d %H:%M:%S"),
+                    job.last_error
+                        .map(|e| format!("  last error: {e}"))
+                        .unwrap_or_default()
+                );
+            }
+        }
+        Command::RetryJob { id } => {
+            let job = job_service::retry_job(&pool, None, id).await?;
+            println!("Job {} ({}) queued to run now", job.id, job.kind);
+        }
+        Command::ExportBoard {
+            org,
+            board,
+            format,
+            output,
+        } => {
+            let board = find_board(&pool, &org, &board).await?;
+            let export = board_transfer_service::export_board(&pool, &board).await?;
+            let data = match format.as_str() {
+                "json" => serde_json::to_vec_pretty(&export)?,
+                "csv" => board_transfer_service::export_to_csv(&export)?,
+                other => return Err(format!("Unknown export format: {other}").into()),
+            };
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, data)?;
+                    eprintln!(
+                        "Exported {} post(s) to {}",
+                        export.posts.len(),
+                        path.display()
+                    );
+                }
+                None => {
+                    use std::io::Write;
+                    std::io::stdout().write_all(&data)?;
+                }
+            }
+        }
+        Command::ImportBoard {
+            org,
+            board,
+            file,
+            format,
+            dry_run,
+        } => {
+            let board = find_board(&pool, &org, &board).await?;
+            let data = std::fs::read(&file)?;
+            let posts = match file_format(&file, format.as_deref())? {
+                Format::Json => board_transfer_service::parse_json(&data)?,
+                Format::Csv => board_transfer_service::parse_csv(&data)?,
+            };
+            let report =
+                board_transfer_service::import_posts(&pool, board.org_id, board.id, posts, dry_run)
+                    .await?;
+            print_report(&report)?;
+        }
+        Command::Import {
+            org,
+            source,
+            file,
+            format,
+            dry_run,
+        } => {
+            let org = org_service::find_org(&pool, &org).await?;
+            let source = Source::from_name(&source)
+                .ok_or_else(|| format!("Unknown import source: {source}"))?;
+            let data = std::fs::read(&file)?;
+            let parsed = importers::parse(source, file_format(&file, format.as_deref())?, &data)?;
+            let report =
+                board_transfer_service::import_foreign(&pool, org.id, parsed, dry_run).await?;
+            print_report(&report)?;
+        }
+        Command::RotateJwtSecret { .. } => unreachable!("handled before connecting"),
+    }
+    Ok(())
+}
+
+async fn find_board(
+    pool: &PgPool,
+    org: &str,
+    slug: &str,
+) -> Result<rivvo::models::board::Board, Box<dyn Error>> {
+    let org = org_service::find_org(pool, org).await?;
+    Ok(board_service::get_board_by_slug(pool, org.id, slug).await?)
+}
+
+fn file_format(file: &Path, format: Option<&str>) -> Result<Format, Box<dyn Error>> {
+    let name = format
+        .map(str::to_string)
+        .or_else(|| {
+            file.extension()
+                .map(|ext| ext.to_string_lossy().into_owned())
+        })
+        .unwrap_or_default();
+    Format::from_name(&name).ok_or_else(|| "Import format must be json or csv".into())
+}
+
+/// Print an import report as JSON; failed validation is an error.
+fn print_report(report: &ImportReport) -> CliResult {
+    println!("{}", serde_json::to_string_pretty(report)?);
+    if !report.valid {
+        return Err("Import has invalid rows; nothing was written".into());
+    }
+    Ok(())
+}
+
+fn password_or_prompt(password: Option<String>) -> Result<String, Box<dyn Error>> {
+    if let Some(password) = password {
+        return Ok(password);
+    }
+    let password = rpassword::prompt_password("New password: ")?;
+    if rpassword::prompt_password("Repeat password: ")? != password {
+        return Err("Passwords do not match".into());
+    }
+    Ok(password)
+}
+
+fn check_password_length(password: &str) -> CliResult {
+    if !(8..=128).contains(&password.chars().count()) {
+        return Err("Password must be between 8 and 128 characters".into());
+    }
+    Ok(())
+}
+
+fn rotate_jwt_secret(env_file: Option<&Path>) -> CliResult {
+    let secret = auth_service::generate_token();
+    match env_file {
+        Some(path) => {
+            let contents = std::fs::read_to_string(path).unwrap_or_default();
+            std::fs::write(path, set_env_var(&contents, "JWT_SECRET", &secret))?;
+            println!(
+                "Wrote a new JWT_SECRET to {}. Restart the server to apply it; existing sessions will end.",
+                path.display()
+            );
+        }
+        None => {
+            println!("{secret}");
+            eprintln!(
+                "Set JWT_SECRET to this value and restart the server; existing sessions will end."
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Replace `key`'s line in a dotenv file, or append one.
+fn set_env_var(contents: &str, key: &str, value: &str) -> String {
+    let prefix = format!("{key}=");
+    let mut found = false;
+    let mut lines: Vec<String> = contents
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with(&prefix) {
+                found = true;
+                format!("{prefix}{value}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    if !found {
+        lines.push(format!("{prefix}{value}"));
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_env_var_replaces_or_appends() {
+        let env = "DATABASE_URL=postgres://x\nJWT_SECRET=old\n# JWT_SECRET=commented\n";
+        assert_eq!(
+            set_env_var(env, "JWT_SECRET", "new"),
+            "DATABASE_URL=postgres://x\nJWT_SECRET=new\n# JWT_SECRET=commented\n"
+        );
+        assert_eq!(set_env_var("", "JWT_SECRET", "new"), "JWT_SECRET=new\n");
+    }
+
+    #[test]
+    fn cli_definition_is_valid() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+    }
+}
@@ -1,5 +1,8 @@
 use sqlx::PgPool;
-use sqlx::postgres::PgPoolOptions;
+use sqlx::migrate::Migrator;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
 
 pub async fn create_pool(database_url: &str, max_connections: u32) -> Result<PgPool, sqlx::Error> {
     PgPoolOptions::new()
@@ -7,3 +10,29 @@ pub async fn create_pool(database_url: &str, max_connections: u32) -> Result<PgP
         .connect(database_url)
         .await
 }
+
+/// Point new connections of `pool` at `url`, e.g. after its credentials
+/// were rotated. Open connections are used until the pool retires them.
+pub fn set_url(pool: &PgPool, url: &str) -> Result<(), sqlx::Error> {
+    pool.set_connect_options(url.parse::<PgConnectOptions>()?);
+    Ok(())
+}
+
+pub async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::migrate::MigrateError> {
+    MIGRATOR.run(pool).await
+}
+
+/// Versions of the migrations in this build that the database hasn't
+/// applied successfully.
+pub async fn pending_migrations(pool: &PgPool) -> Result<Vec<i64>, sqlx::Error> {
+    let applied: Vec<i64> =
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success")
+            .fetch_all(pool)
+            .await?;
+
+    Ok(MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration() && !applied.contains(&m.version))
+        .map(|m| m.version)
+        .collect())
+}
@@ -1,5 +1,11 @@
-use actix_web::{HttpResponse, ResponseError, http::StatusCode};
-use serde_json::json;
+use actix_web::error::{JsonPayloadError, PathError, QueryPayloadError};
+use actix_web::{HttpRequest, HttpResponse, ResponseError, http::StatusCode};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::middleware::request_id;
+
+pub const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
 
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
@@ -17,6 +23,65 @@ pub enum AppError {
     DatabaseError(#[from] sqlx::Error),
     #[error("Validation error: {0}")]
     ValidationError(String),
+    /// Failed `Validate` checks, reported per field.
+    #[error("Validation failed")]
+    InvalidFields(#[from] validator::ValidationErrors),
+    #[error("{0}")]
+    PayloadTooLarge(String),
+    #[error("{0}")]
+    UnsupportedMediaType(String),
+    /// The organization's storage quota can't fit the upload.
+    #[error("{0}")]
+    QuotaExceeded(String),
+}
+
+/// Body of every error response: RFC 7807 problem details, served as
+/// `application/problem+json`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    /// Always `about:blank`; `code` identifies the problem
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// Reason phrase of the status code
+    pub title: String,
+    pub status: u16,
+    /// Human-readable explanation
+    pub detail: String,
+    /// Stable, machine-readable error code, e.g. `not_found`
+    pub code: String,
+    /// Matches the `X-Request-Id` response header
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Per-field problems when `code` is `validation_failed`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+    /// Same as `detail`, for clients written before problem details
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    /// Failed check, e.g. `length` or `email`
+    pub code: String,
+    pub message: String,
+}
+
+impl AppError {
+    /// Stable identifier of the kind of error, sent as `code`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound(_) => "not_found",
+            AppError::BadRequest(_) => "bad_request",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::InternalError(_) | AppError::DatabaseError(_) => "internal_error",
+            AppError::ValidationError(_) | AppError::InvalidFields(_) => "validation_failed",
+            AppError::PayloadTooLarge(_) => "payload_too_large",
+            AppError::UnsupportedMediaType(_) => "unsupported_media_type",
+            AppError::QuotaExceeded(_) => "storage_quota_exceeded",
+        }
+    }
 }
 
 impl ResponseError for AppError {
@@ -29,6 +94,10 @@ impl ResponseError for AppError {
             AppError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::ValidationError(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::InvalidFields(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::QuotaExceeded(_) => StatusCode::INSUFFICIENT_STORAGE,
         }
     }
 
@@ -42,14 +111,74 @@ impl ResponseError for AppError {
             }
             _ => self.to_string(),
         };
+        let errors = match self {
+            AppError::InvalidFields(e) => field_errors(e),
+            _ => Vec::new(),
+        };
 
-        HttpResponse::build(status).json(json!({ "error": message }))
+        HttpResponse::build(status)
+            .content_type(PROBLEM_CONTENT_TYPE)
+            .json(ErrorResponse {
+                problem_type: "about:blank".to_string(),
+                title: status.canonical_reason().unwrap_or_default().to_string(),
+                status: status.as_u16(),
+                detail: message.clone(),
+                code: self.code().to_string(),
+                request_id: request_id::current(),
+                errors,
+                error: message,
+            })
     }
 }
 
+/// Field errors sorted by field, so responses are stable.
+fn field_errors(errors: &validator::ValidationErrors) -> Vec<FieldError> {
+    let mut fields: Vec<_> = errors.field_errors().into_iter().collect();
+    fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+    fields
+        .into_iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |e| FieldError {
+                field: field.to_string(),
+                code: e.code.to_string(),
+                message: e
+                    .message
+                    .as_ref()
+                    .map_or_else(|| format!("Invalid {field}"), |m| m.to_string()),
+            })
+        })
+        .collect()
+}
+
 impl From<jsonwebtoken::errors::Error> for AppError {
     fn from(e: jsonwebtoken::errors::Error) -> Self {
         tracing::debug!("JWT error: {e}");
         AppError::Unauthorized("Invalid or expired token".to_string())
     }
 }
+
+/// Error handler for `web::JsonConfig`.
+pub fn json_error(err: JsonPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    match err {
+        JsonPayloadError::ContentType => {
+            AppError::UnsupportedMediaType("Expected a JSON body".to_string())
+        }
+        JsonPayloadError::Overflow { .. } | JsonPayloadError::OverflowKnownLength { .. } => {
+            AppError::PayloadTooLarge("JSON body is too large".to_string())
+        }
+        e => AppError::BadRequest(format!("Invalid JSON body: {e}")),
+    }
+    .into()
+}
+
+/// Error handler for `web::PathConfig`. A malformed path segment names
+/// nothing, so it is a 404 like any other unknown path.
+pub fn path_error(err: PathError, _req: &HttpRequest) -> actix_web::Error {
+    tracing::debug!("Path extraction failed: {err}");
+    AppError::NotFound("Not found".to_string()).into()
+}
+
+/// Error handler for `web::QueryConfig`.
+pub fn query_error(err: QueryPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    AppError::BadRequest(format!("Invalid query string: {err}")).into()
+}
@@ -0,0 +1,71 @@
+//! Live board events.
+//!
+//! Services publish `BoardEvent`s with `NOTIFY` (see `event_service`), so an
+//! event reaches every server instance, whichever one handled the change.
+//! Each instance holds one `LISTEN` connection, opened when the first client
+//! subscribes, and fans incoming events out to its subscribers.
+
+use std::time::Duration;
+
+use sqlx::PgPool;
+use sqlx::postgres::PgListener;
+use tokio::sync::{OnceCell, broadcast};
+
+use crate::errors::AppError;
+use crate::models::event::{BoardEvent, CHANNEL};
+
+/// Events buffered per subscriber. A client that falls further behind is
+/// told to resync.
+const CAPACITY: usize = 256;
+
+pub struct EventHub {
+    pool: PgPool,
+    sender: broadcast::Sender<BoardEvent>,
+    listening: OnceCell<()>,
+}
+
+impl EventHub {
+    pub fn new(pool: PgPool) -> Self {
+        let (sender, _) = broadcast::channel(CAPACITY);
+        EventHub {
+            pool,
+            sender,
+            listening: OnceCell::new(),
+        }
+    }
+
+    /// Receive every event published from now on. Starts listening on the
+    /// first call; an error means the `LISTEN` connection could not be set up.
+    pub async fn subscribe(&self) -> Result<broadcast::Receiver<BoardEvent>, AppError> {
+        self.listening
+            .get_or_try_init(|| async {
+                let mut listener = PgListener::connect_with(&self.pool).await?;
+                listener.listen(CHANNEL).await?;
+                tokio::spawn(forward(listener, self.sender.clone()));
+                Ok::<_, AppError>(())
+            })
+            .await?;
+
+        Ok(self.sender.subscribe())
+    }
+}
+
+async fn forward(mut listener: PgListener, sender: broadcast::Sender<BoardEvent>) {
+    loop {
+        // `recv` reconnects and listens again after a lost connection;
+        // events published in between are missed.
+        match listener.recv().await {
+            Ok(notification) => match serde_json::from_str(notification.payload()) {
+                Ok(event) => {
+                    // No subscribers is fine
+                    let _ = sender.send(event);
+                }
+                Err(e) => tracing::warn!("Ignoring malformed board event: {e}"),
+            },
+            Err(e) => {
+                tracing::warn!("Board event listener failed: {e}");
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
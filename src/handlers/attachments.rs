@@ -0,0 +1,208 @@
+use actix_multipart::Multipart;
+use actix_web::http::header::{self, ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{HttpResponse, web};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::errors::{AppError, ErrorResponse};
+use crate::middleware::auth::AuthenticatedUser;
+use crate::models::attachment::AttachmentResponse;
+use crate::services::{
+    attachment_service, board_service, comment_service, org_service, post_service,
+};
+use crate::storage::Storage;
+use crate::utils::multipart;
+use crate::utils::multipart::FileUpload;
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/posts/{post_id}/attachments",
+    tag = "attachments",
+    params(
+        ("post_id" = Uuid, Path, description = "Post id"),
+    ),
+    request_body(content = FileUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Attachment stored", body = AttachmentResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+        (status = 413, description = "File too large", body = ErrorResponse),
+        (status = 415, description = "File type not allowed", body = ErrorResponse),
+        (status = 507, description = "Organization storage quota exceeded", body = ErrorResponse),
+    )
+)]
+pub async fn upload_post_attachment(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn Storage>,
+    config: web::Data<Config>,
+    auth: AuthenticatedUser,
+    post_id: web::Path<Uuid>,
+    payload: Multipart,
+) -> Result<HttpResponse, AppError> {
+    let post_id = post_id.into_inner();
+
+    // Verify user is org member
+    let post = post_service::get_post_raw(pool.get_ref(), post_id).await?;
+    let board = board_service::get_board_by_id(pool.get_ref(), post.board_id).await?;
+    org_service::get_member(pool.get_ref(), board.org_id, auth.user_id).await?;
+
+    let (filename, data) =
+        multipart::read_file_field(payload, config.storage.max_upload_bytes).await?;
+
+    let attachment = attachment_service::create_attachment(
+        pool.get_ref(),
+        storage.get_ref(),
+        board.org_id,
+        post_id,
+        None,
+        auth.user_id,
+        &filename,
+        &data,
+        config.storage.org_quota_bytes,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(AttachmentResponse::from(attachment)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/comments/{comment_id}/attachments",
+    tag = "attachments",
+    params(
+        ("comment_id" = Uuid, Path, description = "Comment id"),
+    ),
+    request_body(content = FileUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Attachment stored", body = AttachmentResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Only the comment author may attach files", body = ErrorResponse),
+        (status = 404, description = "Comment not found", body = ErrorResponse),
+        (status = 413, description = "File too large", body = ErrorResponse),
+        (status = 415, description = "File type not allowed", body = ErrorResponse),
+        (status = 507, description = "Organization storage quota exceeded", body = ErrorResponse),
+    )
+)]
+pub async fn upload_comment_attachment(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn Storage>,
+    config: web::Data<Config>,
+    auth: AuthenticatedUser,
+    comment_id: web::Path<Uuid>,
+    payload: Multipart,
+) -> Result<HttpResponse, AppError> {
+    let comment_id = comment_id.into_inner();
+
+    // Only the comment author can attach files to it
+    let comment = comment_service::get_comment_by_id(pool.get_ref(), comment_id).await?;
+    let post = post_service::get_post_raw(pool.get_ref(), comment.post_id).await?;
+    let board = board_service::get_board_by_id(pool.get_ref(), post.board_id).await?;
+    org_service::get_member(pool.get_ref(), board.org_id, auth.user_id).await?;
+    if comment.author_id != Some(auth.user_id) {
+        return Err(AppError::Forbidden(
+            "You can only attach files to your own comments".to_string(),
+        ));
+    }
+
+    let (filename, data) =
+        multipart::read_file_field(payload, config.storage.max_upload_bytes).await?;
+
+    let attachment = attachment_service::create_attachment(
+        pool.get_ref(),
+        storage.get_ref(),
+        board.org_id,
+        comment.post_id,
+        Some(comment_id),
+        auth.user_id,
+        &filename,
+        &data,
+        config.storage.org_quota_bytes,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(AttachmentResponse::from(attachment)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/attachments/{attachment_id}",
+    tag = "attachments",
+    params(
+        ("attachment_id" = Uuid, Path, description = "Attachment id"),
+    ),
+    responses(
+        (status = 200, description = "File contents, with the stored content type"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Attachment not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_attachment(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn Storage>,
+    auth: AuthenticatedUser,
+    attachment_id: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let attachment_id = attachment_id.into_inner();
+
+    let attachment =
+        attachment_service::get_attachment_by_id(pool.get_ref(), attachment_id).await?;
+    org_service::get_member(pool.get_ref(), attachment.org_id, auth.user_id).await?;
+
+    let data = storage.get(&attachment.storage_key).await?;
+
+    // Only images render inline; everything else downloads
+    let disposition_type = if attachment.content_type.starts_with("image/") {
+        DispositionType::Inline
+    } else {
+        DispositionType::Attachment
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type(attachment.content_type.as_str())
+        .insert_header(ContentDisposition {
+            disposition: disposition_type,
+            parameters: vec![DispositionParam::Filename(attachment.filename)],
+        })
+        .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+        .insert_header((header::CACHE_CONTROL, "private, max-age=3600"))
+        .body(data))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/attachments/{attachment_id}",
+    tag = "attachments",
+    params(
+        ("attachment_id" = Uuid, Path, description = "Attachment id"),
+    ),
+    responses(
+        (status = 204, description = "Attachment deleted"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Only the uploader or an admin may delete", body = ErrorResponse),
+        (status = 404, description = "Attachment not found", body = ErrorResponse),
+    )
+)]
+pub async fn delete_attachment(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn Storage>,
+    auth: AuthenticatedUser,
+    attachment_id: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let attachment_id = attachment_id.into_inner();
+
+    // Uploader or org admin
+    let attachment =
+        attachment_service::get_attachment_by_id(pool.get_ref(), attachment_id).await?;
+    let member = org_service::get_member(pool.get_ref(), attachment.org_id, auth.user_id).await?;
+    if attachment.uploader_id != Some(auth.user_id) && member.role.as_deref() != Some("admin") {
+        return Err(AppError::Forbidden(
+            "Only the uploader or an admin can delete this attachment".to_string(),
+        ));
+    }
+
+    attachment_service::delete_attachment(pool.get_ref(), storage.get_ref(), attachment_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -1,16 +1,19 @@
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
 use actix_web::{HttpResponse, web};
 use serde::Deserialize;
 use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
 use crate::config::Config;
-use crate::errors::AppError;
+use crate::errors::{AppError, ErrorResponse};
 use crate::middleware::auth::AuthenticatedUser;
 use crate::models::user::UserResponse;
-use crate::services::auth_service;
+use crate::services::{auth_service, gdpr_service, image_service};
+use crate::storage::Storage;
 use crate::utils::jwt;
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct RegisterRequest {
     #[validate(email(message = "Invalid email address"))]
     pub email: String,
@@ -28,7 +31,7 @@ pub struct RegisterRequest {
     pub password: String,
 }
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct LoginRequest {
     #[validate(email(message = "Invalid email address"))]
     pub email: String,
@@ -36,24 +39,87 @@ pub struct LoginRequest {
     pub password: String,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct UpdateProfileRequest {
+    #[validate(length(
+        min = 2,
+        max = 255,
+        message = "Name must be between 2 and 255 characters"
+    ))]
+    pub name: String,
+    /// Replaces the current avatar; `null` removes it. Use
+    /// `POST /auth/me/avatar` to upload a new image instead of linking one.
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct ChangePasswordRequest {
+    #[validate(length(min = 1, message = "Current password is required"))]
+    pub current_password: String,
+    #[validate(length(
+        min = 8,
+        max = 128,
+        message = "Password must be between 8 and 128 characters"
+    ))]
+    pub new_password: String,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct ChangeEmailRequest {
+    #[validate(email(message = "Invalid email address"))]
+    pub new_email: String,
+    #[validate(length(min = 1, message = "Password is required"))]
+    pub password: String,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct ConfirmEmailRequest {
+    #[validate(length(min = 1, message = "Token is required"))]
+    pub token: String,
+}
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct DeleteAccountRequest {
+    #[validate(length(min = 1, message = "Password is required"))]
+    pub password: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// `json` (default) or `zip`
+    pub format: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, ToSchema)]
 pub struct AuthResponse {
     pub token: String,
     pub user: UserResponse,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/register",
+    tag = "auth",
+    request_body = RegisterRequest,
+    security(()),
+    responses(
+        (status = 201, description = "Account created", body = AuthResponse),
+        (status = 400, description = "Email already registered", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
 pub async fn register(
     pool: web::Data<PgPool>,
     config: web::Data<Config>,
     body: web::Json<RegisterRequest>,
 ) -> Result<HttpResponse, AppError> {
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    body.validate()?;
 
     let user = auth_service::register_user(pool.get_ref(), &body.email, &body.name, &body.password)
         .await?;
 
-    let token = jwt::encode_token(user.id, &config.jwt_secret)
+    let token = jwt::encode_token(&user, &config.auth.jwt_secret)
         .map_err(|e| AppError::InternalError(format!("Token generation failed: {e}")))?;
 
     Ok(HttpResponse::Created().json(AuthResponse {
@@ -62,17 +128,28 @@ pub async fn register(
     }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    security(()),
+    responses(
+        (status = 200, description = "Logged in", body = AuthResponse),
+        (status = 401, description = "Wrong email or password", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
 pub async fn login(
     pool: web::Data<PgPool>,
     config: web::Data<Config>,
     body: web::Json<LoginRequest>,
 ) -> Result<HttpResponse, AppError> {
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    body.validate()?;
 
     let user = auth_service::login_user(pool.get_ref(), &body.email, &body.password).await?;
 
-    let token = jwt::encode_token(user.id, &config.jwt_secret)
+    let token = jwt::encode_token(&user, &config.auth.jwt_secret)
         .map_err(|e| AppError::InternalError(format!("Token generation failed: {e}")))?;
 
     Ok(HttpResponse::Ok().json(AuthResponse {
@@ -81,6 +158,15 @@ pub async fn login(
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/me",
+    tag = "auth",
+    responses(
+        (status = 200, description = "The current user", body = UserResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+    )
+)]
 pub async fn me(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -89,3 +175,219 @@ pub async fn me(
 
     Ok(HttpResponse::Ok().json(UserResponse::from(user)))
 }
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/auth/me",
+    tag = "auth",
+    request_body = UpdateProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated", body = UserResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
+pub async fn update_me(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn Storage>,
+    auth: AuthenticatedUser,
+    body: web::Json<UpdateProfileRequest>,
+) -> Result<HttpResponse, AppError> {
+    body.validate()?;
+
+    let current = auth_service::get_user_by_id(pool.get_ref(), auth.user_id).await?;
+    let avatar_changed = body.avatar_url != current.avatar_url;
+    if avatar_changed
+        && let Some(url) = &body.avatar_url
+        && !(url.starts_with("https://") || url.starts_with("http://"))
+    {
+        return Err(AppError::ValidationError(
+            "Avatar URL must be an http(s) URL".to_string(),
+        ));
+    }
+
+    let mut user = auth_service::update_profile(pool.get_ref(), auth.user_id, &body.name).await?;
+    if avatar_changed {
+        user = image_service::set_user_avatar(
+            pool.get_ref(),
+            storage.get_ref(),
+            auth.user_id,
+            body.avatar_url.as_deref(),
+        )
+        .await?;
+    }
+
+    Ok(HttpResponse::Ok().json(UserResponse::from(user)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/auth/me/password",
+    tag = "auth",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, description = "Password changed; other tokens are revoked and a new one is returned", body = AuthResponse),
+        (status = 400, description = "Wrong current password", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
+pub async fn change_password(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    auth: AuthenticatedUser,
+    body: web::Json<ChangePasswordRequest>,
+) -> Result<HttpResponse, AppError> {
+    body.validate()?;
+
+    let user = auth_service::change_password(
+        pool.get_ref(),
+        auth.user_id,
+        &body.current_password,
+        &body.new_password,
+    )
+    .await?;
+
+    let token = jwt::encode_token(&user, &config.auth.jwt_secret)
+        .map_err(|e| AppError::InternalError(format!("Token generation failed: {e}")))?;
+
+    Ok(HttpResponse::Ok().json(AuthResponse {
+        token,
+        user: user.into(),
+    }))
+}
+
+/// Send a confirmation link to the new address. The email only changes once
+/// that link is followed.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/me/email",
+    tag = "auth",
+    request_body = ChangeEmailRequest,
+    responses(
+        (status = 202, description = "Confirmation link queued for the new address"),
+        (status = 400, description = "Wrong password, or email already in use", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
+pub async fn request_email_change(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    auth: AuthenticatedUser,
+    body: web::Json<ChangeEmailRequest>,
+) -> Result<HttpResponse, AppError> {
+    body.validate()?;
+
+    auth_service::request_email_change(
+        pool.get_ref(),
+        &config.server.app_url,
+        auth.user_id,
+        &body.new_email,
+        &body.password,
+    )
+    .await?;
+
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/email/confirm",
+    tag = "auth",
+    request_body = ConfirmEmailRequest,
+    security(()),
+    responses(
+        (status = 200, description = "Email changed", body = UserResponse),
+        (status = 400, description = "Invalid or expired token", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
+pub async fn confirm_email_change(
+    pool: web::Data<PgPool>,
+    body: web::Json<ConfirmEmailRequest>,
+) -> Result<HttpResponse, AppError> {
+    body.validate()?;
+
+    let user = auth_service::confirm_email_change(pool.get_ref(), &body.token).await?;
+
+    Ok(HttpResponse::Ok().json(UserResponse::from(user)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/auth/me",
+    tag = "auth",
+    request_body = DeleteAccountRequest,
+    responses(
+        (status = 202, description = "Account erasure queued"),
+        (status = 400, description = "Wrong password, or the only admin of a shared organization", body = ErrorResponse),
+        (status = 401, description = "Missing token", body = ErrorResponse),
+    )
+)]
+pub async fn delete_me(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<DeleteAccountRequest>,
+) -> Result<HttpResponse, AppError> {
+    body.validate()?;
+
+    auth_service::delete_account(pool.get_ref(), auth.user_id, &body.password).await?;
+
+    Ok(HttpResponse::Accepted().finish())
+}
+
+/// Download everything stored about the current user (GDPR Art. 15/20).
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/me/export",
+    tag = "auth",
+    params(
+        ExportQuery,
+    ),
+    responses(
+        (status = 200, description = "Everything stored about the user, as JSON or a ZIP archive"),
+        (status = 400, description = "Unknown format", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+    )
+)]
+pub async fn export_me(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<ExportQuery>,
+) -> Result<HttpResponse, AppError> {
+    let zip = match query.format.as_deref() {
+        None | Some("json") => false,
+        Some("zip") => true,
+        Some(other) => {
+            return Err(AppError::BadRequest(format!(
+                "Unknown export format: {other}. Must be json or zip"
+            )));
+        }
+    };
+
+    let export = gdpr_service::export_user_data(pool.get_ref(), auth.user_id).await?;
+    let date = export.exported_at.format("%Y-%m-%d");
+
+    let (body, content_type, extension) = if zip {
+        (
+            gdpr_service::export_to_zip(&export)?,
+            "application/zip",
+            "zip",
+        )
+    } else {
+        let json = serde_json::to_vec_pretty(&export)
+            .map_err(|e| AppError::InternalError(format!("Export serialization failed: {e}")))?;
+        (json, "application/json", "json")
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "rivvo-export-{date}.{extension}"
+            ))],
+        })
+        .body(body))
+}
@@ -1,15 +1,23 @@
+use actix_multipart::Multipart;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
 use actix_web::{HttpResponse, web};
 use serde::Deserialize;
 use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::errors::AppError;
+use crate::config::Config;
+use crate::errors::{AppError, ErrorResponse};
 use crate::middleware::auth::AuthenticatedUser;
 use crate::models::board::BoardResponse;
-use crate::services::{board_service, org_service};
+use crate::models::board_transfer::ImportReport;
+use crate::services::{attachment_service, board_service, board_transfer_service, org_service};
+use crate::storage::{self, Storage};
+use crate::utils::multipart;
+use crate::utils::multipart::FileUpload;
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct CreateBoardRequest {
     #[validate(length(
         min = 1,
@@ -20,7 +28,7 @@ pub struct CreateBoardRequest {
     pub description: Option<String>,
 }
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct UpdateBoardRequest {
     #[validate(length(
         min = 1,
@@ -31,6 +39,35 @@ pub struct UpdateBoardRequest {
     pub description: Option<String>,
 }
 
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// `json` (default) or `csv`
+    pub format: Option<String>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    /// `json` or `csv`; taken from the file extension when omitted
+    pub format: Option<String>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org_id}/boards",
+    tag = "boards",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+    ),
+    responses(
+        (status = 200, description = "Boards with post counts", body = Vec<BoardResponse>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+    )
+)]
 pub async fn list_boards(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -45,6 +82,21 @@ pub async fn list_boards(
     Ok(HttpResponse::Ok().json(response))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/orgs/{org_id}/boards",
+    tag = "boards",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+    ),
+    request_body = CreateBoardRequest,
+    responses(
+        (status = 201, description = "Board created", body = BoardResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
 pub async fn create_board(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -52,8 +104,7 @@ pub async fn create_board(
     body: web::Json<CreateBoardRequest>,
 ) -> Result<HttpResponse, AppError> {
     let org_id = org_id.into_inner();
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    body.validate()?;
     org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
 
     let board = board_service::create_board(
@@ -73,6 +124,21 @@ pub async fn create_board(
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org_id}/boards/{slug}",
+    tag = "boards",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        ("slug" = String, Path, description = "Board slug"),
+    ),
+    responses(
+        (status = 200, description = "The board", body = BoardResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Board not found", body = ErrorResponse),
+    )
+)]
 pub async fn get_board(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -92,6 +158,23 @@ pub async fn get_board(
     }))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/orgs/{org_id}/boards/{slug}",
+    tag = "boards",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        ("slug" = String, Path, description = "Board slug"),
+    ),
+    request_body = UpdateBoardRequest,
+    responses(
+        (status = 200, description = "Board updated", body = BoardResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Board not found", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
 pub async fn update_board(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -99,8 +182,7 @@ pub async fn update_board(
     body: web::Json<UpdateBoardRequest>,
 ) -> Result<HttpResponse, AppError> {
     let (org_id, slug) = path.into_inner();
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    body.validate()?;
     org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
 
     let board = board_service::get_board_by_slug(pool.get_ref(), org_id, &slug).await?;
@@ -121,8 +203,24 @@ pub async fn update_board(
     }))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/orgs/{org_id}/boards/{slug}",
+    tag = "boards",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        ("slug" = String, Path, description = "Board slug"),
+    ),
+    responses(
+        (status = 204, description = "Board deleted"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Board not found", body = ErrorResponse),
+    )
+)]
 pub async fn delete_board(
     pool: web::Data<PgPool>,
+    storage: web::Data<dyn Storage>,
     auth: AuthenticatedUser,
     path: web::Path<(Uuid, String)>,
 ) -> Result<HttpResponse, AppError> {
@@ -130,7 +228,139 @@ pub async fn delete_board(
     org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
 
     let board = board_service::get_board_by_slug(pool.get_ref(), org_id, &slug).await?;
+    let storage_keys =
+        attachment_service::get_storage_keys_for_board(pool.get_ref(), board.id).await?;
     board_service::delete_board(pool.get_ref(), board.id).await?;
+    storage::delete_all(storage.get_ref(), &storage_keys).await;
 
     Ok(HttpResponse::NoContent().finish())
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org_id}/boards/{slug}/export",
+    tag = "boards",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        ("slug" = String, Path, description = "Board slug"),
+        ExportQuery,
+    ),
+    responses(
+        (status = 200, description = "All posts of the board as a JSON or CSV download"),
+        (status = 400, description = "Unknown format", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Board not found", body = ErrorResponse),
+    )
+)]
+pub async fn export_board(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<(Uuid, String)>,
+    query: web::Query<ExportQuery>,
+) -> Result<HttpResponse, AppError> {
+    let (org_id, slug) = path.into_inner();
+    let csv = match query.format.as_deref() {
+        None | Some("json") => false,
+        Some("csv") => true,
+        Some(other) => {
+            return Err(AppError::BadRequest(format!(
+                "Unknown export format: {other}. Must be json or csv"
+            )));
+        }
+    };
+    org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
+
+    let board = board_service::get_board_by_slug(pool.get_ref(), org_id, &slug).await?;
+    let export = board_transfer_service::export_board(pool.get_ref(), &board).await?;
+    let date = export.exported_at.format("%Y-%m-%d");
+
+    let (body, content_type, extension) = if csv {
+        (
+            board_transfer_service::export_to_csv(&export)?,
+            "text/csv; charset=utf-8",
+            "csv",
+        )
+    } else {
+        let json = serde_json::to_vec_pretty(&export)
+            .map_err(|e| AppError::InternalError(format!("Export serialization failed: {e}")))?;
+        (json, "application/json", "json")
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "{}-{date}.{extension}",
+                board.slug
+            ))],
+        })
+        .body(body))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/orgs/{org_id}/boards/{slug}/import",
+    tag = "boards",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        ("slug" = String, Path, description = "Board slug"),
+        ImportQuery,
+    ),
+    request_body(content = FileUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Imported, or validated with `dry_run`", body = ImportReport),
+        (status = 400, description = "Unreadable file", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Board not found", body = ErrorResponse),
+        (status = 422, description = "Some rows failed validation; nothing was written", body = ImportReport),
+    )
+)]
+pub async fn import_board(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    auth: AuthenticatedUser,
+    path: web::Path<(Uuid, String)>,
+    query: web::Query<ImportQuery>,
+    payload: Multipart,
+) -> Result<HttpResponse, AppError> {
+    let (org_id, slug) = path.into_inner();
+    org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
+    let board = board_service::get_board_by_slug(pool.get_ref(), org_id, &slug).await?;
+
+    let (filename, data) =
+        multipart::read_file_field(payload, config.storage.max_upload_bytes).await?;
+    let format = match query.format.as_deref() {
+        Some(format) => format.to_lowercase(),
+        None => filename
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_lowercase())
+            .unwrap_or_default(),
+    };
+    let posts = match format.as_str() {
+        "json" => board_transfer_service::parse_json(&data)?,
+        "csv" => board_transfer_service::parse_csv(&data)?,
+        _ => {
+            return Err(AppError::BadRequest(
+                "Import format must be json or csv".to_string(),
+            ));
+        }
+    };
+
+    let report = board_transfer_service::import_posts(
+        pool.get_ref(),
+        org_id,
+        board.id,
+        posts,
+        query.dry_run,
+    )
+    .await?;
+
+    if report.valid {
+        Ok(HttpResponse::Ok().json(report))
+    } else {
+        Ok(HttpResponse::UnprocessableEntity().json(report))
+    }
+}
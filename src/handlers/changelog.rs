@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use actix_web::{HttpResponse, web};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::errors::{AppError, ErrorResponse};
+use crate::middleware::auth::AuthenticatedUser;
+use crate::models::changelog::{
+    ChangelogEntry, ChangelogEntryResponse, ChangelogInput, ChangelogPostRef, STATUS_PUBLISHED,
+};
+use crate::services::{changelog_service, org_service};
+use crate::utils::markdown;
+
+#[derive(Deserialize, Validate, ToSchema)]
+pub struct ChangelogEntryRequest {
+    #[validate(length(
+        min = 1,
+        max = 500,
+        message = "Title must be between 1 and 500 characters"
+    ))]
+    pub title: String,
+    #[validate(length(min = 1, message = "Body is required"))]
+    pub body: String,
+    /// Drafts are only visible to admins. Defaults to `true`.
+    pub is_draft: Option<bool>,
+    /// When the entry goes public. A future time schedules it; omitted
+    /// means now once `is_draft` is `false`.
+    pub published_at: Option<DateTime<Utc>>,
+    /// Move linked posts to `done` at publish time
+    #[serde(default)]
+    pub complete_posts: bool,
+    #[serde(default)]
+    pub post_ids: Vec<Uuid>,
+}
+
+impl ChangelogEntryRequest {
+    fn input(&self) -> ChangelogInput<'_> {
+        ChangelogInput {
+            title: &self.title,
+            body: &self.body,
+            is_draft: self.is_draft.unwrap_or(true),
+            published_at: self.published_at,
+            complete_posts: self.complete_posts,
+            post_ids: &self.post_ids,
+        }
+    }
+}
+
+/// Published entries, newest first, for members of the organization.
+/// Admins also see drafts and scheduled entries.
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org_id}/changelog",
+    tag = "changelog",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+    ),
+    responses(
+        (status = 200, description = "Entries, newest first", body = Vec<ChangelogEntryResponse>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of this organization", body = ErrorResponse),
+    )
+)]
+pub async fn list_entries(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    org_id: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let org_id = org_id.into_inner();
+    // Members only, since entries link posts; admins also see unpublished ones
+    let member = org_service::get_member(pool.get_ref(), org_id, auth.user_id).await?;
+    let is_admin = member.role.as_deref() == Some("admin");
+
+    let entries = changelog_service::get_entries(pool.get_ref(), org_id, is_admin).await?;
+    let ids: Vec<Uuid> = entries.iter().map(|e| e.id).collect();
+    let mut posts: HashMap<Uuid, Vec<ChangelogPostRef>> = HashMap::new();
+    for post in changelog_service::get_linked_posts(pool.get_ref(), &ids).await? {
+        posts.entry(post.changelog_id).or_default().push(post);
+    }
+
+    let now = Utc::now();
+    let response: Vec<ChangelogEntryResponse> = entries
+        .into_iter()
+        .map(|entry| {
+            let linked = posts.remove(&entry.id).unwrap_or_default();
+            build_response(entry, linked, now)
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org_id}/changelog/{entry_id}",
+    tag = "changelog",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        ("entry_id" = Uuid, Path, description = "Changelog entry id"),
+    ),
+    responses(
+        (status = 200, description = "The entry", body = ChangelogEntryResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of this organization", body = ErrorResponse),
+        (status = 404, description = "Entry not found or not published", body = ErrorResponse),
+    )
+)]
+pub async fn get_entry(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, AppError> {
+    let (org_id, entry_id) = path.into_inner();
+    // Members only, since entries link posts; admins also see unpublished ones
+    let member = org_service::get_member(pool.get_ref(), org_id, auth.user_id).await?;
+    let is_admin = member.role.as_deref() == Some("admin");
+
+    let entry = changelog_service::get_entry(pool.get_ref(), org_id, entry_id).await?;
+    let now = Utc::now();
+    if !is_admin && entry.status(now) != STATUS_PUBLISHED {
+        return Err(AppError::NotFound("Changelog entry not found".to_string()));
+    }
+
+    let posts = changelog_service::get_linked_posts(pool.get_ref(), &[entry.id]).await?;
+    Ok(HttpResponse::Ok().json(build_response(entry, posts, now)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/orgs/{org_id}/changelog",
+    tag = "changelog",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+    ),
+    request_body = ChangelogEntryRequest,
+    responses(
+        (status = 201, description = "Entry created", body = ChangelogEntryResponse),
+        (status = 400, description = "Linked posts outside the organization", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
+pub async fn create_entry(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    org_id: web::Path<Uuid>,
+    body: web::Json<ChangelogEntryRequest>,
+) -> Result<HttpResponse, AppError> {
+    let org_id = org_id.into_inner();
+    body.validate()?;
+    org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
+
+    let entry = changelog_service::create_entry(pool.get_ref(), org_id, &body.input()).await?;
+    let posts = changelog_service::get_linked_posts(pool.get_ref(), &[entry.id]).await?;
+
+    Ok(HttpResponse::Created().json(build_response(entry, posts, Utc::now())))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/orgs/{org_id}/changelog/{entry_id}",
+    tag = "changelog",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        ("entry_id" = Uuid, Path, description = "Changelog entry id"),
+    ),
+    request_body = ChangelogEntryRequest,
+    responses(
+        (status = 200, description = "Entry updated", body = ChangelogEntryResponse),
+        (status = 400, description = "Linked posts outside the organization", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Entry not found", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
+pub async fn update_entry(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<(Uuid, Uuid)>,
+    body: web::Json<ChangelogEntryRequest>,
+) -> Result<HttpResponse, AppError> {
+    let (org_id, entry_id) = path.into_inner();
+    body.validate()?;
+    org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
+
+    let entry =
+        changelog_service::update_entry(pool.get_ref(), org_id, entry_id, &body.input()).await?;
+    let posts = changelog_service::get_linked_posts(pool.get_ref(), &[entry.id]).await?;
+
+    Ok(HttpResponse::Ok().json(build_response(entry, posts, Utc::now())))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/orgs/{org_id}/changelog/{entry_id}",
+    tag = "changelog",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        ("entry_id" = Uuid, Path, description = "Changelog entry id"),
+    ),
+    responses(
+        (status = 204, description = "Entry deleted"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Entry not found", body = ErrorResponse),
+    )
+)]
+pub async fn delete_entry(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, AppError> {
+    let (org_id, entry_id) = path.into_inner();
+    org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
+
+    changelog_service::delete_entry(pool.get_ref(), org_id, entry_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+fn build_response(
+    entry: ChangelogEntry,
+    posts: Vec<ChangelogPostRef>,
+    now: DateTime<Utc>,
+) -> ChangelogEntryResponse {
+    ChangelogEntryResponse {
+        id: entry.id,
+        status: entry.status(now),
+        body_html: markdown::render_html(&entry.body),
+        body_markdown: entry.body,
+        title: entry.title,
+        published_at: entry.published_at,
+        complete_posts: entry.complete_posts,
+        announced_at: entry.announced_at,
+        posts,
+        created_at: entry.created_at,
+        updated_at: entry.updated_at,
+    }
+}
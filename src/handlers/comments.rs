@@ -1,15 +1,23 @@
 use actix_web::{HttpResponse, web};
 use serde::Deserialize;
 use sqlx::PgPool;
+use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::errors::AppError;
+use crate::errors::{AppError, ErrorResponse};
 use crate::middleware::auth::AuthenticatedUser;
+use crate::models::attachment::AttachmentResponse;
 use crate::models::comment::{CommentAuthor, CommentResponse};
-use crate::services::{board_service, comment_service, org_service, post_service};
+use crate::models::reaction::ReactionTarget;
+use crate::services::{
+    attachment_service, board_service, comment_service, mention_service, org_service, post_service,
+    reaction_service,
+};
+use crate::storage::{self, Storage};
+use crate::utils::markdown;
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct CreateCommentRequest {
     #[validate(length(
         min = 1,
@@ -19,6 +27,20 @@ pub struct CreateCommentRequest {
     pub body: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/posts/{post_id}/comments",
+    tag = "comments",
+    params(
+        ("post_id" = Uuid, Path, description = "Post id"),
+    ),
+    responses(
+        (status = 200, description = "Comments, oldest first", body = Vec<CommentResponse>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+    )
+)]
 pub async fn list_comments(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -33,17 +55,40 @@ pub async fn list_comments(
 
     let rows = comment_service::get_comments(pool.get_ref(), post_id).await?;
 
+    // Fetch reactions for all comments in a single batch query
+    let comment_ids: Vec<Uuid> = rows.iter().map(|r| r.id).collect();
+    let mut reactions_map = reaction_service::get_reactions_for_target_ids(
+        pool.get_ref(),
+        ReactionTarget::Comment,
+        &comment_ids,
+        auth.user_id,
+    )
+    .await?;
+    let mut mentions_map =
+        mention_service::get_mentions_for_comment_ids(pool.get_ref(), &comment_ids).await?;
+    let mut attachments_map =
+        attachment_service::get_attachments_for_comment_ids(pool.get_ref(), &comment_ids).await?;
+
     let response: Vec<CommentResponse> = rows
         .into_iter()
         .map(|row| CommentResponse {
             id: row.id,
-            body: row.body,
+            body_html: markdown::render_html(&row.body),
+            body_markdown: row.body,
+            mentions: mentions_map.remove(&row.id).unwrap_or_default(),
             is_admin_reply: row.is_admin_reply.unwrap_or(false),
             author: row.author_id.map(|id| CommentAuthor {
                 id,
                 name: row.author_name.unwrap_or_default(),
                 avatar_url: row.author_avatar_url,
             }),
+            reactions: reactions_map.remove(&row.id).unwrap_or_default(),
+            attachments: attachments_map
+                .remove(&row.id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(AttachmentResponse::from)
+                .collect(),
             created_at: row.created_at,
         })
         .collect();
@@ -51,6 +96,22 @@ pub async fn list_comments(
     Ok(HttpResponse::Ok().json(response))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/posts/{post_id}/comments",
+    tag = "comments",
+    params(
+        ("post_id" = Uuid, Path, description = "Post id"),
+    ),
+    request_body = CreateCommentRequest,
+    responses(
+        (status = 201, description = "Comment created", body = CommentResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
 pub async fn create_comment(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -58,8 +119,7 @@ pub async fn create_comment(
     body: web::Json<CreateCommentRequest>,
 ) -> Result<HttpResponse, AppError> {
     let post_id = post_id.into_inner();
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    body.validate()?;
 
     // Verify membership and check admin status
     let post = post_service::get_post_raw(pool.get_ref(), post_id).await?;
@@ -69,28 +129,52 @@ pub async fn create_comment(
 
     let comment = comment_service::create_comment(
         pool.get_ref(),
+        board.org_id,
         post_id,
         auth.user_id,
         &body.body,
         is_admin,
     )
     .await?;
+    let mentions = mention_service::get_mentions_for_comment_ids(pool.get_ref(), &[comment.id])
+        .await?
+        .remove(&comment.id)
+        .unwrap_or_default();
 
     Ok(HttpResponse::Created().json(CommentResponse {
         id: comment.id,
-        body: comment.body,
+        body_html: markdown::render_html(&comment.body),
+        body_markdown: comment.body,
+        mentions,
         is_admin_reply: comment.is_admin_reply.unwrap_or(false),
         author: Some(CommentAuthor {
             id: auth.user_id,
             name: String::new(), // Will be filled by frontend from auth state
             avatar_url: None,
         }),
+        reactions: Vec::new(),
+        attachments: Vec::new(),
         created_at: comment.created_at,
     }))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/comments/{comment_id}",
+    tag = "comments",
+    params(
+        ("comment_id" = Uuid, Path, description = "Comment id"),
+    ),
+    responses(
+        (status = 204, description = "Comment deleted"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Only the author or an admin may delete", body = ErrorResponse),
+        (status = 404, description = "Comment not found", body = ErrorResponse),
+    )
+)]
 pub async fn delete_comment(
     pool: web::Data<PgPool>,
+    storage: web::Data<dyn Storage>,
     auth: AuthenticatedUser,
     comment_id: web::Path<Uuid>,
 ) -> Result<HttpResponse, AppError> {
@@ -103,6 +187,8 @@ pub async fn delete_comment(
     let member = org_service::get_member(pool.get_ref(), board.org_id, auth.user_id).await?;
     let is_admin = member.role.as_deref() == Some("admin");
 
+    let storage_keys =
+        attachment_service::get_storage_keys_for_comment(pool.get_ref(), comment_id).await?;
     comment_service::delete_comment(
         pool.get_ref(),
         comment_id,
@@ -111,5 +197,7 @@ pub async fn delete_comment(
         comment.post_id,
     )
     .await?;
+    storage::delete_all(storage.get_ref(), &storage_keys).await;
+
     Ok(HttpResponse::NoContent().finish())
 }
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::web::Bytes;
+use actix_web::{HttpResponse, web};
+use futures_util::stream;
+use sqlx::PgPool;
+use tokio::sync::broadcast::{self, error::RecvError};
+use uuid::Uuid;
+
+use crate::errors::{AppError, ErrorResponse};
+use crate::events::EventHub;
+use crate::middleware::auth::AuthenticatedUser;
+use crate::models::event::BoardEvent;
+use crate::services::{board_service, org_service, post_service};
+
+/// Idle connections get a comment line this often, so proxies keep them open
+/// and dead clients are noticed.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Server-Sent Events for everything happening on a board. Same access rule
+/// as listing its posts: any org member.
+#[utoipa::path(
+    get,
+    path = "/api/v1/boards/{board_id}/events",
+    tag = "events",
+    params(
+        ("board_id" = Uuid, Path, description = "Board id"),
+    ),
+    responses(
+        (status = 200, description = "Server-Sent Events stream; each event's data is a `BoardEvent`", content_type = "text/event-stream"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Board not found", body = ErrorResponse),
+    )
+)]
+pub async fn board_events(
+    pool: web::Data<PgPool>,
+    hub: web::Data<EventHub>,
+    auth: AuthenticatedUser,
+    board_id: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let board_id = board_id.into_inner();
+
+    let board = board_service::get_board_by_id(pool.get_ref(), board_id).await?;
+    org_service::get_member(pool.get_ref(), board.org_id, auth.user_id).await?;
+
+    let events = hub.subscribe().await?;
+    Ok(event_stream(events, move |event| {
+        event.board_id == board_id
+    }))
+}
+
+/// Server-Sent Events for a single post.
+#[utoipa::path(
+    get,
+    path = "/api/v1/posts/{post_id}/events",
+    tag = "events",
+    params(
+        ("post_id" = Uuid, Path, description = "Post id"),
+    ),
+    responses(
+        (status = 200, description = "Server-Sent Events stream; each event's data is a `BoardEvent`", content_type = "text/event-stream"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+    )
+)]
+pub async fn post_events(
+    pool: web::Data<PgPool>,
+    hub: web::Data<EventHub>,
+    auth: AuthenticatedUser,
+    post_id: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let post_id = post_id.into_inner();
+
+    let post = post_service::get_post_raw(pool.get_ref(), post_id).await?;
+    let board = board_service::get_board_by_id(pool.get_ref(), post.board_id).await?;
+    org_service::get_member(pool.get_ref(), board.org_id, auth.user_id).await?;
+
+    let events = hub.subscribe().await?;
+    Ok(event_stream(events, move |event| event.post_id == post_id))
+}
+
+fn event_stream(
+    events: broadcast::Receiver<BoardEvent>,
+    wanted: impl Fn(&BoardEvent) -> bool + 'static,
+) -> HttpResponse {
+    // Ask clients to reconnect after 3s if the connection drops
+    let hello = stream::once(async { Ok::<_, AppError>(Bytes::from_static(b"retry: 3000\n\n")) });
+
+    let body = stream::unfold((events, wanted), |(mut events, wanted)| {
+        async move {
+            loop {
+                let frame = match tokio::time::timeout(KEEPALIVE_INTERVAL, events.recv()).await {
+                    Err(_) => ": keepalive\n\n".to_string(),
+                    Ok(Ok(event)) if wanted(&event) => match serde_json::to_string(&event) {
+                        Ok(data) => format!("event: {}\ndata: {data}\n\n", event.name()),
+                        Err(e) => {
+                            tracing::warn!("Failed to serialize board event: {e}");
+                            continue;
+                        }
+                    },
+                    Ok(Ok(_)) => continue,
+                    // Missed events; the client should refetch what it shows
+                    Ok(Err(RecvError::Lagged(_))) => "event: resync\ndata: {}\n\n".to_string(),
+                    Ok(Err(RecvError::Closed)) => return None,
+                };
+                return Some((Ok(Bytes::from(frame)), (events, wanted)));
+            }
+        }
+    });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+        // Stop nginx from buffering the stream
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(futures_util::StreamExt::chain(hello, body))
+}
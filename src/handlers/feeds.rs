@@ -0,0 +1,270 @@
+use std::time::SystemTime;
+
+use actix_web::http::header::{
+    self, CacheControl, CacheDirective, ETag, EntityTag, Header, HttpDate, IfModifiedSince,
+    IfNoneMatch, LastModified,
+};
+use actix_web::{HttpRequest, HttpResponse, web};
+use chrono::{DateTime, SubsecRound};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::errors::{AppError, ErrorResponse};
+use crate::models::board::Board;
+use crate::services::{board_service, feed_service, org_service};
+use crate::utils::feed::{self, Feed, FeedFormat, FeedItem};
+use crate::utils::markdown;
+
+/// Published changelog entries of an organization. Public: unpublished
+/// entries never appear.
+#[utoipa::path(
+    get,
+    path = "/api/v1/feeds/{org}/changelog.{format}",
+    tag = "feeds",
+    params(
+        ("org" = String, Path, description = "Organization id or slug"),
+        ("format" = String, Path, description = "`atom` or `rss`"),
+    ),
+    security(()),
+    responses(
+        (status = 200, description = "Atom or RSS 2.0 document", content_type = "application/atom+xml"),
+        (status = 304, description = "Unchanged since `If-None-Match` / `If-Modified-Since`"),
+        (status = 404, description = "Organization not found or unknown format", body = ErrorResponse),
+    )
+)]
+pub async fn changelog_feed(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, AppError> {
+    let (org, format) = path.into_inner();
+    let format = parse_format(&format)?;
+    let org = org_service::find_org(pool.get_ref(), &org).await?;
+
+    let entries = feed_service::get_changelog_entries(pool.get_ref(), org.id).await?;
+    let items: Vec<FeedItem> = entries
+        .into_iter()
+        .map(|entry| {
+            let published = entry.published_at.unwrap_or(DateTime::UNIX_EPOCH);
+            FeedItem {
+                id: format!("urn:uuid:{}", entry.id),
+                link: format!("{}/changelog#{}", config.server.app_url, entry.id),
+                content_html: markdown::render_html(&entry.body),
+                title: entry.title,
+                author: None,
+                published,
+                updated: entry.updated_at.unwrap_or(published).max(published),
+            }
+        })
+        .collect();
+
+    let feed = build_feed(
+        format!("urn:rivvo:org:{}:changelog", org.id),
+        format!("{} changelog", org.name),
+        format!("{}/changelog", config.server.app_url),
+        &req,
+        items,
+    );
+    Ok(respond(&req, &feed, format))
+}
+
+/// Newest posts of a board.
+#[utoipa::path(
+    get,
+    path = "/api/v1/feeds/{org}/boards/{slug}/posts.{format}",
+    tag = "feeds",
+    params(
+        ("org" = String, Path, description = "Organization id or slug"),
+        ("slug" = String, Path, description = "Board slug"),
+        ("format" = String, Path, description = "`atom` or `rss`"),
+    ),
+    security(()),
+    responses(
+        (status = 200, description = "Atom or RSS 2.0 document", content_type = "application/atom+xml"),
+        (status = 304, description = "Unchanged since `If-None-Match` / `If-Modified-Since`"),
+        (status = 404, description = "Board not found, private, or unknown format", body = ErrorResponse),
+    )
+)]
+pub async fn board_posts_feed(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    req: HttpRequest,
+    path: web::Path<(String, String, String)>,
+) -> Result<HttpResponse, AppError> {
+    let (org, slug, format) = path.into_inner();
+    let format = parse_format(&format)?;
+    let board = find_public_board(pool.get_ref(), &org, &slug).await?;
+
+    let posts = feed_service::get_new_posts(pool.get_ref(), board.id).await?;
+    let items: Vec<FeedItem> = posts
+        .into_iter()
+        .map(|post| FeedItem {
+            id: format!("urn:uuid:{}", post.id),
+            link: format!("{}/posts/{}", config.server.app_url, post.id),
+            content_html: markdown::render_html(post.description.as_deref().unwrap_or("")),
+            title: post.title,
+            author: post.author_name,
+            published: post.created_at,
+            updated: post.updated_at.max(post.created_at),
+        })
+        .collect();
+
+    let feed = build_feed(
+        format!("urn:rivvo:board:{}:posts", board.id),
+        format!("{}: new posts", board.name),
+        format!("{}/boards/{}", config.server.app_url, board.slug),
+        &req,
+        items,
+    );
+    Ok(respond(&req, &feed, format))
+}
+
+/// Status changes of posts on a board, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/feeds/{org}/boards/{slug}/status.{format}",
+    tag = "feeds",
+    params(
+        ("org" = String, Path, description = "Organization id or slug"),
+        ("slug" = String, Path, description = "Board slug"),
+        ("format" = String, Path, description = "`atom` or `rss`"),
+    ),
+    security(()),
+    responses(
+        (status = 200, description = "Atom or RSS 2.0 document", content_type = "application/atom+xml"),
+        (status = 304, description = "Unchanged since `If-None-Match` / `If-Modified-Since`"),
+        (status = 404, description = "Board not found, private, or unknown format", body = ErrorResponse),
+    )
+)]
+pub async fn board_status_feed(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    req: HttpRequest,
+    path: web::Path<(String, String, String)>,
+) -> Result<HttpResponse, AppError> {
+    let (org, slug, format) = path.into_inner();
+    let format = parse_format(&format)?;
+    let board = find_public_board(pool.get_ref(), &org, &slug).await?;
+
+    let changes = feed_service::get_status_changes(pool.get_ref(), board.id).await?;
+    let items: Vec<FeedItem> = changes
+        .into_iter()
+        .map(|change| {
+            let from = status_label(change.from_status.as_deref());
+            let to = status_label(change.to_status.as_deref());
+            FeedItem {
+                id: format!("urn:uuid:{}", change.id),
+                title: format!("{} is now {to}", change.post_title),
+                link: format!("{}/posts/{}", config.server.app_url, change.post_id),
+                content_html: format!(
+                    "<p>Status changed from <strong>{from}</strong> to <strong>{to}</strong>.</p>"
+                ),
+                author: None,
+                published: change.changed_at,
+                updated: change.changed_at,
+            }
+        })
+        .collect();
+
+    let feed = build_feed(
+        format!("urn:rivvo:board:{}:status", board.id),
+        format!("{}: status changes", board.name),
+        format!("{}/boards/{}", config.server.app_url, board.slug),
+        &req,
+        items,
+    );
+    Ok(respond(&req, &feed, format))
+}
+
+fn parse_format(ext: &str) -> Result<FeedFormat, AppError> {
+    FeedFormat::from_extension(ext)
+        .ok_or_else(|| AppError::NotFound(format!("Unknown feed format: {ext}")))
+}
+
+/// Feeds are unauthenticated, so private boards look like missing ones.
+async fn find_public_board(pool: &PgPool, org: &str, slug: &str) -> Result<Board, AppError> {
+    let org = org_service::find_org(pool, org).await?;
+    let board = board_service::get_board_by_slug(pool, org.id, slug).await?;
+    if board.is_private == Some(true) {
+        return Err(AppError::NotFound("Board not found".to_string()));
+    }
+    Ok(board)
+}
+
+/// Status values are stored snake_case; feeds show them to people.
+fn status_label(status: Option<&str>) -> String {
+    status.unwrap_or("open").replace('_', " ")
+}
+
+fn build_feed(
+    id: String,
+    title: String,
+    link: String,
+    req: &HttpRequest,
+    items: Vec<FeedItem>,
+) -> Feed {
+    let info = req.connection_info();
+    let self_link = format!("{}://{}{}", info.scheme(), info.host(), req.path());
+    Feed {
+        id,
+        title,
+        link,
+        self_link,
+        updated: items
+            .iter()
+            .map(|item| item.updated)
+            .max()
+            .unwrap_or(DateTime::UNIX_EPOCH),
+        items,
+    }
+}
+
+/// Render `feed`, answering conditional requests with `304 Not Modified`.
+/// The ETag is a hash of the rendered document; `Last-Modified` is the
+/// newest item's update time. As in RFC 9110, `If-None-Match` takes
+/// precedence over `If-Modified-Since` when both are sent.
+fn respond(req: &HttpRequest, feed: &Feed, format: FeedFormat) -> HttpResponse {
+    let body = feed::render(feed, format);
+    let etag = EntityTag::new_strong(hex::encode(Sha256::digest(body.as_bytes())));
+    // HTTP dates have whole seconds; truncate so If-Modified-Since compares equal
+    let last_modified = (!feed.items.is_empty())
+        .then(|| HttpDate::from(SystemTime::from(feed.updated.trunc_subsecs(0))));
+
+    let not_modified = if req.headers().contains_key(header::IF_NONE_MATCH) {
+        match IfNoneMatch::parse(req) {
+            Ok(IfNoneMatch::Any) => true,
+            Ok(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+            Err(_) => false,
+        }
+    } else {
+        match (IfModifiedSince::parse(req), last_modified) {
+            (Ok(IfModifiedSince(since)), Some(modified)) => {
+                SystemTime::from(modified) <= SystemTime::from(since)
+            }
+            _ => false,
+        }
+    };
+
+    let mut response = if not_modified {
+        HttpResponse::NotModified()
+    } else {
+        HttpResponse::Ok()
+    };
+    response
+        .insert_header(ETag(etag))
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(300),
+        ]));
+    if let Some(modified) = last_modified {
+        response.insert_header(LastModified(modified));
+    }
+
+    if not_modified {
+        response.finish()
+    } else {
+        response.content_type(format.content_type()).body(body)
+    }
+}
@@ -2,6 +2,23 @@ use actix_web::{HttpResponse, web};
 use serde_json::json;
 use sqlx::PgPool;
 
+use crate::jobs::Heartbeat;
+use crate::mailer::Mailer;
+use crate::models::health::{BuildInfo, LivenessReport, ReadinessReport, STATUS_OK};
+use crate::services::health_service;
+use crate::shutdown::Drain;
+use crate::storage::Storage;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/health",
+    tag = "health",
+    security(()),
+    responses(
+        (status = 200, description = "Database reachable"),
+        (status = 503, description = "Database unreachable"),
+    )
+)]
 pub async fn health_check(pool: web::Data<PgPool>) -> HttpResponse {
     match sqlx::query_scalar::<_, i32>("SELECT 1")
         .fetch_one(pool.get_ref())
@@ -20,3 +37,58 @@ pub async fn health_check(pool: web::Data<PgPool>) -> HttpResponse {
         }
     }
 }
+
+/// Liveness: the process is up and serving requests. Checks no
+/// dependencies, so a database outage doesn't get the process restarted.
+#[utoipa::path(
+    get,
+    path = "/api/v1/health/live",
+    tag = "health",
+    security(()),
+    responses(
+        (status = 200, description = "Process is serving requests", body = LivenessReport),
+    )
+)]
+pub async fn live() -> HttpResponse {
+    HttpResponse::Ok().json(LivenessReport {
+        status: STATUS_OK,
+        build: BuildInfo::current(),
+    })
+}
+
+/// Readiness: every dependency the API needs is usable, so the instance
+/// may receive traffic.
+#[utoipa::path(
+    get,
+    path = "/api/v1/health/ready",
+    tag = "health",
+    security(()),
+    responses(
+        (status = 200, description = "All components ready", body = ReadinessReport),
+        (status = 503, description = "Not ready, or shutting down", body = ReadinessReport),
+    )
+)]
+pub async fn ready(
+    drain: web::Data<Drain>,
+    pool: web::Data<PgPool>,
+    heartbeat: web::Data<Heartbeat>,
+    mailer: web::Data<dyn Mailer>,
+    storage: web::Data<dyn Storage>,
+) -> HttpResponse {
+    let report = health_service::readiness(&drain, &pool, &heartbeat, &**mailer, &**storage).await;
+    if report.is_ready() {
+        HttpResponse::Ok().json(report)
+    } else if drain.is_draining() {
+        HttpResponse::ServiceUnavailable().json(report)
+    } else {
+        for (name, check) in report.checks.iter().filter(|(_, c)| c.status != STATUS_OK) {
+            tracing::warn!(
+                component = name,
+                status = check.status,
+                detail = check.detail.as_deref(),
+                "Readiness check failed"
+            );
+        }
+        HttpResponse::ServiceUnavailable().json(report)
+    }
+}
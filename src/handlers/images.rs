@@ -0,0 +1,190 @@
+use actix_multipart::Multipart;
+use actix_web::http::header;
+use actix_web::{HttpResponse, web};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::errors::{AppError, ErrorResponse};
+use crate::middleware::auth::AuthenticatedUser;
+use crate::models::organization::OrganizationResponse;
+use crate::models::user::UserResponse;
+use crate::services::{image_service, org_service};
+use crate::storage::{self, Storage};
+use crate::utils::imaging::ImageKind;
+use crate::utils::multipart;
+use crate::utils::multipart::FileUpload;
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/me/avatar",
+    tag = "auth",
+    request_body(content = FileUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Avatar replaced", body = UserResponse),
+        (status = 400, description = "Image could not be decoded", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 413, description = "File too large", body = ErrorResponse),
+        (status = 415, description = "Not a PNG, JPEG, GIF or WebP image", body = ErrorResponse),
+    )
+)]
+pub async fn upload_avatar(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn Storage>,
+    config: web::Data<Config>,
+    auth: AuthenticatedUser,
+    payload: Multipart,
+) -> Result<HttpResponse, AppError> {
+    let (_filename, data) =
+        multipart::read_file_field(payload, config.storage.max_upload_bytes).await?;
+
+    let url = image_service::store_image(storage.get_ref(), ImageKind::Avatar, auth.user_id, data)
+        .await?;
+    let user =
+        image_service::set_user_avatar(pool.get_ref(), storage.get_ref(), auth.user_id, Some(&url))
+            .await;
+    let user = match user {
+        Ok(user) => user,
+        Err(e) => {
+            storage::delete_all(
+                storage.get_ref(),
+                &image_service::storage_keys_for_url(&url),
+            )
+            .await;
+            return Err(e);
+        }
+    };
+
+    Ok(HttpResponse::Ok().json(UserResponse::from(user)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/auth/me/avatar",
+    tag = "auth",
+    responses(
+        (status = 204, description = "Avatar removed"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+    )
+)]
+pub async fn delete_avatar(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn Storage>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    image_service::set_user_avatar(pool.get_ref(), storage.get_ref(), auth.user_id, None).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/orgs/{org_id}/logo",
+    tag = "organizations",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+    ),
+    request_body(content = FileUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Logo replaced", body = OrganizationResponse),
+        (status = 400, description = "Image could not be decoded", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 413, description = "File too large", body = ErrorResponse),
+        (status = 415, description = "Not a PNG, JPEG, GIF or WebP image", body = ErrorResponse),
+    )
+)]
+pub async fn upload_org_logo(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn Storage>,
+    config: web::Data<Config>,
+    auth: AuthenticatedUser,
+    org_id: web::Path<Uuid>,
+    payload: Multipart,
+) -> Result<HttpResponse, AppError> {
+    let org_id = org_id.into_inner();
+    org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
+
+    let (_filename, data) =
+        multipart::read_file_field(payload, config.storage.max_upload_bytes).await?;
+
+    let url = image_service::store_image(storage.get_ref(), ImageKind::Logo, org_id, data).await?;
+    let org =
+        image_service::set_org_logo(pool.get_ref(), storage.get_ref(), org_id, Some(&url)).await;
+    let org = match org {
+        Ok(org) => org,
+        Err(e) => {
+            storage::delete_all(
+                storage.get_ref(),
+                &image_service::storage_keys_for_url(&url),
+            )
+            .await;
+            return Err(e);
+        }
+    };
+
+    Ok(HttpResponse::Ok().json(OrganizationResponse {
+        id: org.id,
+        name: org.name,
+        slug: org.slug,
+        logo_url: org.logo_url,
+        role: "admin".to_string(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/orgs/{org_id}/logo",
+    tag = "organizations",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+    ),
+    responses(
+        (status = 204, description = "Logo removed"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+    )
+)]
+pub async fn delete_org_logo(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn Storage>,
+    auth: AuthenticatedUser,
+    org_id: web::Path<Uuid>,
+) -> Result<HttpResponse, AppError> {
+    let org_id = org_id.into_inner();
+    org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
+
+    image_service::set_org_logo(pool.get_ref(), storage.get_ref(), org_id, None).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Serve a processed avatar or logo. Public, since avatars and logos appear
+/// on shared pages; paths contain random version ids and never change
+/// content, so they are cached as immutable.
+#[utoipa::path(
+    get,
+    path = "/api/v1/images/{path}",
+    tag = "images",
+    params(
+        ("path" = String, Path, description = "Image path from an avatar or logo URL"),
+    ),
+    security(()),
+    responses(
+        (status = 200, description = "Image data", content_type = "image/*"),
+        (status = 404, description = "Image not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_image(
+    storage: web::Data<dyn Storage>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let (key, format) = image_service::resolve_image_path(&path)
+        .ok_or_else(|| AppError::NotFound("Image not found".to_string()))?;
+
+    let data = storage.get(&key).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+        .insert_header((header::CACHE_CONTROL, "public, max-age=31536000, immutable"))
+        .body(data))
+}
@@ -0,0 +1,113 @@
+use actix_web::{HttpResponse, web};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::errors::{AppError, ErrorResponse};
+use crate::middleware::auth::AuthenticatedUser;
+use crate::models::job::{Job, STATUSES};
+use crate::services::{job_service, org_service};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct JobListQuery {
+    /// `pending`, `running`, `completed` or `dead`
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Background jobs working for the organization, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org_id}/jobs",
+    tag = "jobs",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        JobListQuery,
+    ),
+    responses(
+        (status = 200, description = "Jobs, newest first", body = Vec<Job>),
+        (status = 400, description = "Unknown status", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+    )
+)]
+pub async fn list_jobs(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    org_id: web::Path<Uuid>,
+    query: web::Query<JobListQuery>,
+) -> Result<HttpResponse, AppError> {
+    let org_id = org_id.into_inner();
+    org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
+
+    if let Some(status) = query.status.as_deref()
+        && !STATUSES.contains(&status)
+    {
+        return Err(AppError::BadRequest(format!(
+            "Unknown job status: {status}. Must be pending, running, completed or dead"
+        )));
+    }
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+
+    let jobs = job_service::list_jobs(pool.get_ref(), Some(org_id), query.status.as_deref(), limit)
+        .await?;
+    Ok(HttpResponse::Ok().json(jobs))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org_id}/jobs/{job_id}",
+    tag = "jobs",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        ("job_id" = Uuid, Path, description = "Job id"),
+    ),
+    responses(
+        (status = 200, description = "The job", body = Job),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Job not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_job(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, AppError> {
+    let (org_id, job_id) = path.into_inner();
+    org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
+
+    let job = job_service::get_job(pool.get_ref(), Some(org_id), job_id).await?;
+    Ok(HttpResponse::Ok().json(job))
+}
+
+/// Requeue a dead job (or a pending one waiting out its backoff) to run now.
+#[utoipa::path(
+    post,
+    path = "/api/v1/orgs/{org_id}/jobs/{job_id}/retry",
+    tag = "jobs",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        ("job_id" = Uuid, Path, description = "Job id"),
+    ),
+    responses(
+        (status = 200, description = "Job requeued", body = Job),
+        (status = 400, description = "The job cannot be retried", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Job not found", body = ErrorResponse),
+    )
+)]
+pub async fn retry_job(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, AppError> {
+    let (org_id, job_id) = path.into_inner();
+    org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
+
+    let job = job_service::retry_job(pool.get_ref(), Some(org_id), job_id).await?;
+    Ok(HttpResponse::Ok().json(job))
+}
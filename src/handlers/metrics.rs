@@ -0,0 +1,47 @@
+use actix_web::{HttpRequest, HttpResponse, http::header, web};
+use metrics_exporter_prometheus::PrometheusHandle;
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::errors::AppError;
+use crate::models::job::STATUSES;
+use crate::services::job_service;
+use crate::telemetry::{DB_POOL_CONNECTIONS, DB_POOL_MAX_CONNECTIONS, JOBS};
+
+/// Prometheus text exposition. Gauges for the pool and the job queue are
+/// refreshed on every scrape; everything else is recorded as it happens.
+pub async fn metrics(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    handle: web::Data<PrometheusHandle>,
+) -> Result<HttpResponse, AppError> {
+    if let Some(token) = &config.telemetry.metrics_token {
+        let sent = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        if sent != Some(token.as_str()) {
+            return Err(AppError::Unauthorized("Invalid metrics token".to_string()));
+        }
+    }
+
+    let idle = pool.num_idle() as f64;
+    metrics::gauge!(DB_POOL_CONNECTIONS, "state" => "idle").set(idle);
+    metrics::gauge!(DB_POOL_CONNECTIONS, "state" => "in_use").set(f64::from(pool.size()) - idle);
+    metrics::gauge!(DB_POOL_MAX_CONNECTIONS).set(pool.options().get_max_connections());
+
+    let counts = job_service::count_by_status(pool.get_ref()).await?;
+    for status in STATUSES {
+        let count = counts
+            .iter()
+            .find(|(s, _)| s == status)
+            .map_or(0, |(_, n)| *n);
+        metrics::gauge!(JOBS, "status" => *status).set(count as f64);
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(handle.render()))
+}
@@ -1,8 +1,17 @@
+pub mod attachments;
 pub mod auth;
 pub mod boards;
+pub mod changelog;
 pub mod comments;
+pub mod events;
+pub mod feeds;
 pub mod health;
+pub mod images;
+pub mod jobs;
+pub mod metrics;
+pub mod notifications;
 pub mod organizations;
 pub mod posts;
+pub mod reactions;
 pub mod tags;
 pub mod votes;
@@ -0,0 +1,42 @@
+use actix_web::{HttpResponse, web};
+use sqlx::PgPool;
+
+use crate::errors::{AppError, ErrorResponse};
+use crate::middleware::auth::AuthenticatedUser;
+use crate::models::notification::NotificationResponse;
+use crate::services::notification_service;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications",
+    tag = "notifications",
+    responses(
+        (status = 200, description = "The user's notifications, newest first", body = Vec<NotificationResponse>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+    )
+)]
+pub async fn list_notifications(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    let notifications =
+        notification_service::get_notifications(pool.get_ref(), auth.user_id).await?;
+    Ok(HttpResponse::Ok().json(notifications))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/notifications/read",
+    tag = "notifications",
+    responses(
+        (status = 204, description = "All notifications marked read"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+    )
+)]
+pub async fn mark_all_read(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    notification_service::mark_all_read(pool.get_ref(), auth.user_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -1,10 +1,30 @@
+use actix_multipart::Multipart;
 use actix_web::{HttpResponse, web};
+use serde::Deserialize;
 use sqlx::PgPool;
+use utoipa::IntoParams;
+use uuid::Uuid;
 
-use crate::errors::AppError;
+use crate::config::Config;
+use crate::errors::{AppError, ErrorResponse};
+use crate::importers::{self, Format, Source};
 use crate::middleware::auth::AuthenticatedUser;
-use crate::services::org_service;
+use crate::models::board_transfer::ImportReport;
+use crate::models::counter::CounterReport;
+use crate::models::organization::{MemberSuggestion, OrganizationResponse};
+use crate::services::{board_transfer_service, counter_service, org_service};
+use crate::utils::multipart;
+use crate::utils::multipart::FileUpload;
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs",
+    tag = "organizations",
+    responses(
+        (status = 200, description = "Organizations the user belongs to", body = Vec<OrganizationResponse>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+    )
+)]
 pub async fn list_user_orgs(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -12,3 +32,147 @@ pub async fn list_user_orgs(
     let orgs = org_service::get_user_orgs(pool.get_ref(), auth.user_id).await?;
     Ok(HttpResponse::Ok().json(orgs))
 }
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MemberSearchQuery {
+    pub q: Option<String>,
+}
+
+/// Autocomplete org members for @mentions.
+#[utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org_id}/members",
+    tag = "organizations",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        MemberSearchQuery,
+    ),
+    responses(
+        (status = 200, description = "Matching members", body = Vec<MemberSuggestion>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+    )
+)]
+pub async fn search_members(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    org_id: web::Path<Uuid>,
+    query: web::Query<MemberSearchQuery>,
+) -> Result<HttpResponse, AppError> {
+    let org_id = org_id.into_inner();
+    org_service::get_member(pool.get_ref(), org_id, auth.user_id).await?;
+
+    let members = org_service::search_members(
+        pool.get_ref(),
+        org_id,
+        query.q.as_deref().unwrap_or_default(),
+        10,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(members))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    /// `canny` or `uservoice`
+    pub source: String,
+    /// `json` or `csv`; taken from the file extension when omitted
+    pub format: Option<String>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Import another tool's export into the organization's boards.
+#[utoipa::path(
+    post,
+    path = "/api/v1/orgs/{org_id}/import",
+    tag = "organizations",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        ImportQuery,
+    ),
+    request_body(content = FileUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Imported, or validated with `dry_run`", body = ImportReport),
+        (status = 400, description = "Unreadable file", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 422, description = "Some rows failed validation; nothing was written", body = ImportReport),
+    )
+)]
+pub async fn import_from_source(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    auth: AuthenticatedUser,
+    org_id: web::Path<Uuid>,
+    query: web::Query<ImportQuery>,
+    payload: Multipart,
+) -> Result<HttpResponse, AppError> {
+    let org_id = org_id.into_inner();
+    org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
+    let source = Source::from_name(&query.source).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "Unknown import source: {}. Must be canny or uservoice",
+            query.source
+        ))
+    })?;
+
+    let (filename, data) =
+        multipart::read_file_field(payload, config.storage.max_upload_bytes).await?;
+    let format = query
+        .format
+        .as_deref()
+        .or_else(|| filename.rsplit_once('.').map(|(_, ext)| ext))
+        .and_then(Format::from_name)
+        .ok_or_else(|| AppError::BadRequest("Import format must be json or csv".to_string()))?;
+
+    let parsed = importers::parse(source, format, &data)?;
+    let report =
+        board_transfer_service::import_foreign(pool.get_ref(), org_id, parsed, query.dry_run)
+            .await?;
+
+    if report.valid {
+        Ok(HttpResponse::Ok().json(report))
+    } else {
+        Ok(HttpResponse::UnprocessableEntity().json(report))
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReconcileQuery {
+    /// Rewrite drifted counters instead of only reporting them
+    #[serde(default)]
+    pub fix: bool,
+}
+
+/// Check the organization's post vote and comment counters against the
+/// underlying rows.
+#[utoipa::path(
+    post,
+    path = "/api/v1/orgs/{org_id}/counters/reconcile",
+    tag = "organizations",
+    params(
+        ("org_id" = Uuid, Path, description = "Organization id"),
+        ReconcileQuery,
+    ),
+    responses(
+        (status = 200, description = "Posts whose counters drifted", body = CounterReport),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+    )
+)]
+pub async fn reconcile_counters(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    org_id: web::Path<Uuid>,
+    query: web::Query<ReconcileQuery>,
+) -> Result<HttpResponse, AppError> {
+    let org_id = org_id.into_inner();
+    org_service::require_org_admin(pool.get_ref(), org_id, auth.user_id).await?;
+
+    let report = counter_service::reconcile(pool.get_ref(), Some(org_id), query.fix).await?;
+    Ok(HttpResponse::Ok().json(report))
+}
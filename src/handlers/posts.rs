@@ -1,16 +1,25 @@
 use actix_web::{HttpResponse, web};
 use serde::Deserialize;
 use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::errors::AppError;
+use crate::errors::{AppError, ErrorResponse};
 use crate::middleware::auth::AuthenticatedUser;
-use crate::models::post::{PostAuthor, PostDetailResponse, PostListResponse};
+use crate::models::attachment::AttachmentResponse;
+use crate::models::page::{Page, PageQuery};
+use crate::models::post::{PostAuthor, PostDetailResponse, PostListResponse, PostListRow};
+use crate::models::reaction::ReactionTarget;
 use crate::models::tag::TagResponse;
-use crate::services::{board_service, org_service, post_service, tag_service};
-
-#[derive(Deserialize, Validate)]
+use crate::services::{
+    attachment_service, board_service, mention_service, org_service, post_service,
+    reaction_service, tag_service,
+};
+use crate::storage::{self, Storage};
+use crate::utils::markdown;
+
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct CreatePostRequest {
     #[validate(length(
         min = 1,
@@ -24,17 +33,37 @@ pub struct CreatePostRequest {
 
 pub type UpdatePostRequest = CreatePostRequest;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateStatusRequest {
     pub status: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PostQuery {
+    /// `votes` (default), `recent` or `oldest`
     pub sort: Option<String>,
+    /// Only posts with this status
     pub status: Option<String>,
 }
 
+/// Superseded by the paginated `/api/v2/boards/{board_id}/posts`.
+#[deprecated = "use list_posts_page"]
+#[utoipa::path(
+    get,
+    path = "/api/v1/boards/{board_id}/posts",
+    tag = "posts",
+    params(
+        ("board_id" = Uuid, Path, description = "Board id"),
+        PostQuery,
+    ),
+    responses(
+        (status = 200, description = "Posts, pinned first", body = Vec<PostListResponse>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Board not found", body = ErrorResponse),
+    )
+)]
 pub async fn list_posts(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -51,10 +80,63 @@ pub async fn list_posts(
 
     let rows = post_service::get_posts(pool.get_ref(), board_id, auth.user_id, sort, status_filter)
         .await?;
+    let response = list_responses(pool.get_ref(), rows).await?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v2/boards/{board_id}/posts",
+    tag = "posts",
+    params(
+        ("board_id" = Uuid, Path, description = "Board id"),
+        PostQuery,
+        PageQuery,
+    ),
+    responses(
+        (status = 200, description = "One page of posts, pinned first", body = Page<PostListResponse>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Board not found", body = ErrorResponse),
+    )
+)]
+pub async fn list_posts_page(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    board_id: web::Path<Uuid>,
+    query: web::Query<PostQuery>,
+    page: web::Query<PageQuery>,
+) -> Result<HttpResponse, AppError> {
+    let board_id = board_id.into_inner();
+    let sort = query.sort.as_deref().unwrap_or("votes");
+    let status_filter = query.status.as_deref();
+
+    let board = board_service::get_board_by_id(pool.get_ref(), board_id).await?;
+    org_service::get_member(pool.get_ref(), board.org_id, auth.user_id).await?;
+
+    let (rows, total) = post_service::get_posts_page(
+        pool.get_ref(),
+        board_id,
+        auth.user_id,
+        sort,
+        status_filter,
+        page.per_page(),
+        page.offset(),
+    )
+    .await?;
+    let data = list_responses(pool.get_ref(), rows).await?;
 
+    Ok(HttpResponse::Ok().json(Page::new(data, &page, total)))
+}
+
+async fn list_responses(
+    pool: &PgPool,
+    rows: Vec<PostListRow>,
+) -> Result<Vec<PostListResponse>, AppError> {
     // Fetch tags for all posts in a single batch query
     let post_ids: Vec<Uuid> = rows.iter().map(|r| r.id).collect();
-    let mut tags_map = tag_service::get_tags_for_post_ids(pool.get_ref(), &post_ids).await?;
+    let mut tags_map = tag_service::get_tags_for_post_ids(pool, &post_ids).await?;
 
     let mut response: Vec<PostListResponse> = Vec::with_capacity(rows.len());
     for row in rows {
@@ -65,14 +147,10 @@ pub async fn list_posts(
             .map(TagResponse::from)
             .collect();
 
-        let description_preview = row.description.as_ref().map(|d| {
-            if d.chars().count() > 200 {
-                let truncated: String = d.chars().take(200).collect();
-                format!("{truncated}...")
-            } else {
-                d.clone()
-            }
-        });
+        let description_preview = row
+            .description
+            .as_deref()
+            .map(|d| markdown::preview(d, 200));
 
         response.push(PostListResponse {
             id: row.id,
@@ -89,9 +167,25 @@ pub async fn list_posts(
         });
     }
 
-    Ok(HttpResponse::Ok().json(response))
+    Ok(response)
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/boards/{board_id}/posts",
+    tag = "posts",
+    params(
+        ("board_id" = Uuid, Path, description = "Board id"),
+    ),
+    request_body = CreatePostRequest,
+    responses(
+        (status = 201, description = "Post created", body = PostDetailResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Board not found", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
 pub async fn create_post(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -99,8 +193,7 @@ pub async fn create_post(
     body: web::Json<CreatePostRequest>,
 ) -> Result<HttpResponse, AppError> {
     let board_id = board_id.into_inner();
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    body.validate()?;
 
     // Verify board exists and user is org member
     let board = board_service::get_board_by_id(pool.get_ref(), board_id).await?;
@@ -108,6 +201,7 @@ pub async fn create_post(
 
     let post = post_service::create_post(
         pool.get_ref(),
+        board.org_id,
         board_id,
         auth.user_id,
         &body.title,
@@ -118,10 +212,40 @@ pub async fn create_post(
     // Return full detail response
     let detail = post_service::get_post(pool.get_ref(), post.id, auth.user_id).await?;
     let tags = tag_service::get_post_tags(pool.get_ref(), post.id).await?;
-
-    Ok(HttpResponse::Created().json(build_detail_response(detail, tags)))
+    let reactions = reaction_service::get_reactions(
+        pool.get_ref(),
+        ReactionTarget::Post,
+        post.id,
+        auth.user_id,
+    )
+    .await?;
+    let mentions = mention_service::get_post_mentions(pool.get_ref(), post.id).await?;
+    let attachments = attachment_service::get_post_attachments(pool.get_ref(), post.id).await?;
+
+    Ok(HttpResponse::Created().json(build_detail_response(
+        detail,
+        tags,
+        reactions,
+        mentions,
+        attachments,
+    )))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/boards/{board_id}/posts/{post_id}",
+    tag = "posts",
+    params(
+        ("board_id" = Uuid, Path, description = "Board id"),
+        ("post_id" = Uuid, Path, description = "Post id"),
+    ),
+    responses(
+        (status = 200, description = "The post", body = PostDetailResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+    )
+)]
 pub async fn get_post(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -141,11 +265,40 @@ pub async fn get_post(
     }
 
     let tags = tag_service::get_post_tags(pool.get_ref(), post_id).await?;
-
-    Ok(HttpResponse::Ok().json(build_detail_response(detail, tags)))
+    let reactions = reaction_service::get_reactions(
+        pool.get_ref(),
+        ReactionTarget::Post,
+        post_id,
+        auth.user_id,
+    )
+    .await?;
+    let mentions = mention_service::get_post_mentions(pool.get_ref(), post_id).await?;
+    let attachments = attachment_service::get_post_attachments(pool.get_ref(), post_id).await?;
+
+    Ok(HttpResponse::Ok().json(build_detail_response(
+        detail,
+        tags,
+        reactions,
+        mentions,
+        attachments,
+    )))
 }
 
 /// Direct post lookup by ID — no board_id required in the path.
+#[utoipa::path(
+    get,
+    path = "/api/v1/posts/{post_id}",
+    tag = "posts",
+    params(
+        ("post_id" = Uuid, Path, description = "Post id"),
+    ),
+    responses(
+        (status = 200, description = "The post", body = PostDetailResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+    )
+)]
 pub async fn get_post_direct(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -160,10 +313,42 @@ pub async fn get_post_direct(
 
     let detail = post_service::get_post(pool.get_ref(), post_id, auth.user_id).await?;
     let tags = tag_service::get_post_tags(pool.get_ref(), post_id).await?;
-
-    Ok(HttpResponse::Ok().json(build_detail_response(detail, tags)))
+    let reactions = reaction_service::get_reactions(
+        pool.get_ref(),
+        ReactionTarget::Post,
+        post_id,
+        auth.user_id,
+    )
+    .await?;
+    let mentions = mention_service::get_post_mentions(pool.get_ref(), post_id).await?;
+    let attachments = attachment_service::get_post_attachments(pool.get_ref(), post_id).await?;
+
+    Ok(HttpResponse::Ok().json(build_detail_response(
+        detail,
+        tags,
+        reactions,
+        mentions,
+        attachments,
+    )))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/boards/{board_id}/posts/{post_id}",
+    tag = "posts",
+    params(
+        ("board_id" = Uuid, Path, description = "Board id"),
+        ("post_id" = Uuid, Path, description = "Post id"),
+    ),
+    request_body = CreatePostRequest,
+    responses(
+        (status = 200, description = "Post updated", body = PostDetailResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Only the author or an admin may edit", body = ErrorResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
 pub async fn update_post(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -171,8 +356,7 @@ pub async fn update_post(
     body: web::Json<UpdatePostRequest>,
 ) -> Result<HttpResponse, AppError> {
     let (board_id, post_id) = path.into_inner();
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    body.validate()?;
 
     let post = post_service::get_post_raw(pool.get_ref(), post_id).await?;
     if post.board_id != board_id {
@@ -190,7 +374,9 @@ pub async fn update_post(
 
     let updated = post_service::update_post(
         pool.get_ref(),
+        board.org_id,
         post_id,
+        auth.user_id,
         &body.title,
         body.description.as_deref(),
     )
@@ -198,12 +384,43 @@ pub async fn update_post(
 
     let detail = post_service::get_post(pool.get_ref(), updated.id, auth.user_id).await?;
     let tags = tag_service::get_post_tags(pool.get_ref(), updated.id).await?;
-
-    Ok(HttpResponse::Ok().json(build_detail_response(detail, tags)))
+    let reactions = reaction_service::get_reactions(
+        pool.get_ref(),
+        ReactionTarget::Post,
+        updated.id,
+        auth.user_id,
+    )
+    .await?;
+    let mentions = mention_service::get_post_mentions(pool.get_ref(), updated.id).await?;
+    let attachments = attachment_service::get_post_attachments(pool.get_ref(), updated.id).await?;
+
+    Ok(HttpResponse::Ok().json(build_detail_response(
+        detail,
+        tags,
+        reactions,
+        mentions,
+        attachments,
+    )))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/boards/{board_id}/posts/{post_id}",
+    tag = "posts",
+    params(
+        ("board_id" = Uuid, Path, description = "Board id"),
+        ("post_id" = Uuid, Path, description = "Post id"),
+    ),
+    responses(
+        (status = 204, description = "Post deleted"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Only the author or an admin may delete", body = ErrorResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+    )
+)]
 pub async fn delete_post(
     pool: web::Data<PgPool>,
+    storage: web::Data<dyn Storage>,
     auth: AuthenticatedUser,
     path: web::Path<(Uuid, Uuid)>,
 ) -> Result<HttpResponse, AppError> {
@@ -223,10 +440,31 @@ pub async fn delete_post(
         ));
     }
 
+    let storage_keys =
+        attachment_service::get_storage_keys_for_post(pool.get_ref(), post_id).await?;
     post_service::delete_post(pool.get_ref(), post_id).await?;
+    storage::delete_all(storage.get_ref(), &storage_keys).await;
+
     Ok(HttpResponse::NoContent().finish())
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/boards/{board_id}/posts/{post_id}/status",
+    tag = "posts",
+    params(
+        ("board_id" = Uuid, Path, description = "Board id"),
+        ("post_id" = Uuid, Path, description = "Post id"),
+    ),
+    request_body = UpdateStatusRequest,
+    responses(
+        (status = 200, description = "Status changed", body = PostDetailResponse),
+        (status = 400, description = "Unknown status", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+    )
+)]
 pub async fn update_status(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -246,13 +484,31 @@ pub async fn update_status(
     let updated = post_service::update_status(pool.get_ref(), post_id, &body.status).await?;
     let detail = post_service::get_post(pool.get_ref(), updated.id, auth.user_id).await?;
     let tags = tag_service::get_post_tags(pool.get_ref(), updated.id).await?;
-
-    Ok(HttpResponse::Ok().json(build_detail_response(detail, tags)))
+    let reactions = reaction_service::get_reactions(
+        pool.get_ref(),
+        ReactionTarget::Post,
+        updated.id,
+        auth.user_id,
+    )
+    .await?;
+    let mentions = mention_service::get_post_mentions(pool.get_ref(), updated.id).await?;
+    let attachments = attachment_service::get_post_attachments(pool.get_ref(), updated.id).await?;
+
+    Ok(HttpResponse::Ok().json(build_detail_response(
+        detail,
+        tags,
+        reactions,
+        mentions,
+        attachments,
+    )))
 }
 
 fn build_detail_response(
     row: crate::models::post::PostDetailRow,
     tags: Vec<crate::models::tag::Tag>,
+    reactions: Vec<crate::models::reaction::ReactionSummary>,
+    mentions: Vec<crate::models::mention::MentionSpan>,
+    attachments: Vec<crate::models::attachment::Attachment>,
 ) -> PostDetailResponse {
     let author = row.author_id.map(|id| PostAuthor {
         id,
@@ -264,7 +520,9 @@ fn build_detail_response(
         id: row.id,
         board_id: row.board_id,
         title: row.title,
-        description: row.description,
+        description_html: row.description.as_deref().map(markdown::render_html),
+        description_markdown: row.description,
+        mentions,
         status: row.status.unwrap_or_else(|| "open".to_string()),
         vote_count: row.vote_count.unwrap_or(0),
         comment_count: row.comment_count.unwrap_or(0),
@@ -272,6 +530,11 @@ fn build_detail_response(
         author,
         has_voted: row.has_voted,
         tags: tags.into_iter().map(TagResponse::from).collect(),
+        reactions,
+        attachments: attachments
+            .into_iter()
+            .map(AttachmentResponse::from)
+            .collect(),
         created_at: row.created_at,
         updated_at: row.updated_at,
     }
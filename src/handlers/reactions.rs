@@ -0,0 +1,98 @@
+use actix_web::{HttpResponse, web};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::{AppError, ErrorResponse};
+use crate::middleware::auth::AuthenticatedUser;
+use crate::models::reaction::{ReactionResult, ReactionTarget};
+use crate::services::{
+    board_service, comment_service, org_service, post_service, reaction_service,
+};
+
+#[derive(Deserialize, ToSchema)]
+pub struct ToggleReactionRequest {
+    pub emoji: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/posts/{post_id}/reactions",
+    tag = "reactions",
+    params(
+        ("post_id" = Uuid, Path, description = "Post id"),
+    ),
+    request_body = ToggleReactionRequest,
+    responses(
+        (status = 200, description = "Reaction added or removed", body = ReactionResult),
+        (status = 400, description = "Emoji not allowed", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+    )
+)]
+pub async fn toggle_post_reaction(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    post_id: web::Path<Uuid>,
+    body: web::Json<ToggleReactionRequest>,
+) -> Result<HttpResponse, AppError> {
+    let post_id = post_id.into_inner();
+
+    // Verify user is org member
+    let post = post_service::get_post_raw(pool.get_ref(), post_id).await?;
+    let board = board_service::get_board_by_id(pool.get_ref(), post.board_id).await?;
+    org_service::get_member(pool.get_ref(), board.org_id, auth.user_id).await?;
+
+    let result = reaction_service::toggle_reaction(
+        pool.get_ref(),
+        ReactionTarget::Post,
+        post_id,
+        auth.user_id,
+        &body.emoji,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/comments/{comment_id}/reactions",
+    tag = "reactions",
+    params(
+        ("comment_id" = Uuid, Path, description = "Comment id"),
+    ),
+    request_body = ToggleReactionRequest,
+    responses(
+        (status = 200, description = "Reaction added or removed", body = ReactionResult),
+        (status = 400, description = "Emoji not allowed", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Comment not found", body = ErrorResponse),
+    )
+)]
+pub async fn toggle_comment_reaction(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    comment_id: web::Path<Uuid>,
+    body: web::Json<ToggleReactionRequest>,
+) -> Result<HttpResponse, AppError> {
+    let comment_id = comment_id.into_inner();
+
+    // Verify user is org member through comment -> post -> board chain
+    let comment = comment_service::get_comment_by_id(pool.get_ref(), comment_id).await?;
+    let post = post_service::get_post_raw(pool.get_ref(), comment.post_id).await?;
+    let board = board_service::get_board_by_id(pool.get_ref(), post.board_id).await?;
+    org_service::get_member(pool.get_ref(), board.org_id, auth.user_id).await?;
+
+    let result = reaction_service::toggle_reaction(
+        pool.get_ref(),
+        ReactionTarget::Comment,
+        comment_id,
+        auth.user_id,
+        &body.emoji,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(result))
+}
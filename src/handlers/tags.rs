@@ -1,15 +1,16 @@
 use actix_web::{HttpResponse, web};
 use serde::Deserialize;
 use sqlx::PgPool;
+use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::errors::AppError;
+use crate::errors::{AppError, ErrorResponse};
 use crate::middleware::auth::AuthenticatedUser;
 use crate::models::tag::TagResponse;
 use crate::services::{board_service, org_service, post_service, tag_service};
 
-#[derive(Deserialize, Validate)]
+#[derive(Deserialize, Validate, ToSchema)]
 pub struct CreateTagRequest {
     #[validate(length(
         min = 1,
@@ -20,6 +21,20 @@ pub struct CreateTagRequest {
     pub color: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/boards/{board_id}/tags",
+    tag = "tags",
+    params(
+        ("board_id" = Uuid, Path, description = "Board id"),
+    ),
+    responses(
+        (status = 200, description = "Tags of the board", body = Vec<TagResponse>),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Board not found", body = ErrorResponse),
+    )
+)]
 pub async fn list_tags(
     pool: web::Data<PgPool>,
     _auth: AuthenticatedUser,
@@ -31,6 +46,22 @@ pub async fn list_tags(
     Ok(HttpResponse::Ok().json(response))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/boards/{board_id}/tags",
+    tag = "tags",
+    params(
+        ("board_id" = Uuid, Path, description = "Board id"),
+    ),
+    request_body = CreateTagRequest,
+    responses(
+        (status = 201, description = "Tag created", body = TagResponse),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Board not found", body = ErrorResponse),
+        (status = 422, description = "Invalid input", body = ErrorResponse),
+    )
+)]
 pub async fn create_tag(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -38,8 +69,7 @@ pub async fn create_tag(
     body: web::Json<CreateTagRequest>,
 ) -> Result<HttpResponse, AppError> {
     let board_id = board_id.into_inner();
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    body.validate()?;
 
     let board = board_service::get_board_by_id(pool.get_ref(), board_id).await?;
     org_service::require_org_admin(pool.get_ref(), board.org_id, auth.user_id).await?;
@@ -50,6 +80,20 @@ pub async fn create_tag(
     Ok(HttpResponse::Created().json(TagResponse::from(tag)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tags/{tag_id}",
+    tag = "tags",
+    params(
+        ("tag_id" = Uuid, Path, description = "Tag id"),
+    ),
+    responses(
+        (status = 204, description = "Tag deleted"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Tag not found", body = ErrorResponse),
+    )
+)]
 pub async fn delete_tag(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -71,6 +115,21 @@ pub async fn delete_tag(
     Ok(HttpResponse::NoContent().finish())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/posts/{post_id}/tags/{tag_id}",
+    tag = "tags",
+    params(
+        ("post_id" = Uuid, Path, description = "Post id"),
+        ("tag_id" = Uuid, Path, description = "Tag id"),
+    ),
+    responses(
+        (status = 204, description = "Tag added"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Post or tag not found", body = ErrorResponse),
+    )
+)]
 pub async fn add_tag_to_post(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
@@ -86,6 +145,21 @@ pub async fn add_tag_to_post(
     Ok(HttpResponse::NoContent().finish())
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/posts/{post_id}/tags/{tag_id}",
+    tag = "tags",
+    params(
+        ("post_id" = Uuid, Path, description = "Post id"),
+        ("tag_id" = Uuid, Path, description = "Tag id"),
+    ),
+    responses(
+        (status = 204, description = "Tag removed"),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 404, description = "Post or tag not found", body = ErrorResponse),
+    )
+)]
 pub async fn remove_tag_from_post(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
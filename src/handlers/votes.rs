@@ -2,10 +2,25 @@ use actix_web::{HttpResponse, web};
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::errors::AppError;
+use crate::errors::{AppError, ErrorResponse};
 use crate::middleware::auth::AuthenticatedUser;
+use crate::models::vote::VoteResult;
 use crate::services::{board_service, org_service, post_service, vote_service};
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/posts/{post_id}/vote",
+    tag = "posts",
+    params(
+        ("post_id" = Uuid, Path, description = "Post id"),
+    ),
+    responses(
+        (status = 200, description = "Vote added or removed", body = VoteResult),
+        (status = 401, description = "Missing or invalid token", body = ErrorResponse),
+        (status = 403, description = "Not a member of the organization", body = ErrorResponse),
+        (status = 404, description = "Post not found", body = ErrorResponse),
+    )
+)]
 pub async fn toggle_vote(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
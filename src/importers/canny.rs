@@ -0,0 +1,262 @@
+//! Canny exports.
+//!
+//! JSON follows Canny's API objects: either an array of posts or
+//! `{"posts": [...], "votes": [...], "comments": [...]}`, where top-level
+//! votes and comments point at their post with `{"post": {"id": ...}}`.
+//! Posts may also carry `voters` and `comments` inline. Internal comments are
+//! private notes in Canny and are skipped.
+//!
+//! CSV is Canny's post export: one row per post with `Board`, `Title`,
+//! `Details`, `Status`, `Category`, `Tags`, `Author Name`, `Author Email`,
+//! `Created At` and `Voters` (email addresses).
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::{
+    CsvTable, ParsedImport, array, name_of, note_unmapped, split_list, text, voters_from_emails,
+};
+use crate::errors::AppError;
+use crate::models::board_transfer::{ImportComment, ImportPost, ImportUser};
+
+const POST_FIELDS: &[&str] = &[
+    "id", "title", "details", "status", "created", "board", "author", "category", "tags", "voters",
+    "comments",
+];
+const COMMENT_FIELDS: &[&str] = &["id", "post", "value", "created", "author", "internal"];
+
+const CSV_COLUMNS: &[&str] = &[
+    "board",
+    "board_name",
+    "title",
+    "details",
+    "description",
+    "status",
+    "category",
+    "tags",
+    "author_name",
+    "author_email",
+    "created_at",
+    "created_date",
+    "voters",
+    "voter_emails",
+];
+
+pub fn parse_json(root: &Value) -> Result<ParsedImport, AppError> {
+    let posts = match root {
+        Value::Array(posts) => posts.as_slice(),
+        Value::Object(map) if map.contains_key("posts") => array(root, "posts"),
+        _ => {
+            return Err(AppError::BadRequest(
+                "Canny import needs a \"posts\" array".to_string(),
+            ));
+        }
+    };
+
+    let mut parsed = ParsedImport::default();
+    let mut skipped_internal = 0;
+    let mut by_id: HashMap<String, usize> = HashMap::new();
+
+    for post in posts {
+        note_unmapped(post, "posts", POST_FIELDS, &mut parsed.unmapped_fields);
+        if let Some(id) = text(post, "id") {
+            by_id.insert(id, parsed.posts.len());
+        }
+
+        let mut comments = Vec::new();
+        for comment in array(post, "comments") {
+            note_unmapped(
+                comment,
+                "comments",
+                COMMENT_FIELDS,
+                &mut parsed.unmapped_fields,
+            );
+            match parse_comment(comment) {
+                Some(comment) => comments.push(comment),
+                None => skipped_internal += 1,
+            }
+        }
+
+        parsed.posts.push(ImportPost {
+            board: name_of(post.get("board")),
+            title: text(post, "title").unwrap_or_default(),
+            description: text(post, "details"),
+            status: text(post, "status"),
+            category: name_of(post.get("category")),
+            tags: array(post, "tags")
+                .iter()
+                .filter_map(|t| name_of(Some(t)))
+                .collect(),
+            author_email: post.get("author").and_then(|a| text(a, "email")),
+            author_name: post.get("author").and_then(|a| text(a, "name")),
+            created_at: text(post, "created"),
+            voters: array(post, "voters").iter().map(user).collect(),
+            comments,
+            ..Default::default()
+        });
+    }
+
+    let mut orphans = 0;
+    for vote in array(root, "votes") {
+        note_unmapped(
+            vote,
+            "votes",
+            &["id", "post", "voter", "created"],
+            &mut parsed.unmapped_fields,
+        );
+        match post_index(vote, &by_id) {
+            Some(i) => parsed.posts[i]
+                .voters
+                .push(vote.get("voter").map(user).unwrap_or_default()),
+            None => orphans += 1,
+        }
+    }
+    for comment in array(root, "comments") {
+        note_unmapped(
+            comment,
+            "comments",
+            COMMENT_FIELDS,
+            &mut parsed.unmapped_fields,
+        );
+        match (post_index(comment, &by_id), parse_comment(comment)) {
+            (Some(i), Some(comment)) => parsed.posts[i].comments.push(comment),
+            (Some(_), None) => skipped_internal += 1,
+            (None, _) => orphans += 1,
+        }
+    }
+
+    if skipped_internal > 0 {
+        parsed.warnings.push(format!(
+            "Skipped {skipped_internal} internal comment(s); Rivvo has no private comments"
+        ));
+    }
+    if orphans > 0 {
+        parsed.warnings.push(format!(
+            "Skipped {orphans} vote(s) or comment(s) whose post is not in the export"
+        ));
+    }
+    Ok(parsed)
+}
+
+pub fn parse_csv(table: &CsvTable) -> Result<ParsedImport, AppError> {
+    table.require(&["title"])?;
+
+    let posts = table
+        .records
+        .iter()
+        .map(|record| ImportPost {
+            board: table.get(record, &["board", "board_name"]),
+            title: table.get(record, &["title"]).unwrap_or_default(),
+            description: table.get(record, &["details", "description"]),
+            status: table.get(record, &["status"]),
+            category: table.get(record, &["category"]),
+            tags: table
+                .get(record, &["tags"])
+                .map(|cell| split_list(&cell))
+                .unwrap_or_default(),
+            author_name: table.get(record, &["author_name"]),
+            author_email: table.get(record, &["author_email"]),
+            created_at: table.get(record, &["created_at", "created_date"]),
+            voters: voters_from_emails(table.get(record, &["voters", "voter_emails"])),
+            ..Default::default()
+        })
+        .collect();
+
+    Ok(ParsedImport {
+        posts,
+        unmapped_fields: table.unmapped(CSV_COLUMNS),
+        warnings: Vec::new(),
+    })
+}
+
+/// `None` for internal comments.
+fn parse_comment(comment: &Value) -> Option<ImportComment> {
+    if comment.get("internal").and_then(Value::as_bool) == Some(true) {
+        return None;
+    }
+    Some(ImportComment {
+        body: text(comment, "value").unwrap_or_default(),
+        author_email: comment.get("author").and_then(|a| text(a, "email")),
+        author_name: comment.get("author").and_then(|a| text(a, "name")),
+        created_at: text(comment, "created"),
+        ..Default::default()
+    })
+}
+
+fn user(value: &Value) -> ImportUser {
+    ImportUser {
+        email: text(value, "email"),
+        name: text(value, "name"),
+    }
+}
+
+fn post_index(item: &Value, by_id: &HashMap<String, usize>) -> Option<usize> {
+    let id = match item.get("post")? {
+        post @ Value::Object(_) => text(post, "id")?,
+        Value::String(id) => id.clone(),
+        _ => return None,
+    };
+    by_id.get(&id).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn attaches_top_level_votes_and_comments() {
+        let export = json!({
+            "posts": [{
+                "id": "p1",
+                "title": "Dark mode",
+                "details": "Please",
+                "status": "under review",
+                "board": { "id": "b1", "name": "Feature Requests" },
+                "author": { "name": "Ada", "email": "ada@example.com" },
+                "tags": [{ "name": "ui" }],
+                "score": 2,
+                "eta": null
+            }],
+            "votes": [
+                { "post": { "id": "p1" }, "voter": { "email": "bo@example.com" } },
+                { "post": { "id": "missing" }, "voter": { "email": "cy@example.com" } }
+            ],
+            "comments": [
+                { "post": { "id": "p1" }, "value": "Yes!", "author": { "email": "bo@example.com" } },
+                { "post": { "id": "p1" }, "value": "Note to self", "internal": true }
+            ]
+        });
+        let parsed = parse_json(&export).unwrap();
+
+        let post = &parsed.posts[0];
+        assert_eq!(post.board.as_deref(), Some("Feature Requests"));
+        assert_eq!(post.author_email.as_deref(), Some("ada@example.com"));
+        assert_eq!(post.tags, vec!["ui"]);
+        assert_eq!(post.voters.len(), 1);
+        assert_eq!(post.comments.len(), 1);
+        assert_eq!(post.comments[0].body, "Yes!");
+        assert_eq!(
+            parsed.unmapped_fields.into_iter().collect::<Vec<_>>(),
+            vec!["posts.eta", "posts.score"]
+        );
+        assert_eq!(parsed.warnings.len(), 2);
+    }
+
+    #[test]
+    fn reads_post_export_csv() {
+        let csv = "Board,Title,Details,Status,Tags,Author Email,Voters,Owner\n\
+                   Ideas,Export,More formats,complete,\"api, csv\",ada@example.com,\"bo@example.com, cy@example.com\",Dee\n";
+        let parsed = parse_csv(&CsvTable::parse(csv.as_bytes()).unwrap()).unwrap();
+        let post = &parsed.posts[0];
+        assert_eq!(post.board.as_deref(), Some("Ideas"));
+        assert_eq!(post.tags, vec!["api", "csv"]);
+        assert_eq!(post.voters.len(), 2);
+        assert_eq!(
+            parsed.unmapped_fields.into_iter().collect::<Vec<_>>(),
+            vec!["owner"]
+        );
+    }
+}
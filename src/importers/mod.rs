@@ -0,0 +1,227 @@
+//! Parsers for feedback exported from other tools.
+//!
+//! Each source turns its export into plain `ImportPost`s (with board names,
+//! voters and comments filled in) and lists the fields it had to drop.
+//! Writing them to the database is up to `board_transfer_service`.
+
+mod canny;
+mod uservoice;
+
+use std::collections::BTreeSet;
+
+use serde_json::Value;
+
+use crate::errors::AppError;
+use crate::models::board_transfer::{ImportPost, ImportUser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Canny,
+    UserVoice,
+}
+
+impl Source {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "canny" => Some(Source::Canny),
+            "uservoice" => Some(Source::UserVoice),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ParsedImport {
+    pub posts: Vec<ImportPost>,
+    /// Dropped source fields, e.g. `posts.eta` or the `owner` CSV column.
+    pub unmapped_fields: BTreeSet<String>,
+    pub warnings: Vec<String>,
+}
+
+pub fn parse(source: Source, format: Format, data: &[u8]) -> Result<ParsedImport, AppError> {
+    match (source, format) {
+        (Source::Canny, Format::Json) => canny::parse_json(&parse_json_value(data)?),
+        (Source::Canny, Format::Csv) => canny::parse_csv(&CsvTable::parse(data)?),
+        (Source::UserVoice, Format::Json) => uservoice::parse_json(&parse_json_value(data)?),
+        (Source::UserVoice, Format::Csv) => uservoice::parse_csv(&CsvTable::parse(data)?),
+    }
+}
+
+fn parse_json_value(data: &[u8]) -> Result<Value, AppError> {
+    serde_json::from_slice(data)
+        .map_err(|e| AppError::BadRequest(format!("Invalid JSON import: {e}")))
+}
+
+// ── CSV ────────────────────────────────────────────────────────────
+
+/// A CSV file with headers normalized to `snake_case` ("Author Email" becomes
+/// `author_email`), so sources can be matched regardless of spelling.
+pub struct CsvTable {
+    headers: Vec<String>,
+    pub records: Vec<csv::StringRecord>,
+}
+
+impl CsvTable {
+    pub fn parse(data: &[u8]) -> Result<Self, AppError> {
+        let invalid = |e: csv::Error| AppError::BadRequest(format!("Invalid CSV import: {e}"));
+
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(data);
+        let headers = reader
+            .headers()
+            .map_err(invalid)?
+            .iter()
+            .map(|h| {
+                h.trim_start_matches('\u{feff}')
+                    .trim()
+                    .to_lowercase()
+                    .replace([' ', '-'], "_")
+            })
+            .collect();
+        let records = reader
+            .records()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(invalid)?;
+        Ok(CsvTable { headers, records })
+    }
+
+    /// Fail unless one of `names` is a column.
+    pub fn require(&self, names: &[&str]) -> Result<(), AppError> {
+        if self.headers.iter().any(|h| names.contains(&h.as_str())) {
+            Ok(())
+        } else {
+            Err(AppError::BadRequest(format!(
+                "CSV import needs a \"{}\" column",
+                names[0]
+            )))
+        }
+    }
+
+    /// The first non-empty cell among the columns called `names`.
+    pub fn get(&self, record: &csv::StringRecord, names: &[&str]) -> Option<String> {
+        names.iter().find_map(|name| {
+            self.headers
+                .iter()
+                .position(|h| h == name)
+                .and_then(|i| record.get(i))
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        })
+    }
+
+    /// Columns that are not in `known`.
+    pub fn unmapped(&self, known: &[&str]) -> BTreeSet<String> {
+        self.headers
+            .iter()
+            .filter(|h| !h.is_empty() && !known.contains(&h.as_str()))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Split a list cell on `;`, or on `,` when the cell has no `;`.
+pub fn split_list(cell: &str) -> Vec<String> {
+    let separator = if cell.contains(';') { ';' } else { ',' };
+    cell.split(separator)
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Voters given as a list of email addresses.
+fn voters_from_emails(cell: Option<String>) -> Vec<ImportUser> {
+    cell.map(|cell| {
+        split_list(&cell)
+            .into_iter()
+            .map(|email| ImportUser {
+                email: Some(email),
+                name: None,
+            })
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+// ── JSON ───────────────────────────────────────────────────────────
+
+/// A string field, accepting numbers too (ids are often numeric).
+fn text(value: &Value, key: &str) -> Option<String> {
+    match value.get(key)? {
+        Value::String(s) if !s.trim().is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// A related record given either as a plain string or as `{"name": ...}`.
+fn name_of(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(s) if !s.trim().is_empty() => Some(s.clone()),
+        object @ Value::Object(_) => text(object, "name"),
+        _ => None,
+    }
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// Record the keys of `object` that are not in `known` as `{prefix}.{key}`.
+fn note_unmapped(object: &Value, prefix: &str, known: &[&str], unmapped: &mut BTreeSet<String>) {
+    if let Some(map) = object.as_object() {
+        for key in map.keys() {
+            if !known.contains(&key.as_str()) {
+                unmapped.insert(format!("{prefix}.{key}"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_headers_are_normalized() {
+        let table =
+            CsvTable::parse(b"\xef\xbb\xbfTitle,Author Email,Vote-Count\nA,a@x.io,3\n").unwrap();
+        let record = &table.records[0];
+        assert_eq!(table.get(record, &["title"]).as_deref(), Some("A"));
+        assert_eq!(
+            table.get(record, &["email", "author_email"]).as_deref(),
+            Some("a@x.io")
+        );
+        assert_eq!(
+            table.unmapped(&["title", "author_email"]),
+            BTreeSet::from(["vote_count".to_string()])
+        );
+    }
+
+    #[test]
+    fn splits_lists_on_semicolons_first() {
+        assert_eq!(split_list("a, b;c"), vec!["a, b", "c"]);
+        assert_eq!(split_list("a, b,,c"), vec!["a", "b", "c"]);
+    }
+}
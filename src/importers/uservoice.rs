@@ -0,0 +1,282 @@
+//! UserVoice exports.
+//!
+//! JSON follows the UserVoice v2 admin API: `suggestions` refer to their
+//! forum, status, category, labels and creator through `links`, and the
+//! referenced `forums`, `statuses`, `categories`, `labels` and `users` are
+//! listed alongside (at the top level or under `linked`). `supporters` and
+//! `comments` link back to their suggestion the same way. Comments that are
+//! not `published` are skipped.
+//!
+//! CSV is the suggestions export: `Forum`, `Title`, `Description`, `Status`,
+//! `Category`, `Labels`, `Creator Name`, `Creator Email`, `Created At` and
+//! `Supporters` (email addresses).
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::{CsvTable, ParsedImport, array, note_unmapped, split_list, text, voters_from_emails};
+use crate::errors::AppError;
+use crate::models::board_transfer::{ImportComment, ImportPost, ImportUser};
+
+const SUGGESTION_FIELDS: &[&str] = &["id", "title", "body", "text", "created_at", "links"];
+const SUGGESTION_LINKS: &[&str] = &["forum", "status", "category", "labels", "created_by"];
+const COMMENT_FIELDS: &[&str] = &["id", "body", "text", "created_at", "state", "links"];
+
+const CSV_COLUMNS: &[&str] = &[
+    "forum",
+    "forum_name",
+    "title",
+    "description",
+    "text",
+    "status",
+    "category",
+    "labels",
+    "creator_name",
+    "creator_email",
+    "created_at",
+    "supporters",
+    "supporter_emails",
+];
+
+/// Records of one linked collection, by id.
+struct Lookup<'a>(HashMap<String, &'a Value>);
+
+impl<'a> Lookup<'a> {
+    fn new(root: &'a Value, name: &str) -> Self {
+        let records = root
+            .get("linked")
+            .filter(|linked| linked.get(name).is_some())
+            .map(|linked| array(linked, name))
+            .unwrap_or_else(|| array(root, name));
+        Lookup(
+            records
+                .iter()
+                .filter_map(|record| text(record, "id").map(|id| (id, record)))
+                .collect(),
+        )
+    }
+
+    fn get(&self, link: Option<&Value>) -> Option<&'a Value> {
+        let id = match link? {
+            Value::String(s) => s.clone(),
+            Value::Number(n) => n.to_string(),
+            _ => return None,
+        };
+        self.0.get(&id).copied()
+    }
+
+    fn name(&self, link: Option<&Value>) -> Option<String> {
+        self.get(link).and_then(|record| text(record, "name"))
+    }
+
+    fn user(&self, link: Option<&Value>) -> ImportUser {
+        let user = self.get(link);
+        ImportUser {
+            email: user.and_then(|u| text(u, "email_address").or_else(|| text(u, "email"))),
+            name: user.and_then(|u| text(u, "name")),
+        }
+    }
+}
+
+fn link<'a>(item: &'a Value, name: &str) -> Option<&'a Value> {
+    item.get("links")?.get(name)
+}
+
+pub fn parse_json(root: &Value) -> Result<ParsedImport, AppError> {
+    if root.get("suggestions").is_none() {
+        return Err(AppError::BadRequest(
+            "UserVoice import needs a \"suggestions\" array".to_string(),
+        ));
+    }
+    let forums = Lookup::new(root, "forums");
+    let statuses = Lookup::new(root, "statuses");
+    let categories = Lookup::new(root, "categories");
+    let labels = Lookup::new(root, "labels");
+    let users = Lookup::new(root, "users");
+
+    let mut parsed = ParsedImport::default();
+    let mut by_id: HashMap<String, usize> = HashMap::new();
+
+    for suggestion in array(root, "suggestions") {
+        note_unmapped(
+            suggestion,
+            "suggestions",
+            SUGGESTION_FIELDS,
+            &mut parsed.unmapped_fields,
+        );
+        if let Some(links) = suggestion.get("links") {
+            note_unmapped(
+                links,
+                "suggestions.links",
+                SUGGESTION_LINKS,
+                &mut parsed.unmapped_fields,
+            );
+        }
+        if let Some(id) = text(suggestion, "id") {
+            by_id.insert(id, parsed.posts.len());
+        }
+
+        let creator = users.user(link(suggestion, "created_by"));
+        parsed.posts.push(ImportPost {
+            board: forums.name(link(suggestion, "forum")),
+            title: text(suggestion, "title").unwrap_or_default(),
+            description: text(suggestion, "body").or_else(|| text(suggestion, "text")),
+            status: statuses.name(link(suggestion, "status")),
+            category: categories.name(link(suggestion, "category")),
+            tags: link(suggestion, "labels")
+                .and_then(Value::as_array)
+                .map(|ids| ids.iter().filter_map(|id| labels.name(Some(id))).collect())
+                .unwrap_or_default(),
+            author_email: creator.email,
+            author_name: creator.name,
+            created_at: text(suggestion, "created_at"),
+            ..Default::default()
+        });
+    }
+
+    let mut orphans = 0;
+    for supporter in array(root, "supporters") {
+        match suggestion_index(supporter, &by_id) {
+            Some(i) => parsed.posts[i]
+                .voters
+                .push(users.user(link(supporter, "user"))),
+            None => orphans += 1,
+        }
+    }
+
+    let mut unpublished = 0;
+    for comment in array(root, "comments") {
+        note_unmapped(
+            comment,
+            "comments",
+            COMMENT_FIELDS,
+            &mut parsed.unmapped_fields,
+        );
+        let Some(i) = suggestion_index(comment, &by_id) else {
+            orphans += 1;
+            continue;
+        };
+        if text(comment, "state").is_some_and(|state| state != "published") {
+            unpublished += 1;
+            continue;
+        }
+        let author = users.user(link(comment, "created_by"));
+        parsed.posts[i].comments.push(ImportComment {
+            body: text(comment, "body")
+                .or_else(|| text(comment, "text"))
+                .unwrap_or_default(),
+            author_email: author.email,
+            author_name: author.name,
+            created_at: text(comment, "created_at"),
+            ..Default::default()
+        });
+    }
+
+    if unpublished > 0 {
+        parsed.warnings.push(format!(
+            "Skipped {unpublished} comment(s) that were not published"
+        ));
+    }
+    if orphans > 0 {
+        parsed.warnings.push(format!(
+            "Skipped {orphans} supporter(s) or comment(s) whose suggestion is not in the export"
+        ));
+    }
+    Ok(parsed)
+}
+
+pub fn parse_csv(table: &CsvTable) -> Result<ParsedImport, AppError> {
+    table.require(&["title"])?;
+
+    let posts = table
+        .records
+        .iter()
+        .map(|record| ImportPost {
+            board: table.get(record, &["forum", "forum_name"]),
+            title: table.get(record, &["title"]).unwrap_or_default(),
+            description: table.get(record, &["description", "text"]),
+            status: table.get(record, &["status"]),
+            category: table.get(record, &["category"]),
+            tags: table
+                .get(record, &["labels"])
+                .map(|cell| split_list(&cell))
+                .unwrap_or_default(),
+            author_name: table.get(record, &["creator_name"]),
+            author_email: table.get(record, &["creator_email"]),
+            created_at: table.get(record, &["created_at"]),
+            voters: voters_from_emails(table.get(record, &["supporters", "supporter_emails"])),
+            ..Default::default()
+        })
+        .collect();
+
+    Ok(ParsedImport {
+        posts,
+        unmapped_fields: table.unmapped(CSV_COLUMNS),
+        warnings: Vec::new(),
+    })
+}
+
+fn suggestion_index(item: &Value, by_id: &HashMap<String, usize>) -> Option<usize> {
+    let id = match link(item, "suggestion")? {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        _ => return None,
+    };
+    by_id.get(&id).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn resolves_links_against_linked_records() {
+        let export = json!({
+            "suggestions": [{
+                "id": 1,
+                "title": "Offline mode",
+                "body": "For trains",
+                "state": "approved",
+                "created_at": "2019-05-06T07:08:09Z",
+                "links": { "forum": 10, "status": 20, "labels": [30], "created_by": 40, "ticket": 5 }
+            }],
+            "linked": {
+                "forums": [{ "id": 10, "name": "Mobile" }],
+                "statuses": [{ "id": 20, "name": "Started" }],
+                "labels": [{ "id": 30, "name": "sync" }],
+                "users": [
+                    { "id": 40, "name": "Ada", "email_address": "ada@example.com" },
+                    { "id": 41, "name": "Bo", "email_address": "bo@example.com" }
+                ]
+            },
+            "supporters": [{ "links": { "suggestion": 1, "user": 41 } }],
+            "comments": [
+                { "body": "Same here", "state": "published", "links": { "suggestion": 1, "created_by": 41 } },
+                { "body": "Buy now!", "state": "spam", "links": { "suggestion": 1 } }
+            ]
+        });
+        let parsed = parse_json(&export).unwrap();
+
+        let post = &parsed.posts[0];
+        assert_eq!(post.board.as_deref(), Some("Mobile"));
+        assert_eq!(post.status.as_deref(), Some("Started"));
+        assert_eq!(post.tags, vec!["sync"]);
+        assert_eq!(post.author_email.as_deref(), Some("ada@example.com"));
+        assert_eq!(post.voters[0].email.as_deref(), Some("bo@example.com"));
+        assert_eq!(post.comments.len(), 1);
+        assert_eq!(post.comments[0].author_name.as_deref(), Some("Bo"));
+        assert_eq!(
+            parsed.unmapped_fields.into_iter().collect::<Vec<_>>(),
+            vec!["suggestions.links.ticket", "suggestions.state"]
+        );
+        assert_eq!(parsed.warnings.len(), 1);
+    }
+
+    #[test]
+    fn rejects_json_without_suggestions() {
+        assert!(parse_json(&json!({ "posts": [] })).is_err());
+    }
+}
@@ -0,0 +1,229 @@
+//! Background job workers.
+//!
+//! Jobs are rows in the `jobs` table (see `job_service`). Each worker polls
+//! for due jobs of the kinds it knows, claims one with `FOR UPDATE SKIP
+//! LOCKED` so that several workers and server instances can share the queue,
+//! and records the outcome. Failed jobs are retried with exponential backoff
+//! and left `dead` after `max_attempts`, for an admin to inspect and retry.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+use sqlx::PgPool;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::errors::AppError;
+use crate::mailer::{Email, Mailer};
+use crate::models::changelog::PublishChangelogPayload;
+use crate::models::job::{
+    DeleteFilesPayload, EraseUserPayload, Job, KIND_DELETE_FILES, KIND_ERASE_USER, KIND_PRUNE_JOBS,
+    KIND_PUBLISH_CHANGELOG, KIND_RECONCILE_COUNTERS, KIND_SEND_EMAIL, STATUS_DEAD,
+};
+use crate::services::{changelog_service, counter_service, gdpr_service, job_service};
+use crate::storage::Storage;
+
+/// Kinds this build can run. Workers leave other kinds alone, so an older
+/// instance never claims jobs queued by a newer one.
+pub const KINDS: &[&str] = &[
+    KIND_SEND_EMAIL,
+    KIND_DELETE_FILES,
+    KIND_RECONCILE_COUNTERS,
+    KIND_PRUNE_JOBS,
+    KIND_PUBLISH_CHANGELOG,
+    KIND_ERASE_USER,
+];
+
+/// How long an idle worker waits before looking for due jobs again.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A job running longer than this counts as failed.
+const JOB_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// What jobs get to work with.
+#[derive(Clone)]
+pub struct JobContext {
+    pub pool: PgPool,
+    pub mailer: Arc<dyn Mailer>,
+    pub storage: Arc<dyn Storage>,
+}
+
+/// Running workers; call `shutdown` before exiting.
+pub struct WorkerPool {
+    shutdown: watch::Sender<bool>,
+    handles: Vec<JoinHandle<()>>,
+    heartbeat: Heartbeat,
+}
+
+/// When a worker of this process last looked for jobs, for the readiness
+/// check. The default has no workers.
+#[derive(Clone, Default)]
+pub struct Heartbeat {
+    workers: usize,
+    last: Arc<Mutex<Option<Instant>>>,
+}
+
+impl Heartbeat {
+    /// A worker that stops beating for longer than this is stuck: even a job
+    /// that runs into its timeout lets it beat again before then.
+    pub const STALE_AFTER: Duration = JOB_TIMEOUT.saturating_add(Duration::from_secs(30));
+
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Time since any worker last beat, or `None` if none has yet.
+    pub fn since_last(&self) -> Option<Duration> {
+        self.last.lock().unwrap().map(|at| at.elapsed())
+    }
+
+    fn beat(&self) {
+        *self.last.lock().unwrap() = Some(Instant::now());
+    }
+}
+
+pub fn spawn_workers(ctx: JobContext, count: usize) -> WorkerPool {
+    let (shutdown, receiver) = watch::channel(false);
+    let ctx = Arc::new(ctx);
+    let heartbeat = Heartbeat {
+        workers: count,
+        ..Default::default()
+    };
+    let handles = (0..count)
+        .map(|_| tokio::spawn(work(ctx.clone(), receiver.clone(), heartbeat.clone())))
+        .collect();
+    WorkerPool {
+        shutdown,
+        handles,
+        heartbeat,
+    }
+}
+
+impl WorkerPool {
+    pub fn heartbeat(&self) -> Heartbeat {
+        self.heartbeat.clone()
+    }
+
+    /// Stop claiming jobs and wait up to `grace` for running ones to finish.
+    /// Jobs still running after that are claimed again once they go stale.
+    pub async fn shutdown(self, grace: Duration) {
+        let _ = self.shutdown.send(true);
+        let all = futures_util::future::join_all(self.handles);
+        if tokio::time::timeout(grace, all).await.is_err() {
+            tracing::warn!("Background jobs still running at shutdown; they will be retried");
+        }
+    }
+}
+
+async fn work(ctx: Arc<JobContext>, mut shutdown: watch::Receiver<bool>, heartbeat: Heartbeat) {
+    while !*shutdown.borrow() {
+        heartbeat.beat();
+        match run_next(&ctx).await {
+            Ok(true) => continue,
+            Ok(false) => {}
+            Err(e) => tracing::warn!("Job queue error: {e}"),
+        }
+        tokio::select! {
+            _ = shutdown.changed() => {}
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+        }
+    }
+}
+
+/// Claim and run one due job. Returns whether there was one.
+pub async fn run_next(ctx: &JobContext) -> Result<bool, AppError> {
+    let Some(job) = job_service::claim_next(&ctx.pool, KINDS).await? else {
+        return Ok(false);
+    };
+
+    let result = tokio::time::timeout(JOB_TIMEOUT, perform(ctx, &job))
+        .await
+        .unwrap_or_else(|_| Err(format!("Timed out after {}s", JOB_TIMEOUT.as_secs())));
+
+    match result {
+        Ok(()) => job_service::complete(&ctx.pool, &job).await?,
+        Err(error) => {
+            let job = job_service::record_failure(&ctx.pool, &job, &error).await?;
+            if job.status == STATUS_DEAD {
+                tracing::error!(
+                    "Job {} ({}) failed for good after {} attempt(s): {error}",
+                    job.id,
+                    job.kind,
+                    job.attempts
+                );
+            } else {
+                tracing::warn!(
+                    "Job {} ({}) failed, retrying at {}: {error}",
+                    job.id,
+                    job.kind,
+                    job.run_at
+                );
+            }
+        }
+    }
+    Ok(true)
+}
+
+async fn perform(ctx: &JobContext, job: &Job) -> Result<(), String> {
+    match job.kind.as_str() {
+        KIND_SEND_EMAIL => {
+            let email: Email = payload(job)?;
+            ctx.mailer.send(email).await.map_err(|e| e.to_string())
+        }
+        KIND_DELETE_FILES => {
+            let DeleteFilesPayload { keys } = payload(job)?;
+            for key in &keys {
+                ctx.storage
+                    .delete(key)
+                    .await
+                    .map_err(|e| format!("{key}: {e}"))?;
+            }
+            Ok(())
+        }
+        KIND_RECONCILE_COUNTERS => {
+            let report = counter_service::reconcile(&ctx.pool, None, true)
+                .await
+                .map_err(|e| e.to_string())?;
+            if report.drifted.is_empty() {
+                tracing::debug!("Post counters are consistent");
+            } else {
+                tracing::warn!(
+                    "Fixed counter drift on {} of {} post(s)",
+                    report.drifted.len(),
+                    report.posts_checked
+                );
+            }
+            Ok(())
+        }
+        KIND_PRUNE_JOBS => {
+            let pruned = job_service::prune_completed(&ctx.pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            tracing::debug!("Pruned {pruned} completed job(s)");
+            Ok(())
+        }
+        KIND_PUBLISH_CHANGELOG => {
+            let PublishChangelogPayload { changelog_id } = payload(job)?;
+            let notified = changelog_service::announce(&ctx.pool, changelog_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            tracing::info!("Published changelog entry {changelog_id}; notified {notified} user(s)");
+            Ok(())
+        }
+        KIND_ERASE_USER => {
+            let EraseUserPayload {
+                user_id,
+                request_id,
+            } = payload(job)?;
+            gdpr_service::erase_user(&ctx.pool, user_id, request_id)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        other => Err(format!("Unknown job kind: {other}")),
+    }
+}
+
+fn payload<T: DeserializeOwned>(job: &Job) -> Result<T, String> {
+    serde_json::from_value(job.payload.clone()).map_err(|e| format!("Invalid payload: {e}"))
+}
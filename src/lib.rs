@@ -1,8 +1,369 @@
 pub mod config;
 pub mod db;
 pub mod errors;
+pub mod events;
 pub mod handlers;
+pub mod importers;
+pub mod jobs;
+pub mod mailer;
 pub mod middleware;
 pub mod models;
+pub mod openapi;
 pub mod services;
+pub mod shutdown;
+pub mod storage;
+pub mod telemetry;
 pub mod utils;
+pub mod vault;
+
+use std::sync::Arc;
+
+use actix_cors::Cors;
+use actix_web::dev::{ServiceFactory, ServiceRequest, ServiceResponse};
+use actix_web::middleware::from_fn;
+use actix_web::{App, http::header, web};
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+use config::Config;
+use events::EventHub;
+use jobs::Heartbeat;
+use mailer::Mailer;
+use middleware::deprecation::deprecated;
+use middleware::{http_metrics, request_id};
+use openapi::ApiDoc;
+use shutdown::Drain;
+use storage::Storage;
+
+/// Shared application state. Cloning is cheap, so the server can hand a
+/// copy to each worker.
+#[derive(Clone)]
+pub struct AppState {
+    pub pool: web::Data<PgPool>,
+    pub config: web::Data<Config>,
+    pub storage: web::Data<dyn Storage>,
+    pub mailer: web::Data<dyn Mailer>,
+    pub events: web::Data<EventHub>,
+    pub heartbeat: web::Data<Heartbeat>,
+    pub drain: web::Data<Drain>,
+}
+
+impl AppState {
+    pub fn new(
+        pool: PgPool,
+        config: Config,
+        storage: Arc<dyn Storage>,
+        mailer: Arc<dyn Mailer>,
+        heartbeat: Heartbeat,
+    ) -> Self {
+        Self {
+            events: web::Data::new(EventHub::new(pool.clone())),
+            pool: web::Data::new(pool),
+            config: web::Data::new(config),
+            storage: web::Data::from(storage),
+            mailer: web::Data::from(mailer),
+            heartbeat: web::Data::new(heartbeat),
+            drain: web::Data::new(Drain::default()),
+        }
+    }
+}
+
+/// The complete application: middleware, app data and routes. Used by the
+/// server binary and the integration tests.
+pub fn build_app(
+    state: AppState,
+) -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Config = (),
+        Response = ServiceResponse<impl actix_web::body::MessageBody>,
+        Error = actix_web::Error,
+        InitError = (),
+    >,
+> {
+    App::new()
+        .wrap(cors(&state.config))
+        .wrap(from_fn(http_metrics::record))
+        .wrap(from_fn(request_id::request_id))
+        .app_data(web::JsonConfig::default().error_handler(errors::json_error))
+        .app_data(web::PathConfig::default().error_handler(errors::path_error))
+        .app_data(web::QueryConfig::default().error_handler(errors::query_error))
+        .app_data(web::Data::new(telemetry::metrics_handle()))
+        .app_data(state.pool)
+        .app_data(state.events)
+        .app_data(state.config)
+        .app_data(state.storage)
+        .app_data(state.mailer)
+        .app_data(state.heartbeat)
+        .app_data(state.drain)
+        .configure(configure_app)
+}
+
+fn cors(config: &Config) -> Cors {
+    Cors::default()
+        .allowed_origin(&config.server.cors_origin)
+        .allowed_methods(vec!["GET", "POST", "PUT", "DELETE"])
+        .allowed_headers(vec![
+            header::AUTHORIZATION,
+            header::CONTENT_TYPE,
+            request_id::HEADER,
+        ])
+        .expose_headers(vec![request_id::HEADER])
+        .max_age(3600)
+}
+
+/// Registers the API routes and the OpenAPI docs.
+pub fn configure_app(cfg: &mut web::ServiceConfig) {
+    cfg.service(SwaggerUi::new("/api/docs/{_:.*}").url("/api/openapi.json", ApiDoc::openapi()))
+        .route("/metrics", web::get().to(handlers::metrics::metrics))
+        .service(web::scope("/api/v1").configure(api_v1))
+        .service(web::scope("/api/v2").configure(api_v2))
+        // Unversioned paths stay v1 for clients from before versioning
+        .service(web::scope("/api").configure(api_v1));
+}
+
+/// Deprecated in favour of the paginated `/api/v2` post list.
+const LIST_POSTS_DEPRECATED: NaiveDate = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+
+/// Routes under `/api/v1`. Response shapes here are frozen; a changed shape
+/// goes into v2 and the v1 endpoint is marked deprecated.
+#[allow(deprecated)]
+fn api_v1(cfg: &mut web::ServiceConfig) {
+    cfg.route("/health", web::get().to(handlers::health::health_check))
+        .route("/health/live", web::get().to(handlers::health::live))
+        .route("/health/ready", web::get().to(handlers::health::ready))
+        // Public feeds (no auth)
+        .service(
+            web::scope("/feeds/{org}")
+                .route(
+                    "/changelog.{format}",
+                    web::get().to(handlers::feeds::changelog_feed),
+                )
+                .route(
+                    "/boards/{slug}/posts.{format}",
+                    web::get().to(handlers::feeds::board_posts_feed),
+                )
+                .route(
+                    "/boards/{slug}/status.{format}",
+                    web::get().to(handlers::feeds::board_status_feed),
+                ),
+        )
+        // Auth
+        .service(
+            web::scope("/auth")
+                .route("/register", web::post().to(handlers::auth::register))
+                .route("/login", web::post().to(handlers::auth::login))
+                .route("/me", web::get().to(handlers::auth::me))
+                .route("/me", web::put().to(handlers::auth::update_me))
+                .route("/me", web::delete().to(handlers::auth::delete_me))
+                .route("/me/export", web::get().to(handlers::auth::export_me))
+                .route(
+                    "/me/password",
+                    web::put().to(handlers::auth::change_password),
+                )
+                .route(
+                    "/me/email",
+                    web::post().to(handlers::auth::request_email_change),
+                )
+                .route(
+                    "/email/confirm",
+                    web::post().to(handlers::auth::confirm_email_change),
+                )
+                .route(
+                    "/me/avatar",
+                    web::post().to(handlers::images::upload_avatar),
+                )
+                .route(
+                    "/me/avatar",
+                    web::delete().to(handlers::images::delete_avatar),
+                ),
+        )
+        // Organizations
+        .route(
+            "/orgs",
+            web::get().to(handlers::organizations::list_user_orgs),
+        )
+        .route(
+            "/orgs/{org_id}/members",
+            web::get().to(handlers::organizations::search_members),
+        )
+        .route(
+            "/orgs/{org_id}/import",
+            web::post().to(handlers::organizations::import_from_source),
+        )
+        .route(
+            "/orgs/{org_id}/counters/reconcile",
+            web::post().to(handlers::organizations::reconcile_counters),
+        )
+        .route(
+            "/orgs/{org_id}/jobs",
+            web::get().to(handlers::jobs::list_jobs),
+        )
+        .route(
+            "/orgs/{org_id}/jobs/{job_id}",
+            web::get().to(handlers::jobs::get_job),
+        )
+        .route(
+            "/orgs/{org_id}/jobs/{job_id}/retry",
+            web::post().to(handlers::jobs::retry_job),
+        )
+        .route(
+            "/orgs/{org_id}/logo",
+            web::post().to(handlers::images::upload_org_logo),
+        )
+        .route(
+            "/orgs/{org_id}/logo",
+            web::delete().to(handlers::images::delete_org_logo),
+        )
+        // Processed avatars and logos (public)
+        .route(
+            "/images/{path:.*}",
+            web::get().to(handlers::images::get_image),
+        )
+        // Notifications
+        .service(
+            web::scope("/notifications")
+                .route(
+                    "",
+                    web::get().to(handlers::notifications::list_notifications),
+                )
+                .route(
+                    "/read",
+                    web::post().to(handlers::notifications::mark_all_read),
+                ),
+        )
+        // Boards (under orgs)
+        .service(
+            web::scope("/orgs/{org_id}/boards")
+                .route("", web::get().to(handlers::boards::list_boards))
+                .route("", web::post().to(handlers::boards::create_board))
+                .route("/{slug}", web::get().to(handlers::boards::get_board))
+                .route("/{slug}", web::put().to(handlers::boards::update_board))
+                .route("/{slug}", web::delete().to(handlers::boards::delete_board))
+                .route(
+                    "/{slug}/export",
+                    web::get().to(handlers::boards::export_board),
+                )
+                .route(
+                    "/{slug}/import",
+                    web::post().to(handlers::boards::import_board),
+                ),
+        )
+        // Changelog (under orgs)
+        .service(
+            web::scope("/orgs/{org_id}/changelog")
+                .route("", web::get().to(handlers::changelog::list_entries))
+                .route("", web::post().to(handlers::changelog::create_entry))
+                .route("/{entry_id}", web::get().to(handlers::changelog::get_entry))
+                .route(
+                    "/{entry_id}",
+                    web::put().to(handlers::changelog::update_entry),
+                )
+                .route(
+                    "/{entry_id}",
+                    web::delete().to(handlers::changelog::delete_entry),
+                ),
+        )
+        // Posts (under boards)
+        .service(
+            web::scope("/boards/{board_id}/posts")
+                .route(
+                    "",
+                    web::get()
+                        .to(handlers::posts::list_posts)
+                        .wrap(deprecated(LIST_POSTS_DEPRECATED, None)),
+                )
+                .route("", web::post().to(handlers::posts::create_post))
+                .route("/{post_id}", web::get().to(handlers::posts::get_post))
+                .route("/{post_id}", web::put().to(handlers::posts::update_post))
+                .route("/{post_id}", web::delete().to(handlers::posts::delete_post))
+                .route(
+                    "/{post_id}/status",
+                    web::put().to(handlers::posts::update_status),
+                ),
+        )
+        // Live updates (Server-Sent Events)
+        .route(
+            "/boards/{board_id}/events",
+            web::get().to(handlers::events::board_events),
+        )
+        .route(
+            "/posts/{post_id}/events",
+            web::get().to(handlers::events::post_events),
+        )
+        // Tags (under boards)
+        .service(
+            web::scope("/boards/{board_id}/tags")
+                .route("", web::get().to(handlers::tags::list_tags))
+                .route("", web::post().to(handlers::tags::create_tag)),
+        )
+        // Direct post lookup (no board_id required)
+        .route(
+            "/posts/{post_id}",
+            web::get().to(handlers::posts::get_post_direct),
+        )
+        // Votes
+        .route(
+            "/posts/{post_id}/vote",
+            web::post().to(handlers::votes::toggle_vote),
+        )
+        // Reactions
+        .route(
+            "/posts/{post_id}/reactions",
+            web::post().to(handlers::reactions::toggle_post_reaction),
+        )
+        .route(
+            "/comments/{comment_id}/reactions",
+            web::post().to(handlers::reactions::toggle_comment_reaction),
+        )
+        // Attachments
+        .route(
+            "/posts/{post_id}/attachments",
+            web::post().to(handlers::attachments::upload_post_attachment),
+        )
+        .route(
+            "/comments/{comment_id}/attachments",
+            web::post().to(handlers::attachments::upload_comment_attachment),
+        )
+        .route(
+            "/attachments/{attachment_id}",
+            web::get().to(handlers::attachments::get_attachment),
+        )
+        .route(
+            "/attachments/{attachment_id}",
+            web::delete().to(handlers::attachments::delete_attachment),
+        )
+        // Comments
+        .service(
+            web::scope("/posts/{post_id}/comments")
+                .route("", web::get().to(handlers::comments::list_comments))
+                .route("", web::post().to(handlers::comments::create_comment)),
+        )
+        // Comment delete + Tag delete + post tag assignment
+        .route(
+            "/comments/{comment_id}",
+            web::delete().to(handlers::comments::delete_comment),
+        )
+        .route(
+            "/tags/{tag_id}",
+            web::delete().to(handlers::tags::delete_tag),
+        )
+        .route(
+            "/posts/{post_id}/tags/{tag_id}",
+            web::post().to(handlers::tags::add_tag_to_post),
+        )
+        .route(
+            "/posts/{post_id}/tags/{tag_id}",
+            web::delete().to(handlers::tags::remove_tag_from_post),
+        );
+}
+
+/// Routes under `/api/v2`, for endpoints whose v1 response shape changed.
+fn api_v2(cfg: &mut web::ServiceConfig) {
+    cfg.route(
+        "/boards/{board_id}/posts",
+        web::get().to(handlers::posts::list_posts_page),
+    );
+}
@@ -0,0 +1,73 @@
+//! Outgoing email.
+//!
+//! The backend is chosen by `mail.backend` (`MAIL_BACKEND`). Only `log` is built in: messages
+//! are written to the application log instead of being delivered, which is
+//! enough for development and single-user installs.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::errors::AppError;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to send email: {0}")]
+pub struct MailError(pub String);
+
+impl From<MailError> for AppError {
+    fn from(e: MailError) -> Self {
+        AppError::InternalError(e.to_string())
+    }
+}
+
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, email: Email) -> Result<(), MailError>;
+
+    /// Confirm the backend can currently accept mail, for the readiness check.
+    async fn check(&self) -> Result<(), MailError> {
+        Ok(())
+    }
+}
+
+/// Writes every message to the log at `info` level.
+pub struct LogMailer {
+    from: String,
+}
+
+impl LogMailer {
+    pub fn new(from: impl Into<String>) -> Self {
+        LogMailer { from: from.into() }
+    }
+}
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, email: Email) -> Result<(), MailError> {
+        tracing::info!(
+            "Email from {} to {}: {}\n{}",
+            self.from,
+            email.to,
+            email.subject,
+            email.body
+        );
+        Ok(())
+    }
+}
+
+/// Build the mailer selected in `config`.
+pub fn from_config(config: &Config) -> Result<Arc<dyn Mailer>, String> {
+    match config.mail.backend.as_str() {
+        "log" => Ok(Arc::new(LogMailer::new(&config.mail.from))),
+        other => Err(format!("Unknown MAIL_BACKEND: {other}")),
+    }
+}
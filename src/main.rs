@@ -1,142 +1,156 @@
-use actix_cors::Cors;
-use actix_web::middleware::Logger;
-use actix_web::{App, HttpServer, web};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use actix_web::HttpServer;
+use clap::Parser;
+use sqlx::PgPool;
 use tracing_subscriber::EnvFilter;
 
-mod config;
-mod db;
-mod errors;
-mod handlers;
-mod middleware;
-mod models;
-mod services;
-mod utils;
-mod vault;
+use rivvo::models::job::{KIND_PRUNE_JOBS, KIND_RECONCILE_COUNTERS};
+use rivvo::services::job_service;
+use rivvo::{AppState, build_app, config, db, jobs, mailer, shutdown, storage, telemetry, vault};
+
+#[derive(Parser)]
+#[command(name = "rivvo", about = "Run the Rivvo server")]
+struct Args {
+    /// TOML configuration file [default: $RIVVO_CONFIG, else ./rivvo.toml if present]
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Print the effective configuration with secrets redacted, then exit
+    #[arg(long)]
+    print_config: bool,
+}
 
 #[tokio::main]
-async fn main() -> std::io::Result<()> {
+async fn main() -> std::io::Result<ExitCode> {
+    let args = Args::parse();
     dotenvy::dotenv().ok();
 
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env())
-        .init();
+    // Log to stdout until the configuration says where else traces go
+    let startup_logs = tracing::subscriber::set_default(
+        tracing_subscriber::fmt()
+            .with_env_filter(EnvFilter::from_default_env())
+            .finish(),
+    );
+
+    // The secret store settings come first: its secrets fill in the rest
+    let secrets = match config::Config::load_secrets(args.config.as_deref()) {
+        Ok(secrets_config) => vault::fetch_secrets(&secrets_config).await,
+        Err(e) => {
+            eprintln!("{e}");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
 
-    vault::fetch_secrets().await;
+    let config = match config::Config::load(args.config.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{e}");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+    if args.print_config {
+        print!("{}", config.to_redacted_toml());
+        return Ok(ExitCode::SUCCESS);
+    }
 
-    let config = config::Config::from_env().expect("Failed to load configuration");
+    drop(startup_logs);
+    let _tracing = telemetry::init_tracing(&config);
 
-    let pool = db::create_pool(&config.database_url, config.max_db_connections)
+    let pool = db::create_pool(&config.database.url, config.database.max_connections)
         .await
         .expect("Failed to create database pool");
 
-    sqlx::migrate!("./migrations")
-        .run(&pool)
+    db::run_migrations(&pool)
         .await
         .expect("Failed to run database migrations");
 
-    let host = config.host.clone();
-    let port = config.port;
+    if let Some(secrets) = secrets {
+        let pool = pool.clone();
+        secrets.spawn_refresh(move |key, value| apply_rotated_secret(&pool, key, value));
+    }
+
+    let storage = storage::from_config(&config).expect("Failed to initialize file storage");
+    let mailer = mailer::from_config(&config).expect("Failed to initialize mailer");
+
+    if config.jobs.counter_reconcile_interval_secs > 0 {
+        job_service::schedule_recurring(
+            &pool,
+            KIND_RECONCILE_COUNTERS,
+            Duration::from_secs(config.jobs.counter_reconcile_interval_secs),
+        )
+        .await
+        .expect("Failed to schedule counter reconciliation");
+    } else {
+        job_service::unschedule_recurring(&pool, KIND_RECONCILE_COUNTERS)
+            .await
+            .expect("Failed to unschedule counter reconciliation");
+    }
+    job_service::schedule_recurring(&pool, KIND_PRUNE_JOBS, Duration::from_secs(24 * 3600))
+        .await
+        .expect("Failed to schedule job pruning");
+
+    let workers = jobs::spawn_workers(
+        jobs::JobContext {
+            pool: pool.clone(),
+            mailer: mailer.clone(),
+            storage: storage.clone(),
+        },
+        config.jobs.workers,
+    );
+
+    let host = config.server.host.clone();
+    let port = config.server.port;
+    let shutdown_delay = Duration::from_secs(config.server.shutdown_delay_secs);
+    let shutdown_timeout = Duration::from_secs(config.server.shutdown_timeout_secs);
 
     tracing::info!("Starting server at http://{}:{}", host, port);
 
-    let cors_origin = config.cors_origin.clone();
-    let config_data = web::Data::new(config);
-    let pool_data = web::Data::new(pool);
-
-    HttpServer::new(move || {
-        let cors = Cors::default()
-            .allowed_origin(&cors_origin)
-            .allowed_methods(vec!["GET", "POST", "PUT", "DELETE"])
-            .allowed_headers(vec![
-                actix_web::http::header::AUTHORIZATION,
-                actix_web::http::header::CONTENT_TYPE,
-            ])
-            .max_age(3600);
-
-        App::new()
-            .wrap(cors)
-            .wrap(Logger::default())
-            .app_data(pool_data.clone())
-            .app_data(config_data.clone())
-            .service(
-                web::scope("/api")
-                    .route("/health", web::get().to(handlers::health::health_check))
-                    // Auth
-                    .service(
-                        web::scope("/auth")
-                            .route("/register", web::post().to(handlers::auth::register))
-                            .route("/login", web::post().to(handlers::auth::login))
-                            .route("/me", web::get().to(handlers::auth::me)),
-                    )
-                    // Organizations
-                    .route(
-                        "/orgs",
-                        web::get().to(handlers::organizations::list_user_orgs),
-                    )
-                    // Boards (under orgs)
-                    .service(
-                        web::scope("/orgs/{org_id}/boards")
-                            .route("", web::get().to(handlers::boards::list_boards))
-                            .route("", web::post().to(handlers::boards::create_board))
-                            .route("/{slug}", web::get().to(handlers::boards::get_board))
-                            .route("/{slug}", web::put().to(handlers::boards::update_board))
-                            .route("/{slug}", web::delete().to(handlers::boards::delete_board)),
-                    )
-                    // Posts (under boards)
-                    .service(
-                        web::scope("/boards/{board_id}/posts")
-                            .route("", web::get().to(handlers::posts::list_posts))
-                            .route("", web::post().to(handlers::posts::create_post))
-                            .route("/{post_id}", web::get().to(handlers::posts::get_post))
-                            .route("/{post_id}", web::put().to(handlers::posts::update_post))
-                            .route("/{post_id}", web::delete().to(handlers::posts::delete_post))
-                            .route(
-                                "/{post_id}/status",
-                                web::put().to(handlers::posts::update_status),
-                            ),
-                    )
-                    // Tags (under boards)
-                    .service(
-                        web::scope("/boards/{board_id}/tags")
-                            .route("", web::get().to(handlers::tags::list_tags))
-                            .route("", web::post().to(handlers::tags::create_tag)),
-                    )
-                    // Direct post lookup (no board_id required)
-                    .route(
-                        "/posts/{post_id}",
-                        web::get().to(handlers::posts::get_post_direct),
-                    )
-                    // Votes
-                    .route(
-                        "/posts/{post_id}/vote",
-                        web::post().to(handlers::votes::toggle_vote),
-                    )
-                    // Comments
-                    .service(
-                        web::scope("/posts/{post_id}/comments")
-                            .route("", web::get().to(handlers::comments::list_comments))
-                            .route("", web::post().to(handlers::comments::create_comment)),
-                    )
-                    // Comment delete + Tag delete + post tag assignment
-                    .route(
-                        "/comments/{comment_id}",
-                        web::delete().to(handlers::comments::delete_comment),
-                    )
-                    .route(
-                        "/tags/{tag_id}",
-                        web::delete().to(handlers::tags::delete_tag),
-                    )
-                    .route(
-                        "/posts/{post_id}/tags/{tag_id}",
-                        web::post().to(handlers::tags::add_tag_to_post),
-                    )
-                    .route(
-                        "/posts/{post_id}/tags/{tag_id}",
-                        web::delete().to(handlers::tags::remove_tag_from_post),
-                    ),
-            )
-    })
-    .bind(format!("{host}:{port}"))?
-    .run()
-    .await
+    let state = AppState::new(pool.clone(), config, storage, mailer, workers.heartbeat());
+    let drain = state.drain.clone();
+
+    let mut server = HttpServer::new(move || build_app(state.clone()))
+        .bind(format!("{host}:{port}"))?
+        .disable_signals()
+        .shutdown_timeout(shutdown_timeout.as_secs())
+        .run();
+    let handle = server.handle();
+
+    tokio::select! {
+        result = &mut server => {
+            result?;
+            workers.shutdown(shutdown_timeout).await;
+        }
+        () = shutdown::signal() => {
+            tracing::info!("Shutting down: readiness now fails");
+            drain.start();
+            tokio::time::sleep(shutdown_delay).await;
+
+            tracing::info!("Draining in-flight requests and jobs");
+            // The server only acts on the stop command while it is polled
+            let (_, result, _) = tokio::join!(
+                handle.stop(true),
+                server,
+                workers.shutdown(shutdown_timeout)
+            );
+            result?;
+        }
+    }
+
+    pool.close().await;
+    tracing::info!("Shutdown complete");
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Put a secret rotated in the secret store into effect where that's possible
+/// without a restart.
+fn apply_rotated_secret(pool: &PgPool, key: &str, value: &str) {
+    match key {
+        "DATABASE_URL" => match db::set_url(pool, value) {
+            Ok(()) => tracing::info!("New database connections use the rotated DATABASE_URL"),
+            Err(e) => tracing::warn!("Ignoring rotated DATABASE_URL: {e}"),
+        },
+        _ => tracing::warn!("Secret {key} changed; restart the server to apply it"),
+    }
 }
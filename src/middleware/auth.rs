@@ -1,12 +1,11 @@
 use actix_web::dev::Payload;
-use actix_web::{
-    Error, FromRequest, HttpRequest, error::ErrorInternalServerError, error::ErrorUnauthorized,
-};
-use serde_json::json;
-use std::future::{Ready, ready};
+use actix_web::{FromRequest, HttpRequest, web};
+use futures_util::future::LocalBoxFuture;
+use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::config::Config;
+use crate::errors::AppError;
 use crate::utils::jwt;
 
 pub struct AuthenticatedUser {
@@ -14,37 +13,51 @@ pub struct AuthenticatedUser {
 }
 
 impl FromRequest for AuthenticatedUser {
-    type Error = Error;
-    type Future = Ready<Result<Self, Self::Error>>;
+    type Error = AppError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
 
     fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
-        let result = extract_user(req);
-        ready(result)
+        let req = req.clone();
+        Box::pin(async move { extract_user(&req).await })
     }
 }
 
-fn extract_user(req: &HttpRequest) -> Result<AuthenticatedUser, Error> {
+async fn extract_user(req: &HttpRequest) -> Result<AuthenticatedUser, AppError> {
     let auth_header = req
         .headers()
         .get("Authorization")
         .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| ErrorUnauthorized(json!({ "error": "Missing Authorization header" })))?;
+        .ok_or_else(|| AppError::Unauthorized("Missing Authorization header".to_string()))?;
 
-    let token = auth_header.strip_prefix("Bearer ").ok_or_else(|| {
-        ErrorUnauthorized(json!({ "error": "Invalid Authorization header format" }))
-    })?;
+    let token = auth_header
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| AppError::Unauthorized("Invalid Authorization header format".to_string()))?;
 
     let config = req
-        .app_data::<actix_web::web::Data<Config>>()
-        .ok_or_else(|| {
-            ErrorInternalServerError(json!({ "error": "Server configuration error" }))
-        })?;
+        .app_data::<web::Data<Config>>()
+        .ok_or_else(|| AppError::InternalError("Server configuration error".to_string()))?;
+    let pool = req
+        .app_data::<web::Data<PgPool>>()
+        .ok_or_else(|| AppError::InternalError("Server configuration error".to_string()))?;
 
-    let claims = jwt::decode_token(token, &config.jwt_secret).map_err(|e| {
+    let claims = jwt::decode_token(token, &config.auth.jwt_secret).map_err(|e| {
         tracing::debug!("Token decode failed: {e}");
-        ErrorUnauthorized(json!({ "error": "Invalid or expired token" }))
+        AppError::Unauthorized("Invalid or expired token".to_string())
     })?;
 
+    // Tokens of deleted users, or from before a password change, are revoked
+    let version: Option<i32> = sqlx::query_scalar("SELECT token_version FROM users WHERE id = $1")
+        .bind(claims.sub)
+        .fetch_optional(pool.get_ref())
+        .await?;
+    if version != Some(claims.ver) {
+        return Err(AppError::Unauthorized(
+            "Invalid or expired token".to_string(),
+        ));
+    }
+
+    tracing::Span::current().record("user_id", tracing::field::display(claims.sub));
+
     Ok(AuthenticatedUser {
         user_id: claims.sub,
     })
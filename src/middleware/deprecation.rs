@@ -0,0 +1,21 @@
+//! Headers announcing that an endpoint is deprecated: `Deprecation`
+//! (RFC 9745) with the date it was deprecated and, once a removal date is
+//! set, `Sunset` (RFC 8594).
+
+use actix_web::middleware::DefaultHeaders;
+use chrono::{NaiveDate, NaiveTime};
+
+pub fn deprecated(since: NaiveDate, sunset: Option<NaiveDate>) -> DefaultHeaders {
+    let since = since.and_time(NaiveTime::MIN).and_utc();
+    let headers = DefaultHeaders::new().add(("Deprecation", format!("@{}", since.timestamp())));
+    match sunset {
+        Some(sunset) => {
+            let sunset = sunset.and_time(NaiveTime::MIN).and_utc();
+            headers.add((
+                "Sunset",
+                sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+            ))
+        }
+        None => headers,
+    }
+}
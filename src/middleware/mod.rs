@@ -1 +1,4 @@
 pub mod auth;
+pub mod deprecation;
+pub mod http_metrics;
+pub mod request_id;
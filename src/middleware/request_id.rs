@@ -0,0 +1,93 @@
+//! Gives every request an id, echoed in the `X-Request-Id` response header
+//! and in error bodies so a report from a client can be matched to the
+//! server logs. A well-formed id sent by the client (e.g. from a proxy) is
+//! kept; otherwise a UUID is generated.
+//!
+//! The request is handled inside a `request` span carrying the id. The auth
+//! extractor and the membership check fill in its `user_id` and `org_id`.
+
+use std::time::Instant;
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpMessage};
+use tracing::Instrument;
+use tracing::field::Empty;
+use uuid::Uuid;
+
+use crate::telemetry;
+
+pub const HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+const MAX_LEN: usize = 128;
+
+/// The id of a request, also available as a request extension.
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+tokio::task_local! {
+    static CURRENT: String;
+}
+
+/// The id of the request being handled, if called while handling one.
+pub fn current() -> Option<String> {
+    CURRENT.try_with(Clone::clone).ok()
+}
+
+pub async fn request_id(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let id = req
+        .headers()
+        .get(&HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| is_valid(v))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = %id,
+        method = %req.method(),
+        path = %req.path(),
+        user_id = Empty,
+        org_id = Empty,
+        otel.kind = Empty,
+    );
+    telemetry::start_request_span(&span, req.headers());
+    let method = req.method().to_string();
+    let started = Instant::now();
+
+    req.extensions_mut().insert(RequestId(id.clone()));
+    let mut res = CURRENT
+        .scope(id.clone(), next.call(req))
+        .instrument(span.clone())
+        .await?;
+    let route = res
+        .request()
+        .match_pattern()
+        .unwrap_or_else(|| "unmatched".to_string());
+    telemetry::finish_request_span(&span, &method, &route, res.status().as_u16());
+    tracing::info!(
+        parent: &span,
+        status = res.status().as_u16(),
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "request completed"
+    );
+
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        res.headers_mut().insert(HEADER, value);
+    }
+    Ok(res)
+}
+
+fn is_valid(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_LEN
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+}
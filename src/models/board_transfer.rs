@@ -0,0 +1,124 @@
+//! Shapes for moving a board's posts in and out of Rivvo.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Full board export. Also accepted as JSON import input (only `posts` is read).
+#[derive(Debug, Serialize)]
+pub struct BoardExport {
+    pub exported_at: DateTime<Utc>,
+    pub board: BoardExportInfo,
+    pub posts: Vec<BoardExportPost>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BoardExportInfo {
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct BoardExportPostRow {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: Option<String>,
+    pub vote_count: Option<i32>,
+    pub comment_count: Option<i32>,
+    pub pinned: Option<bool>,
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BoardExportPost {
+    #[serde(flatten)]
+    pub post: BoardExportPostRow,
+    pub tags: Vec<String>,
+    pub comments: Vec<BoardExportComment>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct BoardExportComment {
+    #[serde(skip)]
+    pub post_id: Uuid,
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+    pub body: String,
+    pub is_admin_reply: Option<bool>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// One post to import, as parsed from JSON, CSV or another tool's export
+/// before validation.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ImportPost {
+    /// Board name; only used by organization-wide imports.
+    pub board: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: Option<String>,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+    pub pinned: Option<bool>,
+    pub author_email: Option<String>,
+    pub author_name: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub voters: Vec<ImportUser>,
+    pub comments: Vec<ImportComment>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ImportComment {
+    pub body: String,
+    pub author_email: Option<String>,
+    pub author_name: Option<String>,
+    pub is_admin_reply: Option<bool>,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ImportUser {
+    pub email: Option<String>,
+    pub name: Option<String>,
+}
+
+/// JSON import envelope; matches the export so exports can be re-imported.
+#[derive(Debug, Deserialize)]
+pub struct ImportFile {
+    pub posts: Vec<ImportPost>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportRowError {
+    /// 1-based position of the post in the input (CSV header excluded).
+    pub row: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    /// False when any row failed validation; nothing is written in that case.
+    pub valid: bool,
+    pub posts: usize,
+    pub comments: usize,
+    pub votes: usize,
+    pub boards_created: Vec<String>,
+    pub tags_created: Vec<String>,
+    /// Users created for authors and voters that are not org members.
+    pub placeholder_users: usize,
+    /// Source fields that have no place in Rivvo and were dropped.
+    pub unmapped_fields: Vec<String>,
+    pub warnings: Vec<String>,
+    pub errors: Vec<ImportRowError>,
+}
@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Entry states, derived from `is_draft` and `published_at`.
+pub const STATUS_DRAFT: &str = "draft";
+pub const STATUS_SCHEDULED: &str = "scheduled";
+pub const STATUS_PUBLISHED: &str = "published";
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ChangelogEntry {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub title: String,
+    pub body: String,
+    pub published_at: Option<DateTime<Utc>>,
+    pub is_draft: Option<bool>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Move linked posts to `done` when the entry is published
+    pub complete_posts: bool,
+    /// When voters and subscribers of linked posts were notified
+    pub announced_at: Option<DateTime<Utc>>,
+}
+
+impl ChangelogEntry {
+    pub fn status(&self, now: DateTime<Utc>) -> &'static str {
+        match self.published_at {
+            _ if self.is_draft.unwrap_or(true) => STATUS_DRAFT,
+            Some(at) if at <= now => STATUS_PUBLISHED,
+            _ => STATUS_SCHEDULED,
+        }
+    }
+}
+
+/// Fields an admin sets when creating or editing an entry.
+#[derive(Debug)]
+pub struct ChangelogInput<'a> {
+    pub title: &'a str,
+    pub body: &'a str,
+    pub is_draft: bool,
+    /// Defaults to now when publishing
+    pub published_at: Option<DateTime<Utc>>,
+    pub complete_posts: bool,
+    pub post_ids: &'a [Uuid],
+}
+
+/// A post linked from an entry.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct ChangelogPostRef {
+    #[serde(skip)]
+    pub changelog_id: Uuid,
+    pub id: Uuid,
+    pub board_id: Uuid,
+    pub title: String,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ChangelogEntryResponse {
+    pub id: Uuid,
+    pub title: String,
+    pub body_markdown: String,
+    /// Sanitized HTML rendering of `body_markdown`.
+    pub body_html: String,
+    /// `draft`, `scheduled` or `published`
+    pub status: &'static str,
+    pub published_at: Option<DateTime<Utc>>,
+    pub complete_posts: bool,
+    pub announced_at: Option<DateTime<Utc>>,
+    pub posts: Vec<ChangelogPostRef>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Payload of the `publish_changelog` job.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PublishChangelogPayload {
+    pub changelog_id: Uuid,
+}
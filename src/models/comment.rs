@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
@@ -25,16 +26,22 @@ pub struct CommentWithAuthorRow {
     pub author_avatar_url: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CommentResponse {
     pub id: Uuid,
-    pub body: String,
+    pub body_markdown: String,
+    /// Sanitized HTML rendering of `body_markdown`.
+    pub body_html: String,
+    /// Resolved `@name` spans in `body_markdown`.
+    pub mentions: Vec<super::mention::MentionSpan>,
     pub is_admin_reply: bool,
     pub author: Option<CommentAuthor>,
+    pub reactions: Vec<super::reaction::ReactionSummary>,
+    pub attachments: Vec<super::attachment::AttachmentResponse>,
     pub created_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CommentAuthor {
     pub id: Uuid,
     pub name: String,
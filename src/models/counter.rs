@@ -0,0 +1,23 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A post whose denormalized counters disagree with the `votes` and
+/// `comments` tables.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct CounterDrift {
+    pub post_id: Uuid,
+    pub board_id: Option<Uuid>,
+    pub stored_vote_count: Option<i32>,
+    pub actual_vote_count: i32,
+    pub stored_comment_count: Option<i32>,
+    pub actual_comment_count: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CounterReport {
+    pub posts_checked: i64,
+    pub drifted: Vec<CounterDrift>,
+    /// Whether the drifted counters were rewritten.
+    pub fixed: bool,
+}
@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+pub const KIND_EXPORT: &str = "export";
+pub const KIND_ERASURE: &str = "erasure";
+
+/// Audit record of a GDPR export or erasure. Kept after the user is gone.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct DataRequest {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: String,
+    pub status: String,
+    pub error: Option<String>,
+    pub requested_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Everything stored about a user, as handed out by the export endpoint.
+#[derive(Debug, Serialize)]
+pub struct UserDataExport {
+    pub exported_at: DateTime<Utc>,
+    pub profile: ExportProfile,
+    pub memberships: Vec<ExportMembership>,
+    pub posts: Vec<ExportPost>,
+    pub comments: Vec<ExportComment>,
+    pub votes: Vec<ExportVote>,
+    pub subscriptions: Vec<ExportSubscription>,
+    pub reactions: Vec<ExportReaction>,
+    pub attachments: Vec<ExportAttachment>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ExportProfile {
+    pub id: Uuid,
+    pub email: String,
+    pub name: String,
+    pub avatar_url: Option<String>,
+    pub provider: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ExportMembership {
+    pub org_id: Uuid,
+    pub org_name: String,
+    pub role: Option<String>,
+    pub joined_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ExportPost {
+    pub id: Uuid,
+    pub board_id: Uuid,
+    pub board_name: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ExportComment {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub post_title: String,
+    pub body: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ExportVote {
+    pub post_id: Uuid,
+    pub post_title: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ExportSubscription {
+    pub post_id: Uuid,
+    pub post_title: String,
+    pub notify_on: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ExportReaction {
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub emoji: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ExportAttachment {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub comment_id: Option<Uuid>,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_at: Option<DateTime<Utc>>,
+}
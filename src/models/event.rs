@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Postgres channel board events are published on.
+pub const CHANNEL: &str = "board_events";
+
+/// A change on a board, pushed to live clients. Events carry ids and new
+/// counts only; clients refetch anything else they need.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BoardEvent {
+    pub board_id: Uuid,
+    pub post_id: Uuid,
+    #[serde(flatten)]
+    pub kind: BoardEventKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BoardEventKind {
+    PostCreated {
+        title: String,
+    },
+    VoteCountChanged {
+        vote_count: i32,
+    },
+    StatusChanged {
+        status: String,
+    },
+    CommentCreated {
+        comment_id: Uuid,
+        comment_count: i32,
+    },
+}
+
+impl BoardEvent {
+    /// The SSE `event:` name, e.g. `vote_count_changed`
+    pub fn name(&self) -> &'static str {
+        match self.kind {
+            BoardEventKind::PostCreated { .. } => "post_created",
+            BoardEventKind::VoteCountChanged { .. } => "vote_count_changed",
+            BoardEventKind::StatusChanged { .. } => "status_changed",
+            BoardEventKind::CommentCreated { .. } => "comment_created",
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// A post in a board's "new posts" feed.
+#[derive(Debug, sqlx::FromRow)]
+pub struct FeedPostRow {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub author_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A row of `post_status_changes` joined with its post.
+#[derive(Debug, sqlx::FromRow)]
+pub struct StatusChangeRow {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub post_title: String,
+    pub from_status: Option<String>,
+    pub to_status: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
@@ -0,0 +1,63 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+pub const STATUS_OK: &str = "ok";
+pub const STATUS_ERROR: &str = "error";
+/// The component is switched off in this process, e.g. no job workers.
+pub const STATUS_DISABLED: &str = "disabled";
+/// The process is shutting down and should get no new traffic.
+pub const STATUS_DRAINING: &str = "draining";
+
+/// Build information, part of both health responses.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BuildInfo {
+    /// Crate version
+    pub version: &'static str,
+    /// Git commit the binary was built from, if `RIVVO_GIT_COMMIT` was set
+    /// at build time
+    pub commit: Option<&'static str>,
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            commit: option_env!("RIVVO_GIT_COMMIT"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LivenessReport {
+    pub status: &'static str,
+    #[serde(flatten)]
+    pub build: BuildInfo,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessReport {
+    /// `ok` when no component reports `error`, `draining` while shutting down
+    pub status: &'static str,
+    #[serde(flatten)]
+    pub build: BuildInfo,
+    /// Keyed by component: database, migrations, jobs, mail, storage
+    pub checks: BTreeMap<&'static str, ComponentHealth>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ComponentHealth {
+    /// `ok`, `error` or `disabled`
+    pub status: &'static str,
+    /// How long the check took
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ReadinessReport {
+    pub fn is_ready(&self) -> bool {
+        self.status == STATUS_OK
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_RUNNING: &str = "running";
+pub const STATUS_COMPLETED: &str = "completed";
+pub const STATUS_DEAD: &str = "dead";
+pub const STATUSES: &[&str] = &[
+    STATUS_PENDING,
+    STATUS_RUNNING,
+    STATUS_COMPLETED,
+    STATUS_DEAD,
+];
+
+pub const KIND_SEND_EMAIL: &str = "send_email";
+pub const KIND_DELETE_FILES: &str = "delete_files";
+pub const KIND_RECONCILE_COUNTERS: &str = "reconcile_counters";
+pub const KIND_PRUNE_JOBS: &str = "prune_jobs";
+pub const KIND_PUBLISH_CHANGELOG: &str = "publish_changelog";
+pub const KIND_ERASE_USER: &str = "erase_user";
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, ToSchema)]
+pub struct Job {
+    pub id: Uuid,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub org_id: Option<Uuid>,
+    pub status: String,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: DateTime<Utc>,
+    pub locked_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub unique_key: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// A job to enqueue. Fill in `kind` and `payload` and take the rest from
+/// `Default`.
+#[derive(Debug, Clone)]
+pub struct NewJob {
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub org_id: Option<Uuid>,
+    /// Run as soon as a worker is free when `None`
+    pub run_at: Option<DateTime<Utc>>,
+    pub max_attempts: i32,
+    /// Skip enqueueing when a pending job with this key already exists
+    pub unique_key: Option<String>,
+}
+
+impl Default for NewJob {
+    fn default() -> Self {
+        NewJob {
+            kind: String::new(),
+            payload: serde_json::Value::Object(Default::default()),
+            org_id: None,
+            run_at: None,
+            max_attempts: 5,
+            unique_key: None,
+        }
+    }
+}
+
+/// Payload of `delete_files`: storage keys whose database rows are gone.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteFilesPayload {
+    pub keys: Vec<String>,
+}
+
+/// Payload of `erase_user`: the account and the `data_requests` entry that
+/// logs its erasure.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EraseUserPayload {
+    pub user_id: Uuid,
+    pub request_id: Uuid,
+}
@@ -0,0 +1,33 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Flat row from JOIN with users.
+#[derive(Debug, sqlx::FromRow)]
+pub struct MentionWithUserRow {
+    pub comment_id: Option<Uuid>,
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub start_offset: i32,
+    pub end_offset: i32,
+}
+
+/// A resolved `@name` span. `start`/`end` are character offsets into the text.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct MentionSpan {
+    pub user_id: Uuid,
+    pub name: String,
+    pub start: i32,
+    pub end: i32,
+}
+
+impl From<MentionWithUserRow> for MentionSpan {
+    fn from(row: MentionWithUserRow) -> Self {
+        MentionSpan {
+            user_id: row.user_id,
+            name: row.user_name,
+            start: row.start_offset,
+            end: row.end_offset,
+        }
+    }
+}
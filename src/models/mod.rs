@@ -1,7 +1,20 @@
+pub mod attachment;
 pub mod board;
+pub mod board_transfer;
+pub mod changelog;
 pub mod comment;
+pub mod counter;
+pub mod data_request;
+pub mod event;
+pub mod feed;
+pub mod health;
+pub mod job;
+pub mod mention;
+pub mod notification;
 pub mod organization;
+pub mod page;
 pub mod post;
+pub mod reaction;
 pub mod tag;
 pub mod user;
 pub mod vote;
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct NotificationResponse {
+    pub id: Uuid,
+    pub kind: String,
+    pub actor_id: Option<Uuid>,
+    pub actor_name: Option<String>,
+    pub post_id: Option<Uuid>,
+    pub post_title: Option<String>,
+    pub comment_id: Option<Uuid>,
+    pub changelog_id: Option<Uuid>,
+    pub changelog_title: Option<String>,
+    pub read: bool,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Notification kinds.
+pub const KIND_MENTION: &str = "mention";
+/// A changelog entry linking a post the user voted on or follows was published.
+pub const KIND_CHANGELOG: &str = "changelog";
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
@@ -24,7 +25,7 @@ pub struct OrgMember {
     pub created_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, sqlx::FromRow)]
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
 pub struct OrganizationResponse {
     pub id: Uuid,
     pub name: String,
@@ -32,3 +33,11 @@ pub struct OrganizationResponse {
     pub logo_url: Option<String>,
     pub role: String,
 }
+
+/// Org member suggestion for @mention autocomplete.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct MemberSuggestion {
+    pub id: Uuid,
+    pub name: String,
+    pub avatar_url: Option<String>,
+}
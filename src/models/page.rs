@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+pub const DEFAULT_PER_PAGE: i64 = 20;
+pub const MAX_PER_PAGE: i64 = 100;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    /// 1-based page number (default 1)
+    pub page: Option<i64>,
+    /// Items per page (default 20, at most 100)
+    pub per_page: Option<i64>,
+}
+
+impl PageQuery {
+    pub fn page(&self) -> i64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn per_page(&self) -> i64 {
+        self.per_page
+            .unwrap_or(DEFAULT_PER_PAGE)
+            .clamp(1, MAX_PER_PAGE)
+    }
+
+    /// Saturates, so an absurd `page` is just past the end rather than an
+    /// overflow.
+    pub fn offset(&self) -> i64 {
+        (self.page() - 1).saturating_mul(self.per_page())
+    }
+}
+
+/// Envelope of `/api/v2` list responses.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    pub pagination: Pagination,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Pagination {
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+    pub total_pages: i64,
+}
+
+impl<T> Page<T> {
+    pub fn new(data: Vec<T>, query: &PageQuery, total: i64) -> Self {
+        let per_page = query.per_page();
+        Self {
+            data,
+            pagination: Pagination {
+                page: query.page(),
+                per_page,
+                total,
+                total_pages: (total + per_page - 1) / per_page,
+            },
+        }
+    }
+}
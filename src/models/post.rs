@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
@@ -34,10 +35,11 @@ pub struct PostListRow {
     pub created_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PostListResponse {
     pub id: Uuid,
     pub title: String,
+    /// Plain-text excerpt of the rendered description.
     pub description_preview: Option<String>,
     pub status: String,
     pub vote_count: i32,
@@ -68,12 +70,16 @@ pub struct PostDetailRow {
     pub has_voted: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PostDetailResponse {
     pub id: Uuid,
     pub board_id: Uuid,
     pub title: String,
-    pub description: Option<String>,
+    pub description_markdown: Option<String>,
+    /// Sanitized HTML rendering of `description_markdown`.
+    pub description_html: Option<String>,
+    /// Resolved `@name` spans in `description_markdown`.
+    pub mentions: Vec<super::mention::MentionSpan>,
     pub status: String,
     pub vote_count: i32,
     pub comment_count: i32,
@@ -81,11 +87,13 @@ pub struct PostDetailResponse {
     pub author: Option<PostAuthor>,
     pub has_voted: bool,
     pub tags: Vec<super::tag::TagResponse>,
+    pub reactions: Vec<super::reaction::ReactionSummary>,
+    pub attachments: Vec<super::attachment::AttachmentResponse>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PostAuthor {
     pub id: Uuid,
     pub name: String,
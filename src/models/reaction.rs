@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Reaction {
+    pub id: Uuid,
+    pub target_type: String,
+    pub target_id: Uuid,
+    pub user_id: Uuid,
+    pub emoji: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// What a reaction is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReactionTarget {
+    Post,
+    Comment,
+}
+
+impl ReactionTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReactionTarget::Post => "post",
+            ReactionTarget::Comment => "comment",
+        }
+    }
+}
+
+/// Aggregated count for one emoji on a target.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct ReactionSummary {
+    pub emoji: String,
+    pub count: i64,
+    pub reacted: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReactionResult {
+    pub emoji: String,
+    pub reacted: bool,
+    pub count: i64,
+}
+
+/// Emoji users may react with.
+pub const ALLOWED_EMOJIS: &[&str] = &["👍", "👎", "😄", "🎉", "😕", "❤️", "🚀", "👀"];
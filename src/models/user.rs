@@ -1,7 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// `provider` of placeholder accounts created for authors and voters in
+/// imported data. They have no password; registering with the same email
+/// claims the account.
+pub const PLACEHOLDER_PROVIDER: &str = "import";
+
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct User {
     pub id: Uuid,
@@ -13,9 +19,12 @@ pub struct User {
     pub provider: Option<String>,
     pub provider_id: Option<String>,
     pub created_at: Option<DateTime<Utc>>,
+    /// Tokens issued at an older version are rejected
+    #[serde(skip_serializing)]
+    pub token_version: i32,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UserResponse {
     pub id: Uuid,
     pub email: String,
@@ -0,0 +1,147 @@
+//! OpenAPI 3.1 description of the HTTP API, generated from the
+//! `#[utoipa::path]` attributes on handlers and the `ToSchema` derives on
+//! request and response types. Served at `/api/openapi.json`, with Swagger UI
+//! at `/api/docs/`.
+
+use utoipa::openapi::RefOr;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::handlers;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Rivvo API",
+        description = "Feedback boards, roadmaps and changelogs.",
+        license(name = "AGPL-3.0", url = "https://www.gnu.org/licenses/agpl-3.0.html")
+    ),
+    paths(
+        handlers::health::health_check,
+        handlers::health::live,
+        handlers::health::ready,
+        handlers::feeds::changelog_feed,
+        handlers::feeds::board_posts_feed,
+        handlers::feeds::board_status_feed,
+        handlers::auth::register,
+        handlers::auth::login,
+        handlers::auth::me,
+        handlers::auth::update_me,
+        handlers::auth::delete_me,
+        handlers::auth::export_me,
+        handlers::auth::change_password,
+        handlers::auth::request_email_change,
+        handlers::auth::confirm_email_change,
+        handlers::images::upload_avatar,
+        handlers::images::delete_avatar,
+        handlers::organizations::list_user_orgs,
+        handlers::organizations::search_members,
+        handlers::organizations::import_from_source,
+        handlers::organizations::reconcile_counters,
+        handlers::jobs::list_jobs,
+        handlers::jobs::get_job,
+        handlers::jobs::retry_job,
+        handlers::images::upload_org_logo,
+        handlers::images::delete_org_logo,
+        handlers::images::get_image,
+        handlers::notifications::list_notifications,
+        handlers::notifications::mark_all_read,
+        handlers::boards::list_boards,
+        handlers::boards::create_board,
+        handlers::boards::get_board,
+        handlers::boards::update_board,
+        handlers::boards::delete_board,
+        handlers::boards::export_board,
+        handlers::boards::import_board,
+        handlers::changelog::list_entries,
+        handlers::changelog::create_entry,
+        handlers::changelog::get_entry,
+        handlers::changelog::update_entry,
+        handlers::changelog::delete_entry,
+        handlers::posts::list_posts,
+        handlers::posts::list_posts_page,
+        handlers::posts::create_post,
+        handlers::posts::get_post,
+        handlers::posts::update_post,
+        handlers::posts::delete_post,
+        handlers::posts::update_status,
+        handlers::posts::get_post_direct,
+        handlers::events::board_events,
+        handlers::events::post_events,
+        handlers::tags::list_tags,
+        handlers::tags::create_tag,
+        handlers::tags::delete_tag,
+        handlers::tags::add_tag_to_post,
+        handlers::tags::remove_tag_from_post,
+        handlers::votes::toggle_vote,
+        handlers::reactions::toggle_post_reaction,
+        handlers::reactions::toggle_comment_reaction,
+        handlers::attachments::upload_post_attachment,
+        handlers::attachments::upload_comment_attachment,
+        handlers::attachments::get_attachment,
+        handlers::attachments::delete_attachment,
+        handlers::comments::list_comments,
+        handlers::comments::create_comment,
+        handlers::comments::delete_comment,
+    ),
+    // Not referenced by any operation, but documents the SSE payloads
+    components(schemas(crate::models::event::BoardEvent)),
+    modifiers(&BearerAuth, &ProblemJson),
+    security(("bearer_auth" = [])),
+)]
+pub struct ApiDoc;
+
+/// Every operation takes a JWT from `/api/v1/auth/login` unless it opts out with
+/// `security(())`.
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// Error bodies are sent as `application/problem+json`, but `#[utoipa::path]`
+/// documents every body as `application/json`.
+struct ProblemJson;
+
+impl Modify for ProblemJson {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let error_ref = "#/components/schemas/ErrorResponse";
+        for item in openapi.paths.paths.values_mut() {
+            let operations = [
+                &mut item.get,
+                &mut item.post,
+                &mut item.put,
+                &mut item.delete,
+                &mut item.patch,
+            ];
+            for operation in operations.into_iter().flatten() {
+                for response in operation.responses.responses.values_mut() {
+                    let RefOr::T(response) = response else {
+                        continue;
+                    };
+                    let is_error = response.content.get("application/json").is_some_and(
+                        |c| matches!(&c.schema, Some(RefOr::Ref(r)) if r.ref_location == error_ref),
+                    );
+                    if is_error
+                        && let Some(content) = response.content.shift_remove("application/json")
+                    {
+                        response
+                            .content
+                            .insert(crate::errors::PROBLEM_CONTENT_TYPE.to_string(), content);
+                    }
+                }
+            }
+        }
+    }
+}
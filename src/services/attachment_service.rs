@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::attachment::{ALLOWED_CONTENT_TYPES, Attachment};
+use crate::storage::Storage;
+use crate::utils::content_type;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_attachment(
+    pool: &PgPool,
+    storage: &dyn Storage,
+    org_id: Uuid,
+    post_id: Uuid,
+    comment_id: Option<Uuid>,
+    uploader_id: Uuid,
+    filename: &str,
+    data: &[u8],
+    quota_bytes: i64,
+) -> Result<Attachment, AppError> {
+    let content_type = content_type::sniff(data)
+        .filter(|t| ALLOWED_CONTENT_TYPES.contains(t))
+        .ok_or_else(|| {
+            AppError::UnsupportedMediaType(format!(
+                "Unsupported file type. Must be one of: {}",
+                ALLOWED_CONTENT_TYPES.join(", ")
+            ))
+        })?;
+    let size_bytes = data.len() as i64;
+
+    let mut tx = pool.begin().await?;
+
+    // Reserve space against the org quota; the row lock serializes concurrent uploads
+    let reserved: Option<i64> = sqlx::query_scalar(
+        r#"
+        UPDATE organizations SET storage_used_bytes = storage_used_bytes + $2
+        WHERE id = $1 AND storage_used_bytes + $2 <= $3
+        RETURNING storage_used_bytes
+        "#,
+    )
+    .bind(org_id)
+    .bind(size_bytes)
+    .bind(quota_bytes)
+    .fetch_optional(&mut *tx)
+    .await?;
+    if reserved.is_none() {
+        return Err(AppError::QuotaExceeded(
+            "Organization storage quota exceeded".to_string(),
+        ));
+    }
+
+    let id = Uuid::new_v4();
+    let storage_key = format!("{org_id}/{id}");
+
+    let attachment: Attachment = sqlx::query_as(
+        r#"
+        INSERT INTO attachments
+            (id, org_id, post_id, comment_id, uploader_id, filename, content_type, size_bytes, storage_key)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(org_id)
+    .bind(post_id)
+    .bind(comment_id)
+    .bind(uploader_id)
+    .bind(sanitize_filename(filename))
+    .bind(content_type)
+    .bind(size_bytes)
+    .bind(&storage_key)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Store the file before committing so a failed write leaves no dangling row
+    storage.put(&storage_key, data, content_type).await?;
+
+    tx.commit().await?;
+
+    Ok(attachment)
+}
+
+pub async fn get_attachment_by_id(
+    pool: &PgPool,
+    attachment_id: Uuid,
+) -> Result<Attachment, AppError> {
+    sqlx::query_as("SELECT * FROM attachments WHERE id = $1")
+        .bind(attachment_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Attachment not found".to_string()))
+}
+
+/// Attachments on the post itself (not on its comments).
+pub async fn get_post_attachments(
+    pool: &PgPool,
+    post_id: Uuid,
+) -> Result<Vec<Attachment>, AppError> {
+    let attachments = sqlx::query_as(
+        "SELECT * FROM attachments WHERE post_id = $1 AND comment_id IS NULL ORDER BY created_at ASC",
+    )
+    .bind(post_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(attachments)
+}
+
+/// Batch-fetch attachments for multiple comments in a single query.
+/// Returns a HashMap mapping comment_id -> Vec<Attachment>.
+pub async fn get_attachments_for_comment_ids(
+    pool: &PgPool,
+    comment_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<Attachment>>, AppError> {
+    let rows: Vec<Attachment> = sqlx::query_as(
+        "SELECT * FROM attachments WHERE comment_id = ANY($1) ORDER BY created_at ASC",
+    )
+    .bind(comment_ids)
+    .fetch_all(pool)
+    .await?;
+
+    let mut map: HashMap<Uuid, Vec<Attachment>> = HashMap::new();
+    for row in rows {
+        if let Some(comment_id) = row.comment_id {
+            map.entry(comment_id).or_default().push(row);
+        }
+    }
+    Ok(map)
+}
+
+/// Storage keys of every attachment on a post and its comments, for cleanup
+/// after the post is deleted.
+pub async fn get_storage_keys_for_post(
+    pool: &PgPool,
+    post_id: Uuid,
+) -> Result<Vec<String>, AppError> {
+    let keys = sqlx::query_scalar("SELECT storage_key FROM attachments WHERE post_id = $1")
+        .bind(post_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(keys)
+}
+
+pub async fn get_storage_keys_for_comment(
+    pool: &PgPool,
+    comment_id: Uuid,
+) -> Result<Vec<String>, AppError> {
+    let keys = sqlx::query_scalar("SELECT storage_key FROM attachments WHERE comment_id = $1")
+        .bind(comment_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(keys)
+}
+
+pub async fn get_storage_keys_for_board(
+    pool: &PgPool,
+    board_id: Uuid,
+) -> Result<Vec<String>, AppError> {
+    let keys = sqlx::query_scalar(
+        r#"
+        SELECT a.storage_key FROM attachments a
+        JOIN posts p ON p.id = a.post_id
+        WHERE p.board_id = $1
+        "#,
+    )
+    .bind(board_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(keys)
+}
+
+pub async fn get_storage_keys_for_org(
+    pool: &PgPool,
+    org_id: Uuid,
+) -> Result<Vec<String>, AppError> {
+    let keys = sqlx::query_scalar("SELECT storage_key FROM attachments WHERE org_id = $1")
+        .bind(org_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(keys)
+}
+
+pub async fn delete_attachment(
+    pool: &PgPool,
+    storage: &dyn Storage,
+    attachment_id: Uuid,
+) -> Result<(), AppError> {
+    // Quota is released by the attachments_release_storage trigger
+    let key: Option<String> =
+        sqlx::query_scalar("DELETE FROM attachments WHERE id = $1 RETURNING storage_key")
+            .bind(attachment_id)
+            .fetch_optional(pool)
+            .await?;
+
+    let key = key.ok_or_else(|| AppError::NotFound("Attachment not found".to_string()))?;
+    crate::storage::delete_all(storage, &[key]).await;
+    Ok(())
+}
+
+/// Keep only the final path component and drop control characters.
+fn sanitize_filename(filename: &str) -> String {
+    let name = filename
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(255)
+        .collect::<String>();
+    let name = name.trim();
+    if name.is_empty() {
+        "file".to_string()
+    } else {
+        name.to_string()
+    }
+}
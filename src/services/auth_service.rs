@@ -1,11 +1,17 @@
 use argon2::password_hash::SaltString;
-use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
 use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use sha2::{Digest, Sha256};
 use sqlx::PgPool;
+use uuid::Uuid;
 
 use crate::errors::AppError;
-use crate::models::user::User;
-use crate::services::org_service;
+use crate::mailer::Email;
+use crate::models::user::{PLACEHOLDER_PROVIDER, User};
+use crate::services::{gdpr_service, job_service, org_service};
+
+/// How long an email change confirmation link stays valid.
+const EMAIL_CHANGE_TTL_HOURS: i32 = 24;
 
 pub async fn register_user(
     pool: &PgPool,
@@ -19,17 +25,42 @@ pub async fn register_user(
         .fetch_optional(pool)
         .await?;
 
-    if existing.is_some() {
-        return Err(AppError::BadRequest(
-            "A user with this email already exists".to_string(),
-        ));
-    }
+    let placeholder = match existing {
+        Some(user) if user.provider.as_deref() == Some(PLACEHOLDER_PROVIDER) => Some(user.id),
+        Some(_) => {
+            return Err(AppError::BadRequest(
+                "A user with this email already exists".to_string(),
+            ));
+        }
+        None => None,
+    };
 
     let password = password.to_owned();
     let password_hash = tokio::task::spawn_blocking(move || hash_password(&password))
         .await
         .map_err(|e| AppError::InternalError(format!("Blocking task failed: {e}")))??;
 
+    // Imported authors and voters keep their history once they sign up
+    if let Some(user_id) = placeholder {
+        let user: User = sqlx::query_as(
+            r#"
+            UPDATE users SET name = $2, password_hash = $3, provider = 'email'
+            WHERE id = $1 AND provider = $4
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(name)
+        .bind(&password_hash)
+        .bind(PLACEHOLDER_PROVIDER)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("A user with this email already exists".to_string()))?;
+
+        org_service::create_org(pool, &format!("{name}'s Workspace"), user.id).await?;
+        return Ok(user);
+    }
+
     let user: User = sqlx::query_as(
         "INSERT INTO users (email, name, password_hash, provider) VALUES ($1, $2, $3, 'email') RETURNING *",
     )
@@ -88,6 +119,228 @@ pub async fn get_user_by_id(pool: &PgPool, user_id: uuid::Uuid) -> Result<User,
     Ok(user)
 }
 
+pub async fn get_user_by_email(pool: &PgPool, email: &str) -> Result<User, AppError> {
+    sqlx::query_as("SELECT * FROM users WHERE LOWER(email) = LOWER($1)")
+        .bind(email)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("No user with email {email}")))
+}
+
+pub async fn update_profile(pool: &PgPool, user_id: Uuid, name: &str) -> Result<User, AppError> {
+    sqlx::query_as("UPDATE users SET name = $2 WHERE id = $1 RETURNING *")
+        .bind(user_id)
+        .bind(name)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+}
+
+/// Change the password of the signed-in user. Returns the updated user,
+/// whose earlier tokens no longer work.
+pub async fn change_password(
+    pool: &PgPool,
+    user_id: Uuid,
+    current_password: &str,
+    new_password: &str,
+) -> Result<User, AppError> {
+    let user = get_user_by_id(pool, user_id).await?;
+    check_current_password(&user, current_password).await?;
+    set_password(pool, user_id, new_password).await
+}
+
+/// Set a password without checking the current one (operator reset). Either
+/// way every token issued before is revoked.
+pub async fn set_password(
+    pool: &PgPool,
+    user_id: Uuid,
+    new_password: &str,
+) -> Result<User, AppError> {
+    let new_password = new_password.to_owned();
+    let password_hash = tokio::task::spawn_blocking(move || hash_password(&new_password))
+        .await
+        .map_err(|e| AppError::InternalError(format!("Blocking task failed: {e}")))??;
+
+    sqlx::query_as(
+        r#"
+        UPDATE users SET password_hash = $2, token_version = token_version + 1
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(&password_hash)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+}
+
+/// Start an email change and queue the confirmation link (under `app_url`)
+/// to the new address. Only the hash of the token is stored. Any earlier
+/// pending request is replaced.
+pub async fn request_email_change(
+    pool: &PgPool,
+    app_url: &str,
+    user_id: Uuid,
+    new_email: &str,
+    password: &str,
+) -> Result<(), AppError> {
+    let user = get_user_by_id(pool, user_id).await?;
+    check_current_password(&user, password).await?;
+
+    if user.email.eq_ignore_ascii_case(new_email) {
+        return Err(AppError::BadRequest(
+            "That is already your email address".to_string(),
+        ));
+    }
+    let taken: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE LOWER(email) = LOWER($1))")
+            .bind(new_email)
+            .fetch_one(pool)
+            .await?;
+    if taken {
+        return Err(AppError::BadRequest(
+            "A user with this email already exists".to_string(),
+        ));
+    }
+
+    let token = generate_token();
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM email_change_requests WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO email_change_requests (user_id, new_email, token_hash, expires_at)
+        VALUES ($1, $2, $3, now() + make_interval(hours => $4))
+        "#,
+    )
+    .bind(user_id)
+    .bind(new_email)
+    .bind(hash_token(&token))
+    .bind(EMAIL_CHANGE_TTL_HOURS)
+    .execute(&mut *tx)
+    .await?;
+
+    job_service::enqueue_email(
+        &mut tx,
+        &Email {
+            to: new_email.to_string(),
+            subject: "Confirm your new email address".to_string(),
+            body: format!(
+                "Follow this link to confirm your new Rivvo email address:\n\n{app_url}/confirm-email?token={token}\n\nThe link expires in 24 hours. If you did not request this change, you can ignore this message."
+            ),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Apply a pending email change, queueing a notice to the address it
+/// replaced in case the change was not theirs.
+pub async fn confirm_email_change(pool: &PgPool, token: &str) -> Result<User, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let request: Option<(Uuid, String)> = sqlx::query_as(
+        r#"
+        DELETE FROM email_change_requests
+        WHERE token_hash = $1 AND expires_at > now()
+        RETURNING user_id, new_email
+        "#,
+    )
+    .bind(hash_token(token))
+    .fetch_optional(&mut *tx)
+    .await?;
+    let (user_id, new_email) = request
+        .ok_or_else(|| AppError::BadRequest("Invalid or expired confirmation link".to_string()))?;
+
+    let old_email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = $1 FOR UPDATE")
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let user: User = sqlx::query_as("UPDATE users SET email = $2 WHERE id = $1 RETURNING *")
+        .bind(user_id)
+        .bind(&new_email)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_err) if db_err.code().as_deref() == Some("23505") => {
+                AppError::BadRequest("A user with this email already exists".to_string())
+            }
+            _ => AppError::DatabaseError(e),
+        })?;
+
+    sqlx::query("DELETE FROM email_change_requests WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    job_service::enqueue_email(
+        &mut tx,
+        &Email {
+            to: old_email,
+            subject: "Your Rivvo email address was changed".to_string(),
+            body: format!(
+                "The email address of your Rivvo account was changed to {}.",
+                user.email
+            ),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(user)
+}
+
+/// Queue deletion of a user account after re-verifying the password. See
+/// `gdpr_service::erase_user` for what is removed and what is kept.
+pub async fn delete_account(pool: &PgPool, user_id: Uuid, password: &str) -> Result<(), AppError> {
+    let user = get_user_by_id(pool, user_id).await?;
+    check_current_password(&user, password).await?;
+
+    gdpr_service::request_erasure(pool, user_id).await
+}
+
+/// Re-verify the password of the signed-in user before a sensitive change.
+async fn check_current_password(user: &User, password: &str) -> Result<(), AppError> {
+    let stored_hash = user
+        .password_hash
+        .clone()
+        .ok_or_else(|| AppError::BadRequest("This account does not use a password".to_string()))?;
+
+    let password = password.to_owned();
+    let valid = tokio::task::spawn_blocking(move || verify_password(&password, &stored_hash))
+        .await
+        .map_err(|e| AppError::InternalError(format!("Blocking task failed: {e}")))??;
+
+    if !valid {
+        return Err(AppError::BadRequest(
+            "Current password is incorrect".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 32 random bytes, hex encoded.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
 fn hash_password(password: &str) -> Result<String, AppError> {
     let salt = SaltString::generate(&mut OsRng);
     let argon2 = Argon2::default();
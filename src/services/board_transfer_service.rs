@@ -0,0 +1,722 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::importers::{self, CsvTable, ParsedImport};
+use crate::models::board::Board;
+use crate::models::board_transfer::{
+    BoardExport, BoardExportComment, BoardExportInfo, BoardExportPost, BoardExportPostRow,
+    ImportComment, ImportFile, ImportPost, ImportReport, ImportRowError, ImportUser,
+};
+use crate::models::post::VALID_STATUSES;
+use crate::models::user::PLACEHOLDER_PROVIDER;
+use crate::services::tag_service;
+use crate::utils::slugify::create_slug;
+
+/// Column order of CSV exports. Imports accept the same header.
+const CSV_COLUMNS: &[&str] = &[
+    "id",
+    "title",
+    "description",
+    "status",
+    "tags",
+    "vote_count",
+    "comment_count",
+    "pinned",
+    "author_name",
+    "author_email",
+    "created_at",
+    "updated_at",
+];
+
+// ── Export ─────────────────────────────────────────────────────────
+
+pub async fn export_board(pool: &PgPool, board: &Board) -> Result<BoardExport, AppError> {
+    let rows: Vec<BoardExportPostRow> = sqlx::query_as(
+        r#"
+        SELECT p.id, p.title, p.description, p.status, p.vote_count, p.comment_count, p.pinned,
+               u.name as author_name, u.email as author_email, p.created_at, p.updated_at
+        FROM posts p
+        LEFT JOIN users u ON u.id = p.author_id
+        WHERE p.board_id = $1
+        ORDER BY p.created_at ASC
+        "#,
+    )
+    .bind(board.id)
+    .fetch_all(pool)
+    .await?;
+
+    let post_ids: Vec<Uuid> = rows.iter().map(|r| r.id).collect();
+    let mut tags_map = tag_service::get_tags_for_post_ids(pool, &post_ids).await?;
+
+    let comments: Vec<BoardExportComment> = sqlx::query_as(
+        r#"
+        SELECT c.post_id, u.name as author_name, u.email as author_email, c.body,
+               c.is_admin_reply, c.created_at
+        FROM comments c
+        JOIN posts p ON p.id = c.post_id
+        LEFT JOIN users u ON u.id = c.author_id
+        WHERE p.board_id = $1
+        ORDER BY c.created_at ASC
+        "#,
+    )
+    .bind(board.id)
+    .fetch_all(pool)
+    .await?;
+    let mut comments_map: HashMap<Uuid, Vec<BoardExportComment>> = HashMap::new();
+    for comment in comments {
+        comments_map
+            .entry(comment.post_id)
+            .or_default()
+            .push(comment);
+    }
+
+    let posts = rows
+        .into_iter()
+        .map(|post| BoardExportPost {
+            tags: tags_map
+                .remove(&post.id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|t| t.name)
+                .collect(),
+            comments: comments_map.remove(&post.id).unwrap_or_default(),
+            post,
+        })
+        .collect();
+
+    Ok(BoardExport {
+        exported_at: Utc::now(),
+        board: BoardExportInfo {
+            name: board.name.clone(),
+            slug: board.slug.clone(),
+            description: board.description.clone(),
+        },
+        posts,
+    })
+}
+
+/// One row per post. Comments are only included in the JSON export; CSV
+/// carries their count.
+pub fn export_to_csv(export: &BoardExport) -> Result<Vec<u8>, AppError> {
+    let csv_error = |e: csv::Error| AppError::InternalError(format!("CSV export failed: {e}"));
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(CSV_COLUMNS).map_err(csv_error)?;
+    for item in &export.posts {
+        let post = &item.post;
+        writer
+            .write_record([
+                post.id.to_string(),
+                post.title.clone(),
+                post.description.clone().unwrap_or_default(),
+                post.status.clone().unwrap_or_default(),
+                item.tags.join("; "),
+                post.vote_count.unwrap_or(0).to_string(),
+                post.comment_count.unwrap_or(0).to_string(),
+                post.pinned.unwrap_or(false).to_string(),
+                post.author_name.clone().unwrap_or_default(),
+                post.author_email.clone().unwrap_or_default(),
+                post.created_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+                post.updated_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+            ])
+            .map_err(csv_error)?;
+    }
+    writer
+        .into_inner()
+        .map_err(|e| AppError::InternalError(format!("CSV export failed: {e}")))
+}
+
+// ── Import parsing ─────────────────────────────────────────────────
+
+/// Parse a JSON import: either a board export (`{"posts": [...]}`) or a bare
+/// array of posts.
+pub fn parse_json(data: &[u8]) -> Result<Vec<ImportPost>, AppError> {
+    let invalid = |e: serde_json::Error| AppError::BadRequest(format!("Invalid JSON import: {e}"));
+    let value: serde_json::Value = serde_json::from_slice(data).map_err(invalid)?;
+    if value.is_array() {
+        serde_json::from_value(value).map_err(invalid)
+    } else {
+        serde_json::from_value::<ImportFile>(value)
+            .map(|f| f.posts)
+            .map_err(invalid)
+    }
+}
+
+/// Parse a CSV import. Headers are matched case-insensitively; only `title` is
+/// required and unknown columns are ignored. Tags and voter emails are
+/// separated by `;` (or by `,` when a cell has no `;`).
+pub fn parse_csv(data: &[u8]) -> Result<Vec<ImportPost>, AppError> {
+    let table = CsvTable::parse(data)?;
+    table.require(&["title"])?;
+
+    let posts = table
+        .records
+        .iter()
+        .map(|record| {
+            let field = |name: &str| table.get(record, &[name]);
+            ImportPost {
+                title: field("title").unwrap_or_default(),
+                description: field("description"),
+                status: field("status"),
+                category: field("category"),
+                tags: field("tags")
+                    .map(|cell| importers::split_list(&cell))
+                    .unwrap_or_default(),
+                pinned: field("pinned")
+                    .map(|v| matches!(v.to_lowercase().as_str(), "true" | "yes" | "1")),
+                author_email: field("author_email"),
+                author_name: field("author_name"),
+                created_at: field("created_at"),
+                updated_at: field("updated_at"),
+                voters: field("voters")
+                    .map(|cell| {
+                        importers::split_list(&cell)
+                            .into_iter()
+                            .map(|email| ImportUser {
+                                email: Some(email),
+                                name: None,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                ..Default::default()
+            }
+        })
+        .collect();
+    Ok(posts)
+}
+
+/// Map a status from another tool or a spreadsheet onto `VALID_STATUSES`.
+pub fn map_status(raw: &str) -> Option<&'static str> {
+    let normalized = raw.trim().to_lowercase().replace(['-', ' '], "_");
+    if let Some(status) = VALID_STATUSES.iter().find(|s| **s == normalized) {
+        return Some(status);
+    }
+    let status = match normalized.as_str() {
+        "" | "new" | "under_review" | "needs_review" | "backlog" | "considering" => "open",
+        "accepted" | "next" | "later" | "up_next" => "planned",
+        "started" | "in_development" | "doing" | "active" | "working_on_it" => "in_progress",
+        "complete" | "completed" | "shipped" | "released" | "live" | "fixed" => "done",
+        "declined" | "rejected" | "wontfix" | "won't_fix" | "won't_do" | "duplicate"
+        | "archived" => "closed",
+        _ => return None,
+    };
+    Some(status)
+}
+
+/// Accept RFC 3339 timestamps as well as the plain `YYYY-MM-DD[ HH:MM:SS]`
+/// forms spreadsheets tend to produce (interpreted as UTC).
+pub fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(t) = DateTime::parse_from_rfc3339(raw) {
+        return Some(t.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(t) = NaiveDateTime::parse_from_str(raw, format) {
+            return Some(t.and_utc());
+        }
+    }
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|t| t.and_utc())
+}
+
+// ── Import ─────────────────────────────────────────────────────────
+
+struct ValidPost {
+    board: Option<String>,
+    title: String,
+    description: Option<String>,
+    status: &'static str,
+    category: Option<String>,
+    tags: Vec<String>,
+    pinned: bool,
+    author: ValidUser,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+    voters: Vec<ValidUser>,
+    comments: Vec<ValidComment>,
+}
+
+struct ValidComment {
+    body: String,
+    author: ValidUser,
+    is_admin_reply: bool,
+    created_at: Option<DateTime<Utc>>,
+}
+
+struct ValidUser {
+    email: Option<String>,
+    name: Option<String>,
+}
+
+impl ValidUser {
+    fn new(email: Option<String>, name: Option<String>) -> Self {
+        ValidUser {
+            email: email
+                .map(|e| e.trim().to_lowercase())
+                .filter(|e| e.contains('@')),
+            name: name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+        }
+    }
+}
+
+fn validate_post(post: ImportPost) -> Result<ValidPost, String> {
+    let title = post.title.trim().to_string();
+    if title.is_empty() {
+        return Err("Title is required".to_string());
+    }
+    if title.chars().count() > 500 {
+        return Err("Title must be at most 500 characters".to_string());
+    }
+
+    let status = match post.status.as_deref() {
+        None => "open",
+        Some(raw) => map_status(raw).ok_or_else(|| {
+            format!(
+                "Unknown status \"{raw}\". Must map to one of: {}",
+                VALID_STATUSES.join(", ")
+            )
+        })?,
+    };
+
+    let mut tags: Vec<String> = Vec::new();
+    for tag in post.tags {
+        let tag = tag.trim().to_string();
+        if tag.is_empty() || tags.contains(&tag) {
+            continue;
+        }
+        if tag.chars().count() > 100 {
+            return Err(format!("Tag \"{tag}\" is longer than 100 characters"));
+        }
+        tags.push(tag);
+    }
+
+    let category = post
+        .category
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty());
+    if category.as_ref().is_some_and(|c| c.chars().count() > 100) {
+        return Err("Category must be at most 100 characters".to_string());
+    }
+
+    let created_at = timestamp_field(post.created_at.as_deref(), "created_at")?;
+    let updated_at = timestamp_field(post.updated_at.as_deref(), "updated_at")?;
+
+    let comments = post
+        .comments
+        .into_iter()
+        .enumerate()
+        .map(|(i, c)| validate_comment(c).map_err(|e| format!("Comment {}: {e}", i + 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ValidPost {
+        board: post.board.map(|b| b.trim().to_string()),
+        title,
+        description: post.description.filter(|d| !d.trim().is_empty()),
+        status,
+        category,
+        tags,
+        pinned: post.pinned.unwrap_or(false),
+        author: ValidUser::new(post.author_email, post.author_name),
+        created_at,
+        updated_at,
+        voters: post
+            .voters
+            .into_iter()
+            .map(|v| ValidUser::new(v.email, v.name))
+            .collect(),
+        comments,
+    })
+}
+
+fn validate_comment(comment: ImportComment) -> Result<ValidComment, String> {
+    if comment.body.trim().is_empty() {
+        return Err("Body is required".to_string());
+    }
+    Ok(ValidComment {
+        body: comment.body,
+        author: ValidUser::new(comment.author_email, comment.author_name),
+        is_admin_reply: comment.is_admin_reply.unwrap_or(false),
+        created_at: timestamp_field(comment.created_at.as_deref(), "created_at")?,
+    })
+}
+
+fn timestamp_field(raw: Option<&str>, name: &str) -> Result<Option<DateTime<Utc>>, String> {
+    match raw {
+        None => Ok(None),
+        Some(raw) => parse_timestamp(raw)
+            .map(Some)
+            .ok_or_else(|| format!("Invalid {name} \"{raw}\"")),
+    }
+}
+
+/// Validate and import posts into a board in a single transaction.
+///
+/// All rows are validated first; if any fail, nothing is written and the
+/// report lists every error. With `dry_run` the import runs and is rolled
+/// back, so the report reflects exactly what a real run would create.
+/// Authors and voters are matched to org members by email and dropped
+/// otherwise. Imported posts keep their timestamps, and no mention
+/// notifications are sent.
+pub async fn import_posts(
+    pool: &PgPool,
+    org_id: Uuid,
+    board_id: Uuid,
+    posts: Vec<ImportPost>,
+    dry_run: bool,
+) -> Result<ImportReport, AppError> {
+    let report = ImportReport {
+        dry_run,
+        ..Default::default()
+    };
+    run_import(pool, org_id, Some(board_id), posts, false, report).await
+}
+
+/// Import another tool's export across an organization's boards.
+///
+/// Like [`import_posts`], but each post goes to the board named in the
+/// export, which is created when no board has the same slug. Authors and
+/// voters who are not org members get placeholder accounts (see
+/// `PLACEHOLDER_PROVIDER`); emails of other registered users are left
+/// unattributed rather than tied to someone outside the org.
+pub async fn import_foreign(
+    pool: &PgPool,
+    org_id: Uuid,
+    parsed: ParsedImport,
+    dry_run: bool,
+) -> Result<ImportReport, AppError> {
+    let report = ImportReport {
+        dry_run,
+        unmapped_fields: parsed.unmapped_fields.into_iter().collect(),
+        warnings: parsed.warnings,
+        ..Default::default()
+    };
+    run_import(pool, org_id, None, parsed.posts, true, report).await
+}
+
+/// Resolves author and voter emails to user ids during an import.
+struct Authors {
+    members: HashMap<String, Uuid>,
+    placeholders: bool,
+    resolved: HashMap<String, Option<Uuid>>,
+}
+
+impl Authors {
+    async fn resolve(
+        &mut self,
+        conn: &mut PgConnection,
+        user: &ValidUser,
+        report: &mut ImportReport,
+    ) -> Result<Option<Uuid>, AppError> {
+        let Some(email) = &user.email else {
+            return Ok(None);
+        };
+        if let Some(id) = self.members.get(email) {
+            return Ok(Some(*id));
+        }
+        if !self.placeholders {
+            return Ok(None);
+        }
+        if let Some(resolved) = self.resolved.get(email) {
+            return Ok(*resolved);
+        }
+
+        let existing: Option<(Uuid, Option<String>)> =
+            sqlx::query_as("SELECT id, provider FROM users WHERE LOWER(email) = $1")
+                .bind(email)
+                .fetch_optional(&mut *conn)
+                .await?;
+        let resolved = match existing {
+            Some((id, provider)) if provider.as_deref() == Some(PLACEHOLDER_PROVIDER) => Some(id),
+            Some(_) => {
+                report.warnings.push(format!(
+                    "{email} belongs to a registered user outside this organization; their content was imported without an author"
+                ));
+                None
+            }
+            None => {
+                let name = user
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| email.split('@').next().unwrap_or(email).to_string());
+                let id = sqlx::query_scalar(
+                    "INSERT INTO users (email, name, provider) VALUES ($1, $2, $3) RETURNING id",
+                )
+                .bind(email)
+                .bind(&name)
+                .bind(PLACEHOLDER_PROVIDER)
+                .fetch_one(&mut *conn)
+                .await?;
+                report.placeholder_users += 1;
+                Some(id)
+            }
+        };
+        self.resolved.insert(email.clone(), resolved);
+        Ok(resolved)
+    }
+}
+
+/// Shared by both imports. Posts go to `board_id` when given, otherwise to the
+/// board named on each post.
+async fn run_import(
+    pool: &PgPool,
+    org_id: Uuid,
+    board_id: Option<Uuid>,
+    posts: Vec<ImportPost>,
+    placeholders: bool,
+    mut report: ImportReport,
+) -> Result<ImportReport, AppError> {
+    let mut valid = Vec::new();
+    for (i, post) in posts.into_iter().enumerate() {
+        let result = validate_post(post).and_then(|post| match (&board_id, &post.board) {
+            (None, None) => Err("Board is required".to_string()),
+            (None, Some(board)) if create_slug(board).is_empty() => {
+                Err(format!("Board name \"{board}\" has no letters or digits"))
+            }
+            (None, Some(board)) if board.chars().count() > 255 => {
+                Err("Board name must be at most 255 characters".to_string())
+            }
+            _ => Ok(post),
+        });
+        match result {
+            Ok(post) => valid.push(post),
+            Err(message) => report.errors.push(ImportRowError {
+                row: i + 1,
+                message,
+            }),
+        }
+    }
+    if !report.errors.is_empty() {
+        return Ok(report);
+    }
+    report.valid = true;
+
+    let members: HashMap<String, Uuid> = sqlx::query_as::<_, (String, Uuid)>(
+        r#"
+        SELECT LOWER(u.email), u.id
+        FROM users u
+        JOIN org_members m ON m.user_id = u.id
+        WHERE m.org_id = $1
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect();
+    let mut authors = Authors {
+        members,
+        placeholders,
+        resolved: HashMap::new(),
+    };
+
+    let mut tx = pool.begin().await?;
+    let mut board_ids: HashMap<String, Uuid> = HashMap::new();
+    let mut tag_ids: HashMap<(Uuid, String), Uuid> = HashMap::new();
+    let mut skipped_voters = 0;
+
+    for post in valid {
+        let board_id = match board_id {
+            Some(id) => id,
+            None => {
+                let name = post.board.clone().unwrap_or_default();
+                let slug = create_slug(&name);
+                match board_ids.get(&slug) {
+                    Some(id) => *id,
+                    None => {
+                        let id = find_or_create_board(&mut tx, org_id, &name, &slug, &mut report)
+                            .await?;
+                        board_ids.insert(slug, id);
+                        id
+                    }
+                }
+            }
+        };
+
+        let author_id = authors.resolve(&mut tx, &post.author, &mut report).await?;
+        let post_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO posts
+                (board_id, author_id, title, description, status, category, pinned, comment_count,
+                 created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, COALESCE($9, now()), COALESCE($10, $9, now()))
+            RETURNING id
+            "#,
+        )
+        .bind(board_id)
+        .bind(author_id)
+        .bind(&post.title)
+        .bind(&post.description)
+        .bind(post.status)
+        .bind(&post.category)
+        .bind(post.pinned)
+        .bind(post.comments.len() as i32)
+        .bind(post.created_at)
+        .bind(post.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for name in &post.tags {
+            let key = (board_id, name.clone());
+            let tag_id = match tag_ids.get(&key) {
+                Some(id) => *id,
+                None => {
+                    let (tag, created) =
+                        tag_service::find_or_create_tag(&mut tx, board_id, name).await?;
+                    if created {
+                        report.tags_created.push(tag.name.clone());
+                    }
+                    tag_ids.insert(key, tag.id);
+                    tag.id
+                }
+            };
+            sqlx::query("INSERT INTO post_tags (post_id, tag_id) VALUES ($1, $2)")
+                .bind(post_id)
+                .bind(tag_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let mut votes = 0;
+        for voter in &post.voters {
+            let Some(user_id) = authors.resolve(&mut tx, voter, &mut report).await? else {
+                skipped_voters += 1;
+                continue;
+            };
+            let inserted = sqlx::query(
+                "INSERT INTO votes (post_id, user_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+            )
+            .bind(post_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+            votes += inserted.rows_affected() as i32;
+        }
+        if votes > 0 {
+            sqlx::query("UPDATE posts SET vote_count = $2 WHERE id = $1")
+                .bind(post_id)
+                .bind(votes)
+                .execute(&mut *tx)
+                .await?;
+            report.votes += votes as usize;
+        }
+
+        for comment in &post.comments {
+            let author_id = authors
+                .resolve(&mut tx, &comment.author, &mut report)
+                .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO comments (post_id, author_id, body, is_admin_reply, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, COALESCE($5, now()), COALESCE($5, now()))
+                "#,
+            )
+            .bind(post_id)
+            .bind(author_id)
+            .bind(&comment.body)
+            .bind(comment.is_admin_reply)
+            .bind(comment.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        report.posts += 1;
+        report.comments += post.comments.len();
+    }
+
+    if skipped_voters > 0 {
+        report.warnings.push(format!(
+            "Skipped {skipped_voters} vote(s) from voters without a usable account"
+        ));
+    }
+
+    if report.dry_run {
+        tx.rollback().await?;
+    } else {
+        tx.commit().await?;
+    }
+
+    Ok(report)
+}
+
+async fn find_or_create_board(
+    conn: &mut PgConnection,
+    org_id: Uuid,
+    name: &str,
+    slug: &str,
+    report: &mut ImportReport,
+) -> Result<Uuid, AppError> {
+    let existing: Option<Uuid> =
+        sqlx::query_scalar("SELECT id FROM boards WHERE org_id = $1 AND slug = $2")
+            .bind(org_id)
+            .bind(slug)
+            .fetch_optional(&mut *conn)
+            .await?;
+    if let Some(id) = existing {
+        return Ok(id);
+    }
+
+    let id = sqlx::query_scalar(
+        "INSERT INTO boards (org_id, name, slug) VALUES ($1, $2, $3) RETURNING id",
+    )
+    .bind(org_id)
+    .bind(name)
+    .bind(slug)
+    .fetch_one(&mut *conn)
+    .await?;
+    report.boards_created.push(name.to_string());
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_foreign_statuses() {
+        assert_eq!(map_status("In Progress"), Some("in_progress"));
+        assert_eq!(map_status("under review"), Some("open"));
+        assert_eq!(map_status("Shipped"), Some("done"));
+        assert_eq!(map_status("won't fix"), Some("closed"));
+        assert_eq!(map_status("planned"), Some("planned"));
+        assert_eq!(map_status("someday maybe"), None);
+    }
+
+    #[test]
+    fn parses_spreadsheet_timestamps() {
+        let expected = "2023-04-05T06:07:08Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(parse_timestamp("2023-04-05T06:07:08Z"), Some(expected));
+        assert_eq!(parse_timestamp("2023-04-05T08:07:08+02:00"), Some(expected));
+        assert_eq!(parse_timestamp("2023-04-05 06:07:08"), Some(expected));
+        assert_eq!(
+            parse_timestamp("2023-04-05"),
+            Some("2023-04-05T00:00:00Z".parse().unwrap())
+        );
+        assert_eq!(parse_timestamp("05/04/2023"), None);
+    }
+
+    #[test]
+    fn parses_csv_with_loose_headers() {
+        let csv = "\u{feff}Title,Status,Tags,Created At,Extra\n\
+                   Dark mode,Under Review,\"ui, themes\",2023-01-02,x\n\
+                   ,open,,,\n";
+        let posts = parse_csv(csv.as_bytes()).unwrap();
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[0].title, "Dark mode");
+        assert_eq!(posts[0].status.as_deref(), Some("Under Review"));
+        assert_eq!(posts[0].tags, vec!["ui", "themes"]);
+        assert_eq!(posts[0].created_at.as_deref(), Some("2023-01-02"));
+        assert!(validate_post(posts.into_iter().nth(1).unwrap()).is_err());
+    }
+
+    #[test]
+    fn csv_requires_title_column() {
+        assert!(parse_csv(b"name,status\nx,open\n").is_err());
+    }
+}
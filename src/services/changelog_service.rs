@@ -0,0 +1,322 @@
+use std::collections::HashSet;
+
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::changelog::{
+    ChangelogEntry, ChangelogInput, ChangelogPostRef, PublishChangelogPayload,
+};
+use crate::models::event::{BoardEvent, BoardEventKind};
+use crate::models::job::{KIND_PUBLISH_CHANGELOG, NewJob};
+use crate::models::notification::KIND_CHANGELOG;
+use crate::services::{event_service, job_service};
+
+/// Entries of an organization, newest first. Drafts and scheduled entries
+/// are only included with `include_unpublished`.
+pub async fn get_entries(
+    pool: &PgPool,
+    org_id: Uuid,
+    include_unpublished: bool,
+) -> Result<Vec<ChangelogEntry>, AppError> {
+    let entries = sqlx::query_as(
+        r#"
+        SELECT * FROM changelog_entries
+        WHERE org_id = $1
+          AND ($2 OR (is_draft = FALSE AND published_at <= now()))
+        ORDER BY published_at DESC NULLS FIRST, created_at DESC
+        "#,
+    )
+    .bind(org_id)
+    .bind(include_unpublished)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(entries)
+}
+
+pub async fn get_entry(
+    pool: &PgPool,
+    org_id: Uuid,
+    entry_id: Uuid,
+) -> Result<ChangelogEntry, AppError> {
+    sqlx::query_as("SELECT * FROM changelog_entries WHERE id = $1 AND org_id = $2")
+        .bind(entry_id)
+        .bind(org_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Changelog entry not found".to_string()))
+}
+
+/// Posts linked from any of `entry_ids`, on any board of the organization,
+/// so only for its members.
+pub async fn get_linked_posts(
+    pool: &PgPool,
+    entry_ids: &[Uuid],
+) -> Result<Vec<ChangelogPostRef>, AppError> {
+    let posts = sqlx::query_as(
+        r#"
+        SELECT cp.changelog_id, p.id, p.board_id, p.title, p.status
+        FROM changelog_posts cp
+        JOIN posts p ON p.id = cp.post_id
+        WHERE cp.changelog_id = ANY($1)
+        ORDER BY p.created_at
+        "#,
+    )
+    .bind(entry_ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(posts)
+}
+
+pub async fn create_entry(
+    pool: &PgPool,
+    org_id: Uuid,
+    input: &ChangelogInput<'_>,
+) -> Result<ChangelogEntry, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let entry: ChangelogEntry = sqlx::query_as(
+        r#"
+        INSERT INTO changelog_entries (org_id, title, body, is_draft, published_at, complete_posts)
+        VALUES ($1, $2, $3, $4, CASE WHEN $4 THEN $5 ELSE COALESCE($5, now()) END, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(org_id)
+    .bind(input.title)
+    .bind(input.body)
+    .bind(input.is_draft)
+    .bind(input.published_at)
+    .bind(input.complete_posts)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    set_linked_posts(&mut tx, &entry, input.post_ids).await?;
+    schedule_announcement(&mut tx, &entry).await?;
+
+    tx.commit().await?;
+    Ok(entry)
+}
+
+/// Replace an entry's fields and linked posts. Moving `published_at` or
+/// switching back to draft reschedules (or cancels) the announcement, unless
+/// it has already gone out.
+pub async fn update_entry(
+    pool: &PgPool,
+    org_id: Uuid,
+    entry_id: Uuid,
+    input: &ChangelogInput<'_>,
+) -> Result<ChangelogEntry, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let entry: ChangelogEntry = sqlx::query_as(
+        r#"
+        UPDATE changelog_entries SET
+            title = $3,
+            body = $4,
+            is_draft = $5,
+            published_at = CASE WHEN $5 THEN $6 ELSE COALESCE($6, now()) END,
+            complete_posts = $7,
+            updated_at = now()
+        WHERE id = $1 AND org_id = $2
+        RETURNING *
+        "#,
+    )
+    .bind(entry_id)
+    .bind(org_id)
+    .bind(input.title)
+    .bind(input.body)
+    .bind(input.is_draft)
+    .bind(input.published_at)
+    .bind(input.complete_posts)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Changelog entry not found".to_string()))?;
+
+    set_linked_posts(&mut tx, &entry, input.post_ids).await?;
+    schedule_announcement(&mut tx, &entry).await?;
+
+    tx.commit().await?;
+    Ok(entry)
+}
+
+pub async fn delete_entry(pool: &PgPool, org_id: Uuid, entry_id: Uuid) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+
+    let result = sqlx::query("DELETE FROM changelog_entries WHERE id = $1 AND org_id = $2")
+        .bind(entry_id)
+        .bind(org_id)
+        .execute(&mut *tx)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Changelog entry not found".to_string()));
+    }
+    job_service::cancel_pending(&mut tx, &announcement_key(entry_id)).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn set_linked_posts(
+    conn: &mut PgConnection,
+    entry: &ChangelogEntry,
+    post_ids: &[Uuid],
+) -> Result<(), AppError> {
+    let post_ids: Vec<Uuid> = post_ids
+        .iter()
+        .copied()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    let in_org: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM posts p
+        JOIN boards b ON b.id = p.board_id
+        WHERE p.id = ANY($1) AND b.org_id = $2
+        "#,
+    )
+    .bind(&post_ids)
+    .bind(entry.org_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    if in_org != post_ids.len() as i64 {
+        return Err(AppError::BadRequest(
+            "Linked posts must belong to this organization".to_string(),
+        ));
+    }
+
+    sqlx::query("DELETE FROM changelog_posts WHERE changelog_id = $1")
+        .bind(entry.id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query(
+        "INSERT INTO changelog_posts (changelog_id, post_id) SELECT $1, UNNEST($2::UUID[])",
+    )
+    .bind(entry.id)
+    .bind(&post_ids)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+fn announcement_key(entry_id: Uuid) -> String {
+    format!("{KIND_PUBLISH_CHANGELOG}:{entry_id}")
+}
+
+/// Queue the publish-time side effects for `published_at`, replacing any
+/// earlier schedule.
+async fn schedule_announcement(
+    conn: &mut PgConnection,
+    entry: &ChangelogEntry,
+) -> Result<(), AppError> {
+    let key = announcement_key(entry.id);
+    job_service::cancel_pending(conn, &key).await?;
+
+    if entry.is_draft == Some(false) && entry.announced_at.is_none() {
+        let payload = serde_json::to_value(PublishChangelogPayload {
+            changelog_id: entry.id,
+        })
+        .map_err(|e| AppError::InternalError(format!("Job payload serialization failed: {e}")))?;
+
+        job_service::enqueue(
+            conn,
+            NewJob {
+                kind: KIND_PUBLISH_CHANGELOG.to_string(),
+                payload,
+                org_id: Some(entry.org_id),
+                run_at: entry.published_at,
+                unique_key: Some(key),
+                ..Default::default()
+            },
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Run the side effects of publishing an entry, once: linked posts move to
+/// `done` if the entry asks for it (pushed to live clients like any other
+/// status change), and everyone who voted on or subscribed
+/// to a linked post gets one notification. Does nothing for entries that are
+/// drafts, not yet due, already announced or gone.
+///
+/// Returns the number of users notified.
+pub async fn announce(pool: &PgPool, entry_id: Uuid) -> Result<u64, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let entry: Option<ChangelogEntry> = sqlx::query_as(
+        r#"
+        SELECT * FROM changelog_entries
+        WHERE id = $1 AND is_draft = FALSE AND published_at <= now() AND announced_at IS NULL
+        FOR UPDATE
+        "#,
+    )
+    .bind(entry_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(entry) = entry else {
+        return Ok(0);
+    };
+
+    if entry.complete_posts {
+        let completed: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            r#"
+            UPDATE posts SET status = 'done', updated_at = now()
+            WHERE id IN (SELECT post_id FROM changelog_posts WHERE changelog_id = $1)
+              AND status IS DISTINCT FROM 'done'
+            RETURNING id, board_id
+            "#,
+        )
+        .bind(entry.id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for (post_id, board_id) in completed {
+            event_service::publish(
+                &mut tx,
+                &BoardEvent {
+                    board_id,
+                    post_id,
+                    kind: BoardEventKind::StatusChanged {
+                        status: "done".to_string(),
+                    },
+                },
+            )
+            .await?;
+        }
+    }
+
+    let notified = sqlx::query(
+        r#"
+        INSERT INTO notifications (user_id, kind, post_id, changelog_id)
+        SELECT DISTINCT ON (interested.user_id) interested.user_id, $2, interested.post_id, $1
+        FROM (
+            SELECT v.user_id, v.post_id FROM votes v
+            JOIN changelog_posts cp ON cp.post_id = v.post_id
+            WHERE cp.changelog_id = $1
+            UNION
+            SELECT s.user_id, s.post_id FROM subscriptions s
+            JOIN changelog_posts cp ON cp.post_id = s.post_id
+            WHERE cp.changelog_id = $1
+        ) interested
+        ORDER BY interested.user_id, interested.post_id
+        "#,
+    )
+    .bind(entry.id)
+    .bind(KIND_CHANGELOG)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    sqlx::query("UPDATE changelog_entries SET announced_at = now() WHERE id = $1")
+        .bind(entry.id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(notified)
+}
@@ -3,6 +3,9 @@ use uuid::Uuid;
 
 use crate::errors::AppError;
 use crate::models::comment::{Comment, CommentWithAuthorRow};
+use crate::models::event::{BoardEvent, BoardEventKind};
+use crate::services::{event_service, mention_service};
+use crate::telemetry;
 
 pub async fn get_comment_by_id(pool: &PgPool, comment_id: Uuid) -> Result<Comment, AppError> {
     sqlx::query_as("SELECT * FROM comments WHERE id = $1")
@@ -14,6 +17,7 @@ pub async fn get_comment_by_id(pool: &PgPool, comment_id: Uuid) -> Result<Commen
 
 pub async fn create_comment(
     pool: &PgPool,
+    org_id: Uuid,
     post_id: Uuid,
     author_id: Uuid,
     body: &str,
@@ -35,13 +39,32 @@ pub async fn create_comment(
     .fetch_one(&mut *tx)
     .await?;
 
-    // Increment comment count on the post
-    sqlx::query("UPDATE posts SET comment_count = comment_count + 1 WHERE id = $1")
-        .bind(post_id)
-        .execute(&mut *tx)
+    mention_service::record_mentions(&mut tx, org_id, post_id, Some(comment.id), author_id, body)
         .await?;
 
+    // Increment comment count on the post
+    let (board_id, comment_count): (Uuid, i32) = sqlx::query_as(
+        "UPDATE posts SET comment_count = comment_count + 1 WHERE id = $1 RETURNING board_id, COALESCE(comment_count, 0)",
+    )
+    .bind(post_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    event_service::publish(
+        &mut tx,
+        &BoardEvent {
+            board_id,
+            post_id,
+            kind: BoardEventKind::CommentCreated {
+                comment_id: comment.id,
+                comment_count,
+            },
+        },
+    )
+    .await?;
+
     tx.commit().await?;
+    metrics::counter!(telemetry::COMMENTS_CREATED).increment(1);
 
     Ok(comment)
 }
@@ -0,0 +1,77 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::counter::{CounterDrift, CounterReport};
+
+/// Compare `posts.vote_count` and `posts.comment_count` with the `votes` and
+/// `comments` tables, for one organization or (with `None`) every post.
+/// With `fix`, drifted posts are locked and their counters recomputed.
+pub async fn reconcile(
+    pool: &PgPool,
+    org_id: Option<Uuid>,
+    fix: bool,
+) -> Result<CounterReport, AppError> {
+    let posts_checked: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM posts p
+        JOIN boards b ON b.id = p.board_id
+        WHERE $1::UUID IS NULL OR b.org_id = $1
+        "#,
+    )
+    .bind(org_id)
+    .fetch_one(pool)
+    .await?;
+
+    let drifted: Vec<CounterDrift> = sqlx::query_as(
+        r#"
+        SELECT * FROM (
+            SELECT p.id as post_id, p.board_id,
+                   p.vote_count as stored_vote_count,
+                   (SELECT COUNT(*) FROM votes v WHERE v.post_id = p.id)::INT as actual_vote_count,
+                   p.comment_count as stored_comment_count,
+                   (SELECT COUNT(*) FROM comments c WHERE c.post_id = p.id)::INT as actual_comment_count
+            FROM posts p
+            JOIN boards b ON b.id = p.board_id
+            WHERE $1::UUID IS NULL OR b.org_id = $1
+        ) counts
+        WHERE stored_vote_count IS DISTINCT FROM actual_vote_count
+           OR stored_comment_count IS DISTINCT FROM actual_comment_count
+        ORDER BY post_id
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+
+    if fix && !drifted.is_empty() {
+        let post_ids: Vec<Uuid> = drifted.iter().map(|d| d.post_id).collect();
+        let mut tx = pool.begin().await?;
+
+        // Votes and comments already in flight update the post row too, so
+        // once the rows are locked the counts below can't go stale.
+        sqlx::query("SELECT id FROM posts WHERE id = ANY($1) FOR UPDATE")
+            .bind(&post_ids)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            r#"
+            UPDATE posts SET
+                vote_count = (SELECT COUNT(*) FROM votes v WHERE v.post_id = posts.id),
+                comment_count = (SELECT COUNT(*) FROM comments c WHERE c.post_id = posts.id)
+            WHERE id = ANY($1)
+            "#,
+        )
+        .bind(&post_ids)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+    }
+
+    Ok(CounterReport {
+        posts_checked,
+        fixed: fix && !drifted.is_empty(),
+        drifted,
+    })
+}
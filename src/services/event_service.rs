@@ -0,0 +1,20 @@
+use sqlx::PgConnection;
+
+use crate::errors::AppError;
+use crate::models::event::{BoardEvent, CHANNEL};
+
+/// Notify every server instance of `event`. Inside a transaction the
+/// notification is only delivered on commit, so listeners never see
+/// changes that were rolled back.
+pub async fn publish(conn: &mut PgConnection, event: &BoardEvent) -> Result<(), AppError> {
+    let payload = serde_json::to_string(event)
+        .map_err(|e| AppError::InternalError(format!("Event serialization failed: {e}")))?;
+
+    sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(CHANNEL)
+        .bind(payload)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::changelog::ChangelogEntry;
+use crate::models::feed::{FeedPostRow, StatusChangeRow};
+
+/// Items per feed. Readers poll, so older items have been seen already.
+pub const FEED_LIMIT: i64 = 50;
+
+/// Published changelog entries, newest first.
+pub async fn get_changelog_entries(
+    pool: &PgPool,
+    org_id: Uuid,
+) -> Result<Vec<ChangelogEntry>, AppError> {
+    let entries = sqlx::query_as(
+        r#"
+        SELECT * FROM changelog_entries
+        WHERE org_id = $1 AND is_draft = FALSE AND published_at <= now()
+        ORDER BY published_at DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(org_id)
+    .bind(FEED_LIMIT)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(entries)
+}
+
+/// Newest posts of a board. Posts merged into another are left out.
+pub async fn get_new_posts(pool: &PgPool, board_id: Uuid) -> Result<Vec<FeedPostRow>, AppError> {
+    let posts = sqlx::query_as(
+        r#"
+        SELECT p.id, p.title, p.description, u.name AS author_name,
+               COALESCE(p.created_at, now()) AS created_at,
+               COALESCE(p.updated_at, p.created_at, now()) AS updated_at
+        FROM posts p
+        LEFT JOIN users u ON u.id = p.author_id
+        WHERE p.board_id = $1 AND p.merged_into_id IS NULL
+        ORDER BY p.created_at DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(board_id)
+    .bind(FEED_LIMIT)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(posts)
+}
+
+/// Most recent status changes of posts on a board.
+pub async fn get_status_changes(
+    pool: &PgPool,
+    board_id: Uuid,
+) -> Result<Vec<StatusChangeRow>, AppError> {
+    let changes = sqlx::query_as(
+        r#"
+        SELECT sc.id, sc.post_id, p.title AS post_title, sc.from_status, sc.to_status,
+               sc.changed_at
+        FROM post_status_changes sc
+        JOIN posts p ON p.id = sc.post_id
+        WHERE p.board_id = $1
+        ORDER BY sc.changed_at DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(board_id)
+    .bind(FEED_LIMIT)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(changes)
+}
@@ -0,0 +1,374 @@
+use std::io::{Cursor, Write};
+
+use chrono::Utc;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+
+use crate::errors::AppError;
+use crate::models::data_request::{
+    DataRequest, ExportAttachment, ExportComment, ExportMembership, ExportPost, ExportProfile,
+    ExportReaction, ExportSubscription, ExportVote, KIND_ERASURE, KIND_EXPORT, UserDataExport,
+};
+use crate::models::job::{EraseUserPayload, KIND_ERASE_USER, NewJob};
+use crate::services::{attachment_service, image_service, job_service};
+
+/// Collect everything stored about a user and log the export.
+pub async fn export_user_data(pool: &PgPool, user_id: Uuid) -> Result<UserDataExport, AppError> {
+    let profile: ExportProfile = sqlx::query_as(
+        "SELECT id, email, name, avatar_url, provider, created_at FROM users WHERE id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let memberships: Vec<ExportMembership> = sqlx::query_as(
+        r#"
+        SELECT m.org_id, o.name as org_name, m.role, m.created_at as joined_at
+        FROM org_members m
+        JOIN organizations o ON o.id = m.org_id
+        WHERE m.user_id = $1
+        ORDER BY m.created_at ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let posts: Vec<ExportPost> = sqlx::query_as(
+        r#"
+        SELECT p.id, p.board_id, b.name as board_name, p.title, p.description, p.status,
+               p.created_at, p.updated_at
+        FROM posts p
+        JOIN boards b ON b.id = p.board_id
+        WHERE p.author_id = $1
+        ORDER BY p.created_at ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let comments: Vec<ExportComment> = sqlx::query_as(
+        r#"
+        SELECT c.id, c.post_id, p.title as post_title, c.body, c.created_at
+        FROM comments c
+        JOIN posts p ON p.id = c.post_id
+        WHERE c.author_id = $1
+        ORDER BY c.created_at ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let votes: Vec<ExportVote> = sqlx::query_as(
+        r#"
+        SELECT v.post_id, p.title as post_title, v.created_at
+        FROM votes v
+        JOIN posts p ON p.id = v.post_id
+        WHERE v.user_id = $1
+        ORDER BY v.created_at ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let subscriptions: Vec<ExportSubscription> = sqlx::query_as(
+        r#"
+        SELECT s.post_id, p.title as post_title, s.notify_on, s.created_at
+        FROM subscriptions s
+        JOIN posts p ON p.id = s.post_id
+        WHERE s.user_id = $1
+        ORDER BY s.created_at ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let reactions: Vec<ExportReaction> = sqlx::query_as(
+        "SELECT target_type, target_id, emoji, created_at FROM reactions WHERE user_id = $1 ORDER BY created_at ASC",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let attachments: Vec<ExportAttachment> = sqlx::query_as(
+        r#"
+        SELECT id, post_id, comment_id, filename, content_type, size_bytes, created_at
+        FROM attachments
+        WHERE uploader_id = $1
+        ORDER BY created_at ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let request = start_request(&mut *pool.acquire().await?, user_id, KIND_EXPORT).await?;
+    finish_request(pool, request.id, None).await?;
+    tracing::info!(
+        "Exported data for user {user_id} (data request {})",
+        request.id
+    );
+
+    Ok(UserDataExport {
+        exported_at: Utc::now(),
+        profile,
+        memberships,
+        posts,
+        comments,
+        votes,
+        subscriptions,
+        reactions,
+        attachments,
+    })
+}
+
+/// Package an export as a ZIP archive with one JSON file per section.
+pub fn export_to_zip(export: &UserDataExport) -> Result<Vec<u8>, AppError> {
+    fn section<T: serde::Serialize>(value: &T) -> Result<Vec<u8>, AppError> {
+        serde_json::to_vec_pretty(value)
+            .map_err(|e| AppError::InternalError(format!("Export serialization failed: {e}")))
+    }
+
+    let files = [
+        ("profile.json", section(&export.profile)?),
+        ("memberships.json", section(&export.memberships)?),
+        ("posts.json", section(&export.posts)?),
+        ("comments.json", section(&export.comments)?),
+        ("votes.json", section(&export.votes)?),
+        ("subscriptions.json", section(&export.subscriptions)?),
+        ("reactions.json", section(&export.reactions)?),
+        ("attachments.json", section(&export.attachments)?),
+    ];
+
+    let zip_error =
+        |e: zip::result::ZipError| AppError::InternalError(format!("Export archive failed: {e}"));
+    let mut archive = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, data) in files {
+        archive.start_file(name, options).map_err(zip_error)?;
+        archive
+            .write_all(&data)
+            .map_err(|e| AppError::InternalError(format!("Export archive failed: {e}")))?;
+    }
+    Ok(archive.finish().map_err(zip_error)?.into_inner())
+}
+
+/// Queue erasure of a user as an `erase_user` job, logged as a pending
+/// `data_requests` entry that the job completes. Refuses up front when the
+/// erasure could not succeed (see `erase_user`). The user's tokens stop
+/// working right away. Queueing again while a run is pending does nothing.
+pub async fn request_erasure(pool: &PgPool, user_id: Uuid) -> Result<(), AppError> {
+    plan_erasure(pool, user_id).await?;
+
+    let mut tx = pool.begin().await?;
+    let request = start_request(&mut tx, user_id, KIND_ERASURE).await?;
+
+    let payload = serde_json::to_value(EraseUserPayload {
+        user_id,
+        request_id: request.id,
+    })
+    .map_err(|e| AppError::InternalError(format!("Job payload serialization failed: {e}")))?;
+    let queued = job_service::enqueue(
+        &mut tx,
+        NewJob {
+            kind: KIND_ERASE_USER.to_string(),
+            payload,
+            unique_key: Some(format!("{KIND_ERASE_USER}:{user_id}")),
+            ..Default::default()
+        },
+    )
+    .await?;
+    if queued.is_some() {
+        // Sign the user out now rather than when the job gets to run
+        sqlx::query("UPDATE users SET token_version = token_version + 1 WHERE id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        tracing::info!(
+            "Queued erasure of user {user_id} (data request {})",
+            request.id
+        );
+    }
+    Ok(())
+}
+
+/// Erase a user: detach their posts, comments and uploads, remove their votes
+/// (recomputing `posts.vote_count`) and delete the account. Organizations
+/// where they are the only member go too; the last admin of a shared
+/// organization must hand over first. Each run updates data request
+/// `request_id`, so a failed run that is retried later completes it.
+///
+/// This is the intended level of anonymization: the text of posts and
+/// comments stays as written and only loses its link to the account, so
+/// anything the user wrote about themselves, or @mentions of them in other
+/// people's text, remains.
+///
+/// Files that are no longer referenced are removed by a background job queued
+/// in the same transaction, so they are retried until they are gone.
+pub async fn erase_user(pool: &PgPool, user_id: Uuid, request_id: Uuid) -> Result<(), AppError> {
+    let result = run_erasure(pool, user_id).await;
+    let error = result.as_ref().err().map(|e| e.to_string());
+    finish_request(pool, request_id, error.as_deref()).await?;
+
+    match &error {
+        None => tracing::info!("Erased user {user_id} (data request {request_id})"),
+        Some(e) => {
+            tracing::warn!("Erasure of user {user_id} failed (data request {request_id}): {e}")
+        }
+    }
+    result
+}
+
+/// Organizations to delete with the user, and stored files to remove.
+struct ErasurePlan {
+    orphaned_orgs: Vec<Uuid>,
+    storage_keys: Vec<String>,
+}
+
+/// Work out what erasing a user removes, or why it can't be done yet.
+async fn plan_erasure(pool: &PgPool, user_id: Uuid) -> Result<ErasurePlan, AppError> {
+    let avatar_url: Option<String> =
+        sqlx::query_scalar("SELECT avatar_url FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let admin_orgs: Vec<(Uuid, String, Option<String>, i64, i64)> = sqlx::query_as(
+        r#"
+        SELECT o.id, o.name, o.logo_url,
+               (SELECT COUNT(*) FROM org_members WHERE org_id = o.id) as member_count,
+               (SELECT COUNT(*) FROM org_members WHERE org_id = o.id AND role = 'admin') as admin_count
+        FROM organizations o
+        JOIN org_members m ON m.org_id = o.id
+        WHERE m.user_id = $1 AND m.role = 'admin'
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut orphaned_orgs = Vec::new();
+    let mut storage_keys = Vec::new();
+    for (org_id, name, logo_url, member_count, admin_count) in admin_orgs {
+        if member_count == 1 {
+            orphaned_orgs.push(org_id);
+            storage_keys.extend(attachment_service::get_storage_keys_for_org(pool, org_id).await?);
+            if let Some(logo_url) = logo_url {
+                storage_keys.extend(image_service::storage_keys_for_url(&logo_url));
+            }
+        } else if admin_count == 1 {
+            return Err(AppError::BadRequest(format!(
+                "You are the only admin of \"{name}\". Make another member an admin before deleting your account"
+            )));
+        }
+    }
+    if let Some(avatar_url) = &avatar_url {
+        storage_keys.extend(image_service::storage_keys_for_url(avatar_url));
+    }
+
+    Ok(ErasurePlan {
+        orphaned_orgs,
+        storage_keys,
+    })
+}
+
+async fn run_erasure(pool: &PgPool, user_id: Uuid) -> Result<(), AppError> {
+    let ErasurePlan {
+        orphaned_orgs,
+        storage_keys,
+    } = match plan_erasure(pool, user_id).await {
+        // Already gone, e.g. by an earlier run that failed to record it
+        Err(AppError::NotFound(_)) => return Ok(()),
+        plan => plan?,
+    };
+
+    let mut tx = pool.begin().await?;
+
+    let voted_posts: Vec<Uuid> =
+        sqlx::query_scalar("DELETE FROM votes WHERE user_id = $1 RETURNING post_id")
+            .bind(user_id)
+            .fetch_all(&mut *tx)
+            .await?;
+    sqlx::query(
+        r#"
+        UPDATE posts SET vote_count = (SELECT COUNT(*) FROM votes v WHERE v.post_id = posts.id)
+        WHERE id = ANY($1)
+        "#,
+    )
+    .bind(&voted_posts)
+    .execute(&mut *tx)
+    .await?;
+
+    // Content stays, without its author
+    sqlx::query("UPDATE posts SET author_id = NULL WHERE author_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE comments SET author_id = NULL WHERE author_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE attachments SET uploader_id = NULL WHERE uploader_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("DELETE FROM organizations WHERE id = ANY($1)")
+        .bind(&orphaned_orgs)
+        .execute(&mut *tx)
+        .await?;
+
+    // Memberships, subscriptions, reactions, mentions and notifications cascade
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    job_service::enqueue_file_deletion(&mut tx, storage_keys).await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+async fn start_request(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    kind: &str,
+) -> Result<DataRequest, AppError> {
+    let request =
+        sqlx::query_as("INSERT INTO data_requests (user_id, kind) VALUES ($1, $2) RETURNING *")
+            .bind(user_id)
+            .bind(kind)
+            .fetch_one(conn)
+            .await?;
+    Ok(request)
+}
+
+async fn finish_request(
+    pool: &PgPool,
+    request_id: Uuid,
+    error: Option<&str>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        UPDATE data_requests
+        SET status = CASE WHEN $2::TEXT IS NULL THEN 'completed' ELSE 'failed' END,
+            error = $2, completed_at = now()
+        WHERE id = $1
+        "#,
+    )
+    .bind(request_id)
+    .bind(error)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use sqlx::PgPool;
+
+use crate::db;
+use crate::jobs::Heartbeat;
+use crate::mailer::Mailer;
+use crate::models::health::{
+    BuildInfo, ComponentHealth, ReadinessReport, STATUS_DISABLED, STATUS_DRAINING, STATUS_ERROR,
+    STATUS_OK,
+};
+use crate::shutdown::Drain;
+use crate::storage::Storage;
+
+/// A check that takes longer than this counts as failed, so one hanging
+/// backend can't hold up the probe.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Run every readiness check concurrently. While draining the report fails
+/// regardless, but still shows the components.
+pub async fn readiness(
+    drain: &Drain,
+    pool: &PgPool,
+    heartbeat: &Heartbeat,
+    mailer: &dyn Mailer,
+    storage: &dyn Storage,
+) -> ReadinessReport {
+    let (database, migrations, mail, storage) = tokio::join!(
+        timed(check_database(pool)),
+        timed(check_migrations(pool)),
+        timed(async { mailer.check().await.map_err(|e| e.0) }),
+        timed(async { storage.check().await.map_err(|e| e.to_string()) }),
+    );
+    let checks = BTreeMap::from([
+        ("database", database),
+        ("migrations", migrations),
+        ("jobs", check_jobs(heartbeat)),
+        ("mail", mail),
+        ("storage", storage),
+    ]);
+
+    let status = if drain.is_draining() {
+        STATUS_DRAINING
+    } else if checks.values().any(|c| c.status == STATUS_ERROR) {
+        STATUS_ERROR
+    } else {
+        STATUS_OK
+    };
+    ReadinessReport {
+        status,
+        build: BuildInfo::current(),
+        checks,
+    }
+}
+
+async fn timed(check: impl Future<Output = Result<(), String>>) -> ComponentHealth {
+    let started = Instant::now();
+    let result = tokio::time::timeout(CHECK_TIMEOUT, check)
+        .await
+        .unwrap_or_else(|_| Err(format!("timed out after {}s", CHECK_TIMEOUT.as_secs())));
+    let latency_ms = started.elapsed().as_millis() as u64;
+    match result {
+        Ok(()) => ComponentHealth {
+            status: STATUS_OK,
+            latency_ms,
+            detail: None,
+        },
+        Err(detail) => ComponentHealth {
+            status: STATUS_ERROR,
+            latency_ms,
+            detail: Some(detail),
+        },
+    }
+}
+
+async fn check_database(pool: &PgPool) -> Result<(), String> {
+    sqlx::query_scalar::<_, i32>("SELECT 1")
+        .fetch_one(pool)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+async fn check_migrations(pool: &PgPool) -> Result<(), String> {
+    let pending = db::pending_migrations(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    if pending.is_empty() {
+        Ok(())
+    } else {
+        let versions: Vec<String> = pending.iter().map(i64::to_string).collect();
+        Err(format!("pending: {}", versions.join(", ")))
+    }
+}
+
+fn check_jobs(heartbeat: &Heartbeat) -> ComponentHealth {
+    let (status, detail) = if heartbeat.workers() == 0 {
+        (
+            STATUS_DISABLED,
+            Some("no job workers in this process".to_string()),
+        )
+    } else {
+        match heartbeat.since_last() {
+            Some(since) if since <= Heartbeat::STALE_AFTER => (STATUS_OK, None),
+            Some(since) => (
+                STATUS_ERROR,
+                Some(format!("no worker heartbeat for {}s", since.as_secs())),
+            ),
+            None => (STATUS_ERROR, Some("workers have not started".to_string())),
+        }
+    };
+    ComponentHealth {
+        status,
+        latency_ms: 0,
+        detail,
+    }
+}
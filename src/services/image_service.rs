@@ -0,0 +1,178 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::organization::Organization;
+use crate::models::user::User;
+use crate::storage::{self, Storage};
+use crate::utils::imaging::{self, ImageFormat, ImageKind};
+
+/// Public path prefix under which processed images are served.
+pub const URL_PREFIX: &str = "/api/images/";
+
+/// Process an upload and store every rendition under a fresh version
+/// directory, so URLs change whenever the image does and can be cached
+/// indefinitely. Returns the URL of the largest rendition.
+pub async fn store_image(
+    storage: &dyn Storage,
+    kind: ImageKind,
+    owner_id: Uuid,
+    data: Vec<u8>,
+) -> Result<String, AppError> {
+    let processed = tokio::task::spawn_blocking(move || imaging::process(&data, kind))
+        .await
+        .map_err(|e| AppError::InternalError(format!("Blocking task failed: {e}")))??;
+
+    let dir = format!("{}/{owner_id}/{}", kind.dir(), Uuid::new_v4().simple());
+    let ext = processed.format.extension();
+    for rendition in &processed.renditions {
+        let key = format!("{dir}/{}.{ext}", rendition.size);
+        storage
+            .put(&key, &rendition.data, processed.format.content_type())
+            .await?;
+    }
+
+    Ok(format!("{URL_PREFIX}{dir}/{}.{ext}", kind.sizes()[0]))
+}
+
+/// Storage keys of every rendition behind a URL returned by `store_image`.
+/// External URLs (e.g. from an OAuth provider) yield no keys.
+pub fn storage_keys_for_url(url: &str) -> Vec<String> {
+    let Some(key) = url.strip_prefix(URL_PREFIX) else {
+        return Vec::new();
+    };
+    let Some((dir, file)) = key.rsplit_once('/') else {
+        return Vec::new();
+    };
+    let kind = dir.split('/').next().and_then(ImageKind::from_dir);
+    let ext = file.rsplit_once('.').map(|(_, ext)| ext);
+    match (kind, ext) {
+        (Some(kind), Some(ext)) => kind
+            .sizes()
+            .iter()
+            .map(|size| format!("{dir}/{size}.{ext}"))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Set (or clear, with `None`) a user's avatar and remove the files of the
+/// one it replaces.
+pub async fn set_user_avatar(
+    pool: &PgPool,
+    storage: &dyn Storage,
+    user_id: Uuid,
+    avatar_url: Option<&str>,
+) -> Result<User, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let previous: Option<String> =
+        sqlx::query_scalar("SELECT avatar_url FROM users WHERE id = $1 FOR UPDATE")
+            .bind(user_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let user: User = sqlx::query_as("UPDATE users SET avatar_url = $2 WHERE id = $1 RETURNING *")
+        .bind(user_id)
+        .bind(avatar_url)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    if let Some(previous) = previous {
+        storage::delete_all(storage, &storage_keys_for_url(&previous)).await;
+    }
+
+    Ok(user)
+}
+
+/// Set (or clear, with `None`) an organization's logo and remove the files of
+/// the one it replaces.
+pub async fn set_org_logo(
+    pool: &PgPool,
+    storage: &dyn Storage,
+    org_id: Uuid,
+    logo_url: Option<&str>,
+) -> Result<Organization, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let previous: Option<String> =
+        sqlx::query_scalar("SELECT logo_url FROM organizations WHERE id = $1 FOR UPDATE")
+            .bind(org_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+    let org: Organization = sqlx::query_as(
+        "UPDATE organizations SET logo_url = $2, updated_at = NOW() WHERE id = $1 RETURNING *",
+    )
+    .bind(org_id)
+    .bind(logo_url)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    if let Some(previous) = previous {
+        storage::delete_all(storage, &storage_keys_for_url(&previous)).await;
+    }
+
+    Ok(org)
+}
+
+/// Resolve a served image path (`{kind}/{owner}/{version}/{size}.{ext}`) to
+/// its storage key and format, rejecting anything `store_image` would not
+/// have produced.
+pub fn resolve_image_path(path: &str) -> Option<(String, ImageFormat)> {
+    let parts: Vec<&str> = path.split('/').collect();
+    let [kind, owner, version, file] = parts.as_slice() else {
+        return None;
+    };
+    let kind = ImageKind::from_dir(kind)?;
+    Uuid::parse_str(owner).ok()?;
+    Uuid::parse_str(version).ok()?;
+    let (size, ext) = file.split_once('.')?;
+    let format = ImageFormat::from_extension(ext)?;
+    if !kind.sizes().iter().any(|s| s.to_string() == size) {
+        return None;
+    }
+    Some((path.to_string(), format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_for_stored_url_cover_all_sizes() {
+        let owner = Uuid::new_v4();
+        let version = Uuid::new_v4().simple();
+        let url = format!("{URL_PREFIX}avatars/{owner}/{version}/256.jpg");
+        assert_eq!(
+            storage_keys_for_url(&url),
+            vec![
+                format!("avatars/{owner}/{version}/256.jpg"),
+                format!("avatars/{owner}/{version}/128.jpg"),
+                format!("avatars/{owner}/{version}/64.jpg"),
+            ]
+        );
+        assert!(storage_keys_for_url("https://avatars.example.com/u/1.png").is_empty());
+    }
+
+    #[test]
+    fn resolves_only_well_formed_paths() {
+        let owner = Uuid::new_v4();
+        let version = Uuid::new_v4().simple();
+        let path = format!("logos/{owner}/{version}/128.png");
+        assert_eq!(
+            resolve_image_path(&path),
+            Some((path.clone(), ImageFormat::Png))
+        );
+        assert!(resolve_image_path(&format!("logos/{owner}/{version}/64.png")).is_none());
+        assert!(resolve_image_path(&format!("other/{owner}/{version}/128.png")).is_none());
+        assert!(resolve_image_path(&format!("logos/{owner}/{version}/128.gif")).is_none());
+        assert!(resolve_image_path("logos/../../etc/passwd").is_none());
+    }
+}
@@ -0,0 +1,349 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::mailer::Email;
+use crate::models::job::{
+    DeleteFilesPayload, Job, KIND_DELETE_FILES, KIND_SEND_EMAIL, NewJob, STATUS_DEAD,
+};
+
+/// A `running` job whose worker has been silent this long is assumed lost
+/// (crashed or killed) and may be claimed again.
+const STALE_AFTER_SECS: f64 = 600.0;
+
+/// Completed jobs are kept this long for inspection.
+const KEEP_COMPLETED_DAYS: i32 = 7;
+
+/// Payload of jobs that reschedule themselves after each successful run.
+#[derive(Serialize, Deserialize)]
+struct Recurrence {
+    every_secs: u64,
+}
+
+/// Queue a job. Returns `None` when `unique_key` is set and a pending job
+/// with that key already exists.
+pub async fn enqueue(conn: &mut PgConnection, job: NewJob) -> Result<Option<Job>, AppError> {
+    let job = sqlx::query_as(
+        r#"
+        INSERT INTO jobs (kind, payload, org_id, run_at, max_attempts, unique_key)
+        VALUES ($1, $2, $3, COALESCE($4, now()), $5, $6)
+        ON CONFLICT (unique_key) WHERE status = 'pending' DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(&job.kind)
+    .bind(&job.payload)
+    .bind(job.org_id)
+    .bind(job.run_at)
+    .bind(job.max_attempts)
+    .bind(&job.unique_key)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(job)
+}
+
+/// Queue an email; delivery is retried if the mail backend fails.
+pub async fn enqueue_email(conn: &mut PgConnection, email: &Email) -> Result<(), AppError> {
+    enqueue(
+        conn,
+        NewJob {
+            kind: KIND_SEND_EMAIL.to_string(),
+            payload: to_payload(email)?,
+            ..Default::default()
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+/// Queue removal of stored objects whose database rows are gone. Enqueue in
+/// the transaction that deletes the rows, so the files go if and only if the
+/// rows do.
+pub async fn enqueue_file_deletion(
+    conn: &mut PgConnection,
+    keys: Vec<String>,
+) -> Result<(), AppError> {
+    if keys.is_empty() {
+        return Ok(());
+    }
+    enqueue(
+        conn,
+        NewJob {
+            kind: KIND_DELETE_FILES.to_string(),
+            payload: to_payload(&DeleteFilesPayload { keys })?,
+            ..Default::default()
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+fn to_payload(payload: &impl Serialize) -> Result<serde_json::Value, AppError> {
+    serde_json::to_value(payload)
+        .map_err(|e| AppError::InternalError(format!("Job payload serialization failed: {e}")))
+}
+
+/// Run `kind` every `every`, starting one interval from now. Safe to call on
+/// every startup and from several instances: there is only ever one pending
+/// run, whose interval is updated if it changed.
+pub async fn schedule_recurring(
+    pool: &PgPool,
+    kind: &str,
+    every: Duration,
+) -> Result<(), AppError> {
+    let payload = to_payload(&Recurrence {
+        every_secs: every.as_secs(),
+    })?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO jobs (kind, payload, run_at, unique_key)
+        VALUES ($1, $2, now() + make_interval(secs => $3), $1)
+        ON CONFLICT (unique_key) WHERE status = 'pending'
+        DO UPDATE SET payload = EXCLUDED.payload
+        "#,
+    )
+    .bind(kind)
+    .bind(&payload)
+    .bind(every.as_secs_f64())
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Drop the pending run of a recurring job, e.g. once it is disabled.
+pub async fn unschedule_recurring(pool: &PgPool, kind: &str) -> Result<(), AppError> {
+    let mut conn = pool.acquire().await?;
+    cancel_pending(&mut conn, kind).await
+}
+
+/// Delete the pending job with `unique_key`, if any. A run that has already
+/// started is not affected.
+pub async fn cancel_pending(conn: &mut PgConnection, unique_key: &str) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM jobs WHERE unique_key = $1 AND status = 'pending'")
+        .bind(unique_key)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// Claim the next due job of one of `kinds`, marking it `running`. Rows
+/// locked by other workers are skipped rather than waited on. A stale job
+/// that has used up its attempts is left `dead` instead, so one that keeps
+/// crashing its worker isn't run forever.
+pub async fn claim_next(pool: &PgPool, kinds: &[&str]) -> Result<Option<Job>, AppError> {
+    sqlx::query(
+        r#"
+        UPDATE jobs SET status = 'dead', locked_at = NULL,
+            last_error = 'Worker stopped while running the job'
+        WHERE kind = ANY($1)
+          AND status = 'running'
+          AND locked_at < now() - make_interval(secs => $2)
+          AND attempts >= max_attempts
+        "#,
+    )
+    .bind(kinds)
+    .bind(STALE_AFTER_SECS)
+    .execute(pool)
+    .await?;
+
+    let job = sqlx::query_as(
+        r#"
+        UPDATE jobs SET status = 'running', attempts = attempts + 1, locked_at = now()
+        WHERE id = (
+            SELECT id FROM jobs
+            WHERE kind = ANY($1)
+              AND ((status = 'pending' AND run_at <= now())
+                OR (status = 'running' AND locked_at < now() - make_interval(secs => $2)
+                    AND attempts < max_attempts))
+            ORDER BY run_at
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(kinds)
+    .bind(STALE_AFTER_SECS)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(job)
+}
+
+/// Mark a claimed job as done, queueing its next run if it recurs.
+pub async fn complete(pool: &PgPool, job: &Job) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        r#"
+        UPDATE jobs SET status = 'completed', locked_at = NULL, completed_at = now()
+        WHERE id = $1 AND status = 'running'
+        "#,
+    )
+    .bind(job.id)
+    .execute(&mut *tx)
+    .await?;
+
+    if let Ok(recurrence) = serde_json::from_value::<Recurrence>(job.payload.clone()) {
+        let every = chrono::Duration::seconds(recurrence.every_secs as i64);
+        enqueue(
+            &mut tx,
+            NewJob {
+                kind: job.kind.clone(),
+                payload: job.payload.clone(),
+                org_id: job.org_id,
+                run_at: Some(Utc::now() + every),
+                max_attempts: job.max_attempts,
+                unique_key: job.unique_key.clone(),
+            },
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Record a failed attempt. The job is retried after `backoff`, or left
+/// `dead` once it has used up its attempts (or a newer run with the same
+/// `unique_key` is already queued).
+pub async fn record_failure(pool: &PgPool, job: &Job, error: &str) -> Result<Job, AppError> {
+    let job = sqlx::query_as(
+        r#"
+        UPDATE jobs SET
+            status = CASE
+                WHEN attempts >= max_attempts THEN 'dead'
+                WHEN unique_key IS NOT NULL AND EXISTS (
+                    SELECT 1 FROM jobs other
+                    WHERE other.unique_key = jobs.unique_key AND other.status = 'pending'
+                ) THEN 'dead'
+                ELSE 'pending'
+            END,
+            run_at = now() + make_interval(secs => $3),
+            locked_at = NULL,
+            last_error = $2
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(job.id)
+    .bind(error)
+    .bind(backoff(job.attempts).as_secs_f64())
+    .fetch_one(pool)
+    .await?;
+
+    Ok(job)
+}
+
+/// Delay before retrying after the `attempts`-th failure: 10 seconds,
+/// doubling each time, at most an hour.
+pub fn backoff(attempts: i32) -> Duration {
+    let exponent = attempts.clamp(1, 16) as u32 - 1;
+    Duration::from_secs(10 * 2u64.pow(exponent)).min(Duration::from_secs(3600))
+}
+
+/// Jobs, newest first, optionally limited to one organization or status.
+pub async fn list_jobs(
+    pool: &PgPool,
+    org_id: Option<Uuid>,
+    status: Option<&str>,
+    limit: i64,
+) -> Result<Vec<Job>, AppError> {
+    let jobs = sqlx::query_as(
+        r#"
+        SELECT * FROM jobs
+        WHERE ($1::UUID IS NULL OR org_id = $1)
+          AND ($2::TEXT IS NULL OR status = $2)
+        ORDER BY created_at DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(org_id)
+    .bind(status)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(jobs)
+}
+
+pub async fn get_job(pool: &PgPool, org_id: Option<Uuid>, job_id: Uuid) -> Result<Job, AppError> {
+    sqlx::query_as("SELECT * FROM jobs WHERE id = $1 AND ($2::UUID IS NULL OR org_id = $2)")
+        .bind(job_id)
+        .bind(org_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Job not found".to_string()))
+}
+
+/// Run a dead job again with a fresh set of attempts, or a pending one
+/// without waiting for its backoff.
+pub async fn retry_job(pool: &PgPool, org_id: Option<Uuid>, job_id: Uuid) -> Result<Job, AppError> {
+    let job = get_job(pool, org_id, job_id).await?;
+    let attempts = if job.status == STATUS_DEAD {
+        0
+    } else {
+        job.attempts
+    };
+
+    sqlx::query_as(
+        r#"
+        UPDATE jobs SET status = 'pending', attempts = $2, run_at = now()
+        WHERE id = $1 AND status IN ('pending', 'dead')
+        RETURNING *
+        "#,
+    )
+    .bind(job_id)
+    .bind(attempts)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_err) if db_err.code().as_deref() == Some("23505") => {
+            AppError::BadRequest("The same job is already queued".to_string())
+        }
+        _ => AppError::DatabaseError(e),
+    })?
+    .ok_or_else(|| AppError::BadRequest(format!("Cannot retry a {} job", job.status)))
+}
+
+/// Number of jobs in each status, for the queue depth metric.
+pub async fn count_by_status(pool: &PgPool) -> Result<Vec<(String, i64)>, AppError> {
+    let counts = sqlx::query_as("SELECT status, COUNT(*) FROM jobs GROUP BY status")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(counts)
+}
+
+/// Delete completed jobs older than a week. Returns how many were removed.
+pub async fn prune_completed(pool: &PgPool) -> Result<u64, AppError> {
+    let result = sqlx::query(
+        "DELETE FROM jobs WHERE status = 'completed' AND completed_at < now() - make_interval(days => $1)",
+    )
+    .bind(KEEP_COMPLETED_DAYS)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_an_hour() {
+        assert_eq!(backoff(1), Duration::from_secs(10));
+        assert_eq!(backoff(2), Duration::from_secs(20));
+        assert_eq!(backoff(4), Duration::from_secs(80));
+        assert_eq!(backoff(10), Duration::from_secs(3600));
+        assert_eq!(backoff(1000), Duration::from_secs(3600));
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::mention::{MentionSpan, MentionWithUserRow};
+use crate::models::notification::KIND_MENTION;
+use crate::services::notification_service;
+
+/// An org member that can be mentioned by name.
+#[derive(Debug, sqlx::FromRow)]
+pub struct MentionCandidate {
+    pub user_id: Uuid,
+    pub name: String,
+}
+
+/// A `@name` span found in text. Offsets are character positions.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParsedMention {
+    pub user_id: Uuid,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Find `@name` mentions of the given members in `text`.
+///
+/// Names are matched case-insensitively and may contain spaces; when several
+/// names match at the same `@`, the longest wins. The `@` must not follow a
+/// word character (so e-mail addresses are ignored) and the name must not be
+/// followed by one.
+pub fn parse_mentions(text: &str, members: &[MentionCandidate]) -> Vec<ParsedMention> {
+    let chars: Vec<char> = text.chars().collect();
+
+    let mut candidates: Vec<(&MentionCandidate, Vec<char>)> = members
+        .iter()
+        .map(|m| (m, m.name.chars().collect::<Vec<char>>()))
+        .filter(|(_, name)| !name.is_empty())
+        .collect();
+    candidates.sort_by_key(|(_, name)| std::cmp::Reverse(name.len()));
+
+    let mut mentions = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '@' && (i == 0 || !is_word_char(chars[i - 1])) {
+            let matched = candidates
+                .iter()
+                .find(|(_, name)| matches_at(&chars, i + 1, name));
+            if let Some((member, name)) = matched {
+                let end = i + 1 + name.len();
+                mentions.push(ParsedMention {
+                    user_id: member.user_id,
+                    start: i,
+                    end,
+                });
+                i = end;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    mentions
+}
+
+fn matches_at(chars: &[char], pos: usize, name: &[char]) -> bool {
+    let end = pos + name.len();
+    if end > chars.len() {
+        return false;
+    }
+    let same = chars[pos..end]
+        .iter()
+        .zip(name)
+        .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()));
+    same && chars.get(end).is_none_or(|c| !is_word_char(*c))
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Resolve mentions in a post description (`comment_id` = None) or comment body,
+/// replace any previously stored mentions for that text and notify newly
+/// mentioned users. Runs inside the caller's transaction.
+pub async fn record_mentions(
+    conn: &mut PgConnection,
+    org_id: Uuid,
+    post_id: Uuid,
+    comment_id: Option<Uuid>,
+    author_id: Uuid,
+    text: &str,
+) -> Result<(), AppError> {
+    let previous: Vec<Uuid> = sqlx::query_scalar(
+        "DELETE FROM mentions WHERE post_id = $1 AND comment_id IS NOT DISTINCT FROM $2 RETURNING user_id",
+    )
+    .bind(post_id)
+    .bind(comment_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    if !text.contains('@') {
+        return Ok(());
+    }
+
+    let members: Vec<MentionCandidate> = sqlx::query_as(
+        r#"
+        SELECT u.id as user_id, u.name
+        FROM users u
+        JOIN org_members m ON m.user_id = u.id
+        WHERE m.org_id = $1
+        "#,
+    )
+    .bind(org_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mentions = parse_mentions(text, &members);
+    let mut notified: Vec<Uuid> = Vec::new();
+
+    for mention in &mentions {
+        sqlx::query(
+            r#"
+            INSERT INTO mentions (post_id, comment_id, user_id, start_offset, end_offset)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(post_id)
+        .bind(comment_id)
+        .bind(mention.user_id)
+        .bind(mention.start as i32)
+        .bind(mention.end as i32)
+        .execute(&mut *conn)
+        .await?;
+
+        // Notify once per user, skipping self-mentions and users already mentioned before an edit
+        if mention.user_id == author_id
+            || previous.contains(&mention.user_id)
+            || notified.contains(&mention.user_id)
+        {
+            continue;
+        }
+        notification_service::create_notification(
+            &mut *conn,
+            mention.user_id,
+            Some(author_id),
+            KIND_MENTION,
+            Some(post_id),
+            comment_id,
+        )
+        .await?;
+        notified.push(mention.user_id);
+    }
+
+    Ok(())
+}
+
+pub async fn get_post_mentions(pool: &PgPool, post_id: Uuid) -> Result<Vec<MentionSpan>, AppError> {
+    let rows: Vec<MentionWithUserRow> = sqlx::query_as(
+        r#"
+        SELECT m.comment_id, m.user_id, u.name as user_name, m.start_offset, m.end_offset
+        FROM mentions m
+        JOIN users u ON u.id = m.user_id
+        WHERE m.post_id = $1 AND m.comment_id IS NULL
+        ORDER BY m.start_offset ASC
+        "#,
+    )
+    .bind(post_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(MentionSpan::from).collect())
+}
+
+/// Batch-fetch mentions for multiple comments in a single query.
+/// Returns a HashMap mapping comment_id -> Vec<MentionSpan>.
+pub async fn get_mentions_for_comment_ids(
+    pool: &PgPool,
+    comment_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<MentionSpan>>, AppError> {
+    let rows: Vec<MentionWithUserRow> = sqlx::query_as(
+        r#"
+        SELECT m.comment_id, m.user_id, u.name as user_name, m.start_offset, m.end_offset
+        FROM mentions m
+        JOIN users u ON u.id = m.user_id
+        WHERE m.comment_id = ANY($1)
+        ORDER BY m.start_offset ASC
+        "#,
+    )
+    .bind(comment_ids)
+    .fetch_all(pool)
+    .await?;
+
+    let mut map: HashMap<Uuid, Vec<MentionSpan>> = HashMap::new();
+    for row in rows {
+        if let Some(comment_id) = row.comment_id {
+            map.entry(comment_id)
+                .or_default()
+                .push(MentionSpan::from(row));
+        }
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(name: &str) -> MentionCandidate {
+        MentionCandidate {
+            user_id: Uuid::new_v4(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_simple_mention() {
+        let members = vec![member("Alice")];
+        let mentions = parse_mentions("thanks @alice!", &members);
+        assert_eq!(
+            mentions,
+            vec![ParsedMention {
+                user_id: members[0].user_id,
+                start: 7,
+                end: 13,
+            }]
+        );
+    }
+
+    #[test]
+    fn prefers_longest_name_with_spaces() {
+        let members = vec![member("Bob"), member("Bob Smith")];
+        let mentions = parse_mentions("cc @Bob Smith and @Bob", &members);
+        assert_eq!(mentions.len(), 2);
+        assert_eq!(mentions[0].user_id, members[1].user_id);
+        assert_eq!((mentions[0].start, mentions[0].end), (3, 13));
+        assert_eq!(mentions[1].user_id, members[0].user_id);
+    }
+
+    #[test]
+    fn ignores_emails_and_partial_names() {
+        let members = vec![member("Al")];
+        assert!(parse_mentions("mail al@al.com", &members).is_empty());
+        assert!(parse_mentions("hi @Alan", &members).is_empty());
+    }
+
+    #[test]
+    fn offsets_count_characters_not_bytes() {
+        let members = vec![member("Zoë")];
+        let mentions = parse_mentions("🎉 @zoë", &members);
+        assert_eq!((mentions[0].start, mentions[0].end), (2, 6));
+    }
+}
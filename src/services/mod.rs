@@ -1,7 +1,20 @@
+pub mod attachment_service;
 pub mod auth_service;
 pub mod board_service;
+pub mod board_transfer_service;
+pub mod changelog_service;
 pub mod comment_service;
+pub mod counter_service;
+pub mod event_service;
+pub mod feed_service;
+pub mod gdpr_service;
+pub mod health_service;
+pub mod image_service;
+pub mod job_service;
+pub mod mention_service;
+pub mod notification_service;
 pub mod org_service;
 pub mod post_service;
+pub mod reaction_service;
 pub mod tag_service;
 pub mod vote_service;
@@ -0,0 +1,63 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::notification::NotificationResponse;
+
+pub async fn create_notification(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    actor_id: Option<Uuid>,
+    kind: &str,
+    post_id: Option<Uuid>,
+    comment_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO notifications (user_id, actor_id, kind, post_id, comment_id)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(user_id)
+    .bind(actor_id)
+    .bind(kind)
+    .bind(post_id)
+    .bind(comment_id)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_notifications(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<NotificationResponse>, AppError> {
+    let notifications = sqlx::query_as(
+        r#"
+        SELECT n.id, n.kind, n.actor_id, u.name as actor_name,
+               n.post_id, p.title as post_title, n.comment_id,
+               n.changelog_id, c.title as changelog_title,
+               n.read_at IS NOT NULL as read, n.created_at
+        FROM notifications n
+        LEFT JOIN users u ON u.id = n.actor_id
+        LEFT JOIN posts p ON p.id = n.post_id
+        LEFT JOIN changelog_entries c ON c.id = n.changelog_id
+        WHERE n.user_id = $1
+        ORDER BY n.created_at DESC
+        LIMIT 100
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(notifications)
+}
+
+pub async fn mark_all_read(pool: &PgPool, user_id: Uuid) -> Result<(), AppError> {
+    sqlx::query("UPDATE notifications SET read_at = now() WHERE user_id = $1 AND read_at IS NULL")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
@@ -2,7 +2,9 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::errors::AppError;
-use crate::models::organization::{OrgMember, Organization, OrganizationResponse};
+use crate::models::organization::{
+    MemberSuggestion, OrgMember, Organization, OrganizationResponse,
+};
 use crate::utils::slugify::create_slug;
 
 pub async fn create_org(
@@ -41,6 +43,48 @@ pub async fn create_org(
     Ok(org)
 }
 
+/// Look up an organization by id or slug.
+pub async fn find_org(pool: &PgPool, id_or_slug: &str) -> Result<Organization, AppError> {
+    let org = match id_or_slug.parse::<Uuid>() {
+        Ok(id) => {
+            sqlx::query_as("SELECT * FROM organizations WHERE id = $1")
+                .bind(id)
+                .fetch_optional(pool)
+                .await?
+        }
+        Err(_) => {
+            sqlx::query_as("SELECT * FROM organizations WHERE slug = $1")
+                .bind(id_or_slug)
+                .fetch_optional(pool)
+                .await?
+        }
+    };
+    org.ok_or_else(|| AppError::NotFound(format!("Organization {id_or_slug} not found")))
+}
+
+/// Add a user to an organization with `role`, or change the role of an
+/// existing member.
+pub async fn set_member_role(
+    pool: &PgPool,
+    org_id: Uuid,
+    user_id: Uuid,
+    role: &str,
+) -> Result<OrgMember, AppError> {
+    let member = sqlx::query_as(
+        r#"
+        INSERT INTO org_members (org_id, user_id, role) VALUES ($1, $2, $3)
+        ON CONFLICT (org_id, user_id) DO UPDATE SET role = EXCLUDED.role
+        RETURNING *
+        "#,
+    )
+    .bind(org_id)
+    .bind(user_id)
+    .bind(role)
+    .fetch_one(pool)
+    .await?;
+    Ok(member)
+}
+
 pub async fn get_user_orgs(
     pool: &PgPool,
     user_id: Uuid,
@@ -63,6 +107,9 @@ pub async fn get_user_orgs(
 }
 
 pub async fn get_member(pool: &PgPool, org_id: Uuid, user_id: Uuid) -> Result<OrgMember, AppError> {
+    // Every org-scoped request checks membership, so this tags the request span
+    tracing::Span::current().record("org_id", tracing::field::display(org_id));
+
     sqlx::query_as("SELECT * FROM org_members WHERE org_id = $1 AND user_id = $2")
         .bind(org_id)
         .bind(user_id)
@@ -82,3 +129,38 @@ pub async fn require_org_admin(pool: &PgPool, org_id: Uuid, user_id: Uuid) -> Re
     }
     Ok(())
 }
+
+/// Members whose name starts with `query` (case-insensitive), for @mention autocomplete.
+pub async fn search_members(
+    pool: &PgPool,
+    org_id: Uuid,
+    query: &str,
+    limit: i64,
+) -> Result<Vec<MemberSuggestion>, AppError> {
+    // Escape LIKE wildcards so the query is matched literally
+    let pattern = format!(
+        "{}%",
+        query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+
+    let members = sqlx::query_as(
+        r#"
+        SELECT u.id, u.name, u.avatar_url
+        FROM users u
+        JOIN org_members m ON m.user_id = u.id
+        WHERE m.org_id = $1 AND u.name ILIKE $2
+        ORDER BY u.name ASC
+        LIMIT $3
+        "#,
+    )
+    .bind(org_id)
+    .bind(pattern)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(members)
+}
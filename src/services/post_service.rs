@@ -2,16 +2,22 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::errors::AppError;
+use crate::models::event::{BoardEvent, BoardEventKind};
 use crate::models::post::{Post, PostDetailRow, PostListRow, VALID_STATUSES};
+use crate::services::{event_service, mention_service};
+use crate::telemetry;
 
 pub async fn create_post(
     pool: &PgPool,
+    org_id: Uuid,
     board_id: Uuid,
     author_id: Uuid,
     title: &str,
     description: Option<&str>,
 ) -> Result<Post, AppError> {
-    sqlx::query_as(
+    let mut tx = pool.begin().await?;
+
+    let post: Post = sqlx::query_as(
         r#"
         INSERT INTO posts (board_id, author_id, title, description, status)
         VALUES ($1, $2, $3, $4, 'open')
@@ -22,9 +28,30 @@ pub async fn create_post(
     .bind(author_id)
     .bind(title)
     .bind(description)
-    .fetch_one(pool)
-    .await
-    .map_err(AppError::DatabaseError)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if let Some(description) = description {
+        mention_service::record_mentions(&mut tx, org_id, post.id, None, author_id, description)
+            .await?;
+    }
+
+    event_service::publish(
+        &mut tx,
+        &BoardEvent {
+            board_id,
+            post_id: post.id,
+            kind: BoardEventKind::PostCreated {
+                title: post.title.clone(),
+            },
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+    metrics::counter!(telemetry::POSTS_CREATED).increment(1);
+
+    Ok(post)
 }
 
 pub async fn get_posts(
@@ -33,6 +60,50 @@ pub async fn get_posts(
     user_id: Uuid,
     sort: &str,
     status_filter: Option<&str>,
+) -> Result<Vec<PostListRow>, AppError> {
+    query_posts(pool, board_id, user_id, sort, status_filter, None, 0).await
+}
+
+/// One page of `get_posts`, plus the number of posts on all pages.
+pub async fn get_posts_page(
+    pool: &PgPool,
+    board_id: Uuid,
+    user_id: Uuid,
+    sort: &str,
+    status_filter: Option<&str>,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<PostListRow>, i64), AppError> {
+    let rows = query_posts(
+        pool,
+        board_id,
+        user_id,
+        sort,
+        status_filter,
+        Some(limit),
+        offset,
+    )
+    .await?;
+
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM posts WHERE board_id = $1 AND ($2::text IS NULL OR status = $2)",
+    )
+    .bind(board_id)
+    .bind(status_filter)
+    .fetch_one(pool)
+    .await?;
+
+    Ok((rows, total))
+}
+
+async fn query_posts(
+    pool: &PgPool,
+    board_id: Uuid,
+    user_id: Uuid,
+    sort: &str,
+    status_filter: Option<&str>,
+    limit: Option<i64>,
+    offset: i64,
 ) -> Result<Vec<PostListRow>, AppError> {
     let order_clause = match sort {
         "recent" => "p.created_at DESC",
@@ -40,47 +111,28 @@ pub async fn get_posts(
         _ => "p.vote_count DESC, p.created_at DESC", // "votes" default
     };
 
-    // Build query dynamically based on filter
-    let query = if status_filter.is_some() {
-        format!(
-            r#"
-            SELECT p.id, p.title, p.description, p.status, p.vote_count, p.comment_count,
-                   p.pinned, u.name as author_name, p.created_at,
-                   EXISTS(SELECT 1 FROM votes v WHERE v.post_id = p.id AND v.user_id = $2) as has_voted
-            FROM posts p
-            LEFT JOIN users u ON u.id = p.author_id
-            WHERE p.board_id = $1 AND p.status = $3
-            ORDER BY p.pinned DESC, {order_clause}
-            "#
-        )
-    } else {
-        format!(
-            r#"
-            SELECT p.id, p.title, p.description, p.status, p.vote_count, p.comment_count,
-                   p.pinned, u.name as author_name, p.created_at,
-                   EXISTS(SELECT 1 FROM votes v WHERE v.post_id = p.id AND v.user_id = $2) as has_voted
-            FROM posts p
-            LEFT JOIN users u ON u.id = p.author_id
-            WHERE p.board_id = $1
-            ORDER BY p.pinned DESC, {order_clause}
-            "#
-        )
-    };
+    // A NULL limit returns all rows
+    let query = format!(
+        r#"
+        SELECT p.id, p.title, p.description, p.status, p.vote_count, p.comment_count,
+               p.pinned, u.name as author_name, p.created_at,
+               EXISTS(SELECT 1 FROM votes v WHERE v.post_id = p.id AND v.user_id = $2) as has_voted
+        FROM posts p
+        LEFT JOIN users u ON u.id = p.author_id
+        WHERE p.board_id = $1 AND ($3::text IS NULL OR p.status = $3)
+        ORDER BY p.pinned DESC, {order_clause}, p.id
+        LIMIT $4 OFFSET $5
+        "#
+    );
 
-    let rows = if let Some(status) = status_filter {
-        sqlx::query_as::<_, PostListRow>(&query)
-            .bind(board_id)
-            .bind(user_id)
-            .bind(status)
-            .fetch_all(pool)
-            .await?
-    } else {
-        sqlx::query_as::<_, PostListRow>(&query)
-            .bind(board_id)
-            .bind(user_id)
-            .fetch_all(pool)
-            .await?
-    };
+    let rows = sqlx::query_as::<_, PostListRow>(&query)
+        .bind(board_id)
+        .bind(user_id)
+        .bind(status_filter)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
 
     Ok(rows)
 }
@@ -118,19 +170,37 @@ pub async fn get_post_raw(pool: &PgPool, post_id: Uuid) -> Result<Post, AppError
 
 pub async fn update_post(
     pool: &PgPool,
+    org_id: Uuid,
     post_id: Uuid,
+    editor_id: Uuid,
     title: &str,
     description: Option<&str>,
 ) -> Result<Post, AppError> {
-    sqlx::query_as(
+    let mut tx = pool.begin().await?;
+
+    let post: Post = sqlx::query_as(
         "UPDATE posts SET title = $1, description = $2, updated_at = now() WHERE id = $3 RETURNING *",
     )
     .bind(title)
     .bind(description)
     .bind(post_id)
-    .fetch_one(pool)
-    .await
-    .map_err(AppError::DatabaseError)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Offsets change with the text, so mentions are re-resolved on every edit
+    mention_service::record_mentions(
+        &mut tx,
+        org_id,
+        post_id,
+        None,
+        editor_id,
+        description.unwrap_or_default(),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(post)
 }
 
 pub async fn delete_post(pool: &PgPool, post_id: Uuid) -> Result<(), AppError> {
@@ -153,10 +223,28 @@ pub async fn update_status(pool: &PgPool, post_id: Uuid, status: &str) -> Result
         )));
     }
 
-    sqlx::query_as("UPDATE posts SET status = $1, updated_at = now() WHERE id = $2 RETURNING *")
-        .bind(status)
-        .bind(post_id)
-        .fetch_one(pool)
-        .await
-        .map_err(AppError::DatabaseError)
+    let mut tx = pool.begin().await?;
+
+    let post: Post = sqlx::query_as(
+        "UPDATE posts SET status = $1, updated_at = now() WHERE id = $2 RETURNING *",
+    )
+    .bind(status)
+    .bind(post_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    event_service::publish(
+        &mut tx,
+        &BoardEvent {
+            board_id: post.board_id,
+            post_id,
+            kind: BoardEventKind::StatusChanged {
+                status: status.to_string(),
+            },
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+    Ok(post)
 }
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::reaction::{
+    ALLOWED_EMOJIS, Reaction, ReactionResult, ReactionSummary, ReactionTarget,
+};
+
+pub async fn toggle_reaction(
+    pool: &PgPool,
+    target: ReactionTarget,
+    target_id: Uuid,
+    user_id: Uuid,
+    emoji: &str,
+) -> Result<ReactionResult, AppError> {
+    if !ALLOWED_EMOJIS.contains(&emoji) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid emoji. Must be one of: {}",
+            ALLOWED_EMOJIS.join(" ")
+        )));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    // Lock the target row to serialize concurrent reaction toggles
+    let lock_query = match target {
+        ReactionTarget::Post => "SELECT 1 FROM posts WHERE id = $1 FOR UPDATE",
+        ReactionTarget::Comment => "SELECT 1 FROM comments WHERE id = $1 FOR UPDATE",
+    };
+    sqlx::query(lock_query)
+        .bind(target_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| match target {
+            ReactionTarget::Post => AppError::NotFound("Post not found".to_string()),
+            ReactionTarget::Comment => AppError::NotFound("Comment not found".to_string()),
+        })?;
+
+    // Check if reaction already exists
+    let existing: Option<Reaction> = sqlx::query_as(
+        "SELECT * FROM reactions WHERE target_type = $1 AND target_id = $2 AND user_id = $3 AND emoji = $4",
+    )
+    .bind(target.as_str())
+    .bind(target_id)
+    .bind(user_id)
+    .bind(emoji)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let reacted = if existing.is_some() {
+        sqlx::query(
+            "DELETE FROM reactions WHERE target_type = $1 AND target_id = $2 AND user_id = $3 AND emoji = $4",
+        )
+        .bind(target.as_str())
+        .bind(target_id)
+        .bind(user_id)
+        .bind(emoji)
+        .execute(&mut *tx)
+        .await?;
+        false
+    } else {
+        sqlx::query(
+            "INSERT INTO reactions (target_type, target_id, user_id, emoji) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(target.as_str())
+        .bind(target_id)
+        .bind(user_id)
+        .bind(emoji)
+        .execute(&mut *tx)
+        .await?;
+        true
+    };
+
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM reactions WHERE target_type = $1 AND target_id = $2 AND emoji = $3",
+    )
+    .bind(target.as_str())
+    .bind(target_id)
+    .bind(emoji)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(ReactionResult {
+        emoji: emoji.to_string(),
+        reacted,
+        count,
+    })
+}
+
+pub async fn get_reactions(
+    pool: &PgPool,
+    target: ReactionTarget,
+    target_id: Uuid,
+    user_id: Uuid,
+) -> Result<Vec<ReactionSummary>, AppError> {
+    let mut map = get_reactions_for_target_ids(pool, target, &[target_id], user_id).await?;
+    Ok(map.remove(&target_id).unwrap_or_default())
+}
+
+/// Batch-fetch aggregated reactions for multiple targets in a single query.
+/// Returns a HashMap mapping target_id -> Vec<ReactionSummary>.
+pub async fn get_reactions_for_target_ids(
+    pool: &PgPool,
+    target: ReactionTarget,
+    target_ids: &[Uuid],
+    user_id: Uuid,
+) -> Result<HashMap<Uuid, Vec<ReactionSummary>>, AppError> {
+    #[derive(sqlx::FromRow)]
+    struct ReactionCountRow {
+        target_id: Uuid,
+        emoji: String,
+        count: i64,
+        reacted: bool,
+    }
+
+    let rows: Vec<ReactionCountRow> = sqlx::query_as(
+        r#"
+        SELECT r.target_id, r.emoji,
+               COUNT(*)::bigint as count,
+               BOOL_OR(r.user_id = $3) as reacted
+        FROM reactions r
+        WHERE r.target_type = $1 AND r.target_id = ANY($2)
+        GROUP BY r.target_id, r.emoji
+        ORDER BY MIN(r.created_at) ASC
+        "#,
+    )
+    .bind(target.as_str())
+    .bind(target_ids)
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut map: HashMap<Uuid, Vec<ReactionSummary>> = HashMap::new();
+    for row in rows {
+        map.entry(row.target_id).or_default().push(ReactionSummary {
+            emoji: row.emoji,
+            count: row.count,
+            reacted: row.reacted,
+        });
+    }
+    Ok(map)
+}
@@ -1,4 +1,4 @@
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
 use crate::errors::AppError;
@@ -26,6 +26,33 @@ pub async fn create_tag(
     })
 }
 
+/// Look up a board tag by exact name, creating it with the default color if
+/// missing. Returns the tag and whether it was created. Runs inside the
+/// caller's transaction.
+pub async fn find_or_create_tag(
+    conn: &mut PgConnection,
+    board_id: Uuid,
+    name: &str,
+) -> Result<(Tag, bool), AppError> {
+    let created: Option<Tag> = sqlx::query_as(
+        "INSERT INTO tags (board_id, name) VALUES ($1, $2) ON CONFLICT (board_id, name) DO NOTHING RETURNING *",
+    )
+    .bind(board_id)
+    .bind(name)
+    .fetch_optional(&mut *conn)
+    .await?;
+    if let Some(tag) = created {
+        return Ok((tag, true));
+    }
+
+    let tag = sqlx::query_as("SELECT * FROM tags WHERE board_id = $1 AND name = $2")
+        .bind(board_id)
+        .bind(name)
+        .fetch_one(&mut *conn)
+        .await?;
+    Ok((tag, false))
+}
+
 pub async fn get_tags(pool: &PgPool, board_id: Uuid) -> Result<Vec<Tag>, AppError> {
     let tags = sqlx::query_as("SELECT * FROM tags WHERE board_id = $1 ORDER BY name ASC")
         .bind(board_id)
@@ -2,7 +2,10 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::errors::AppError;
+use crate::models::event::{BoardEvent, BoardEventKind};
 use crate::models::vote::{Vote, VoteResult};
+use crate::services::event_service;
+use crate::telemetry;
 
 pub async fn toggle_vote(
     pool: &PgPool,
@@ -12,7 +15,7 @@ pub async fn toggle_vote(
     let mut tx = pool.begin().await?;
 
     // Lock the parent post row to serialize concurrent vote toggles
-    sqlx::query("SELECT 1 FROM posts WHERE id = $1 FOR UPDATE")
+    let board_id: Uuid = sqlx::query_scalar("SELECT board_id FROM posts WHERE id = $1 FOR UPDATE")
         .bind(post_id)
         .fetch_optional(&mut *tx)
         .await?
@@ -26,7 +29,7 @@ pub async fn toggle_vote(
             .fetch_optional(&mut *tx)
             .await?;
 
-    if existing.is_some() {
+    let result = if existing.is_some() {
         // Remove vote and decrement count
         sqlx::query("DELETE FROM votes WHERE post_id = $1 AND user_id = $2")
             .bind(post_id)
@@ -41,12 +44,10 @@ pub async fn toggle_vote(
         .fetch_one(&mut *tx)
         .await?;
 
-        tx.commit().await?;
-
-        Ok(VoteResult {
+        VoteResult {
             voted: false,
             vote_count: row.0,
-        })
+        }
     } else {
         // Add vote and increment count
         sqlx::query("INSERT INTO votes (post_id, user_id) VALUES ($1, $2)")
@@ -62,11 +63,27 @@ pub async fn toggle_vote(
         .fetch_one(&mut *tx)
         .await?;
 
-        tx.commit().await?;
-
-        Ok(VoteResult {
+        VoteResult {
             voted: true,
             vote_count: row.0,
-        })
+        }
+    };
+
+    event_service::publish(
+        &mut tx,
+        &BoardEvent {
+            board_id,
+            post_id,
+            kind: BoardEventKind::VoteCountChanged {
+                vote_count: result.vote_count,
+            },
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+    if result.voted {
+        metrics::counter!(telemetry::VOTES_CAST).increment(1);
     }
+    Ok(result)
 }
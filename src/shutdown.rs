@@ -0,0 +1,41 @@
+//! Graceful shutdown. On SIGTERM or SIGINT the server first fails its
+//! readiness check for `SHUTDOWN_DELAY_SECS`, so load balancers stop sending
+//! it traffic, then stops accepting connections and gives in-flight requests
+//! and running jobs up to `SHUTDOWN_TIMEOUT_SECS` to finish.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once shutdown has begun; `/health/ready` fails from then on.
+#[derive(Clone, Default)]
+pub struct Drain(Arc<AtomicBool>);
+
+impl Drain {
+    pub fn start(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Resolves on the first SIGTERM or SIGINT (Ctrl-C).
+pub async fn signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut term = signal(SignalKind::terminate()).expect("Failed to listen for SIGTERM");
+        tokio::select! {
+            _ = term.recv() => tracing::info!("Received SIGTERM"),
+            _ = tokio::signal::ctrl_c() => tracing::info!("Received SIGINT"),
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        tracing::info!("Received Ctrl-C");
+    }
+}
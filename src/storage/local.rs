@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+
+use super::{Storage, StorageError, validate_key};
+
+/// Stores objects as files under a base directory.
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalStorage { root: root.into() }
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf, StorageError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn put(&self, key: &str, data: &[u8], _content_type: &str) -> Result<(), StorageError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, data).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        let path = self.path_for(key)?;
+        tokio::fs::read(&path).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => StorageError::NotFound(key.to_string()),
+            _ => StorageError::Io(e),
+        })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(StorageError::Io(e)),
+        }
+    }
+
+    /// A missing file says nothing about whether uploads can be saved, so
+    /// write and remove a probe file instead. Creates the root like the
+    /// first upload would.
+    async fn check(&self) -> Result<(), StorageError> {
+        tokio::fs::create_dir_all(&self.root).await?;
+        let probe = self
+            .root
+            .join(format!(".health-check-{}", uuid::Uuid::new_v4()));
+        tokio::fs::write(&probe, b"").await?;
+        tokio::fs::remove_file(&probe).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_storage() -> LocalStorage {
+        LocalStorage::new(
+            std::env::temp_dir().join(format!("rivvo-local-{}", uuid::Uuid::new_v4())),
+        )
+    }
+
+    #[tokio::test]
+    async fn put_get_delete_roundtrip() {
+        let storage = temp_storage();
+        storage
+            .put("org/file.txt", b"hello", "text/plain")
+            .await
+            .unwrap();
+        assert_eq!(storage.get("org/file.txt").await.unwrap(), b"hello");
+
+        storage.delete("org/file.txt").await.unwrap();
+        assert!(matches!(
+            storage.get("org/file.txt").await,
+            Err(StorageError::NotFound(_))
+        ));
+        // Deleting again is fine
+        storage.delete("org/file.txt").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn check_requires_a_writable_root() {
+        let storage = temp_storage();
+        storage.check().await.unwrap();
+        assert_eq!(std::fs::read_dir(&storage.root).unwrap().count(), 0);
+
+        // A root that is not a directory can't hold uploads
+        let file = std::env::temp_dir().join(format!("rivvo-local-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&file, b"").unwrap();
+        assert!(LocalStorage::new(&file).check().await.is_err());
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_path_traversal() {
+        let storage = temp_storage();
+        for key in ["../etc/passwd", "/abs", "a//b", "a/./b", ""] {
+            assert!(matches!(
+                storage.put(key, b"x", "text/plain").await,
+                Err(StorageError::InvalidKey(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Pluggable blob storage for uploaded files.
+//!
+//! The backend is chosen by `storage.backend` (`STORAGE_BACKEND`): `local`
+//! (default) writes under `storage.dir`; `s3` (requires the `s3` feature)
+//! talks to any S3-compatible endpoint configured in `storage.s3`.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::config::Config;
+use crate::errors::AppError;
+
+pub mod local;
+#[cfg(feature = "s3")]
+pub mod s3;
+
+pub use local::LocalStorage;
+
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("Object not found: {0}")]
+    NotFound(String),
+    #[error("Invalid storage key: {0}")]
+    InvalidKey(String),
+    #[error("Storage I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Storage backend error: {0}")]
+    Backend(String),
+}
+
+impl From<StorageError> for AppError {
+    fn from(e: StorageError) -> Self {
+        match e {
+            StorageError::NotFound(_) => AppError::NotFound("File not found".to_string()),
+            _ => AppError::InternalError(e.to_string()),
+        }
+    }
+}
+
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn put(&self, key: &str, data: &[u8], content_type: &str) -> Result<(), StorageError>;
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError>;
+    /// Deleting a missing object is not an error.
+    async fn delete(&self, key: &str) -> Result<(), StorageError>;
+
+    /// Confirm the backend is reachable, for the readiness check. Looks up an
+    /// object that never exists, so it has no side effects; backends where
+    /// that proves nothing (like `LocalStorage`) override it.
+    async fn check(&self) -> Result<(), StorageError> {
+        match self.get(HEALTH_CHECK_KEY).await {
+            Ok(_) | Err(StorageError::NotFound(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+const HEALTH_CHECK_KEY: &str = "health-check/absent";
+
+/// Build the storage backend selected in `config`.
+pub fn from_config(config: &Config) -> Result<Arc<dyn Storage>, String> {
+    match config.storage.backend.as_str() {
+        "local" => Ok(Arc::new(LocalStorage::new(&config.storage.dir))),
+        #[cfg(feature = "s3")]
+        "s3" => Ok(Arc::new(s3::S3Storage::new(config.storage.s3.clone())?)),
+        #[cfg(not(feature = "s3"))]
+        "s3" => Err("STORAGE_BACKEND=s3 requires building with --features s3".to_string()),
+        other => Err(format!("Unknown STORAGE_BACKEND: {other}")),
+    }
+}
+
+/// Best-effort removal of objects whose database rows are already gone.
+pub async fn delete_all(storage: &dyn Storage, keys: &[String]) {
+    for key in keys {
+        if let Err(e) = storage.delete(key).await {
+            tracing::warn!("Failed to delete stored object {key}: {e}");
+        }
+    }
+}
+
+/// Keys are `/`-separated relative paths; reject anything that could escape
+/// the storage root.
+fn validate_key(key: &str) -> Result<(), StorageError> {
+    let valid = !key.is_empty()
+        && !key.starts_with('/')
+        && key
+            .split('/')
+            .all(|part| !part.is_empty() && part != "." && part != "..")
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'));
+    if valid {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidKey(key.to_string()))
+    }
+}
@@ -0,0 +1,305 @@
+//! Minimal S3-compatible backend (AWS S3, MinIO, R2, ...).
+//!
+//! Uses path-style URLs (`{endpoint}/{bucket}/{key}`) and AWS Signature V4
+//! with a signed payload hash, so it works against most self-hosted stores.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use super::{Storage, StorageError, validate_key};
+use crate::config::S3Config;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub struct S3Storage {
+    config: S3Config,
+    client: reqwest::Client,
+}
+
+impl S3Storage {
+    pub fn new(config: S3Config) -> Result<Self, String> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client for S3: {e}"))?;
+        Ok(S3Storage { config, client })
+    }
+
+    async fn send(
+        &self,
+        method: reqwest::Method,
+        key: &str,
+        body: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> Result<reqwest::Response, StorageError> {
+        validate_key(key)?;
+
+        let path = format!("/{}/{}", self.config.bucket, key);
+        let url = format!("{}{path}", self.config.endpoint);
+        let host = host_from_endpoint(&self.config.endpoint)?;
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+        let authorization = sign(
+            &self.config,
+            method.as_str(),
+            &path,
+            &host,
+            &amz_date,
+            &payload_hash,
+        );
+
+        let mut request = self
+            .client
+            .request(method, &url)
+            .header("x-amz-date", &amz_date)
+            .header("x-amz-content-sha256", &payload_hash)
+            .header("authorization", authorization);
+        if let Some(content_type) = content_type {
+            request = request.header("content-type", content_type);
+        }
+
+        request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| StorageError::Backend(format!("S3 request failed: {e}")))
+    }
+}
+
+#[async_trait]
+impl Storage for S3Storage {
+    async fn put(&self, key: &str, data: &[u8], content_type: &str) -> Result<(), StorageError> {
+        let response = self
+            .send(reqwest::Method::PUT, key, data.to_vec(), Some(content_type))
+            .await?;
+        if !response.status().is_success() {
+            return Err(StorageError::Backend(format!(
+                "S3 PUT {key} failed: HTTP {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        let response = self
+            .send(reqwest::Method::GET, key, Vec::new(), None)
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(StorageError::NotFound(key.to_string()));
+        }
+        if !response.status().is_success() {
+            return Err(StorageError::Backend(format!(
+                "S3 GET {key} failed: HTTP {}",
+                response.status()
+            )));
+        }
+        response
+            .bytes()
+            .await
+            .map(|b| b.to_vec())
+            .map_err(|e| StorageError::Backend(format!("S3 GET {key} failed: {e}")))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let response = self
+            .send(reqwest::Method::DELETE, key, Vec::new(), None)
+            .await?;
+        // S3 answers 204 for deletes, including missing keys
+        if !response.status().is_success() && response.status() != reqwest::StatusCode::NOT_FOUND {
+            return Err(StorageError::Backend(format!(
+                "S3 DELETE {key} failed: HTTP {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn host_from_endpoint(endpoint: &str) -> Result<String, StorageError> {
+    endpoint
+        .split_once("://")
+        .map(|(_, rest)| rest.split('/').next().unwrap_or(rest).to_string())
+        .filter(|host| !host.is_empty())
+        .ok_or_else(|| StorageError::Backend(format!("Invalid S3 endpoint: {endpoint}")))
+}
+
+/// Build the SigV4 `Authorization` header. Keys are restricted by
+/// `validate_key` to characters that need no URI encoding.
+fn sign(
+    config: &S3Config,
+    method: &str,
+    path: &str,
+    host: &str,
+    amz_date: &str,
+    payload_hash: &str,
+) -> String {
+    let date_stamp = &amz_date[..8];
+    let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+    let canonical_request = format!(
+        "{method}\n{path}\n\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\n{signed_headers}\n{payload_hash}"
+    );
+    let scope = format!("{date_stamp}/{}/s3/aws4_request", config.region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let k_date = hmac(
+        format!("AWS4{}", config.secret_key).as_bytes(),
+        date_stamp.as_bytes(),
+    );
+    let k_region = hmac(&k_date, config.region.as_bytes());
+    let k_service = hmac(&k_region, b"s3");
+    let k_signing = hmac(&k_service, b"aws4_request");
+    let signature = hex::encode(hmac(&k_signing, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+        config.access_key
+    )
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use actix_web::{App, HttpRequest, HttpResponse, HttpServer, web};
+
+    use super::*;
+
+    type Objects = web::Data<Mutex<HashMap<String, Vec<u8>>>>;
+
+    /// Stand-in for an S3 server: stores objects in memory and only checks
+    /// that requests carry a SigV4 authorization header.
+    async fn object_handler(req: HttpRequest, body: web::Bytes, objects: Objects) -> HttpResponse {
+        let signed = req
+            .headers()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("AWS4-HMAC-SHA256 Credential=test-access/"));
+        if !signed {
+            return HttpResponse::Forbidden().finish();
+        }
+
+        let path = req.path().to_string();
+        let mut objects = objects.lock().unwrap();
+        match *req.method() {
+            actix_web::http::Method::PUT => {
+                objects.insert(path, body.to_vec());
+                HttpResponse::Ok().finish()
+            }
+            actix_web::http::Method::GET => match objects.get(&path) {
+                Some(data) => HttpResponse::Ok().body(data.clone()),
+                None => HttpResponse::NotFound().finish(),
+            },
+            actix_web::http::Method::DELETE => {
+                objects.remove(&path);
+                HttpResponse::NoContent().finish()
+            }
+            _ => HttpResponse::MethodNotAllowed().finish(),
+        }
+    }
+
+    fn start_mock_s3() -> String {
+        let objects: Objects = web::Data::new(Mutex::new(HashMap::new()));
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(objects.clone())
+                .default_service(web::to(object_handler))
+        })
+        .workers(1)
+        .bind("127.0.0.1:0")
+        .unwrap();
+        let addr = server.addrs()[0];
+        tokio::spawn(server.run());
+        format!("http://{addr}")
+    }
+
+    fn test_config(endpoint: String) -> S3Config {
+        S3Config {
+            endpoint,
+            bucket: "rivvo".to_string(),
+            region: "us-east-1".to_string(),
+            access_key: "test-access".to_string(),
+            secret_key: "test-secret".to_string(),
+        }
+    }
+
+    #[actix_web::test]
+    async fn roundtrip_against_mock_server() {
+        let storage = S3Storage::new(test_config(start_mock_s3())).unwrap();
+
+        storage
+            .put("org/file.png", b"\x89PNG data", "image/png")
+            .await
+            .unwrap();
+        assert_eq!(storage.get("org/file.png").await.unwrap(), b"\x89PNG data");
+
+        storage.delete("org/file.png").await.unwrap();
+        assert!(matches!(
+            storage.get("org/file.png").await,
+            Err(StorageError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn signature_is_deterministic_and_scoped() {
+        let config = test_config("http://localhost:9000".to_string());
+        let a = sign(
+            &config,
+            "GET",
+            "/rivvo/k",
+            "localhost:9000",
+            "20260101T000000Z",
+            "abc",
+        );
+        let b = sign(
+            &config,
+            "GET",
+            "/rivvo/k",
+            "localhost:9000",
+            "20260101T000000Z",
+            "abc",
+        );
+        let c = sign(
+            &config,
+            "PUT",
+            "/rivvo/k",
+            "localhost:9000",
+            "20260101T000000Z",
+            "abc",
+        );
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a.starts_with(
+            "AWS4-HMAC-SHA256 Credential=test-access/20260101/us-east-1/s3/aws4_request, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature="
+        ));
+    }
+
+    #[test]
+    fn host_parsing() {
+        assert_eq!(
+            host_from_endpoint("http://localhost:9000").unwrap(),
+            "localhost:9000"
+        );
+        assert_eq!(
+            host_from_endpoint("https://s3.example.com/").unwrap(),
+            "s3.example.com"
+        );
+        assert!(host_from_endpoint("nonsense").is_err());
+    }
+}
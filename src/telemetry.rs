@@ -0,0 +1,202 @@
+//! Logs, traces and metrics.
+//!
+//! Logs and traces go through `tracing`: `init_tracing` installs the
+//! subscriber, which also exports spans over OTLP when the `otel` feature is
+//! enabled and `OTEL_EXPORTER_OTLP_ENDPOINT` is set.
+//!
+//! Prometheus metrics are recorded through the `metrics` macros using the
+//! names below; `GET /metrics` renders them with the recorder installed by
+//! `metrics_handle`.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use actix_web::http::header::HeaderMap;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use tracing::Span;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+use crate::config::Config;
+
+// ── Metrics ────────────────────────────────────────────────────────
+
+pub const HTTP_REQUESTS: &str = "http_requests_total";
+pub const HTTP_REQUEST_DURATION: &str = "http_request_duration_seconds";
+pub const DB_POOL_CONNECTIONS: &str = "db_pool_connections";
+pub const DB_POOL_MAX_CONNECTIONS: &str = "db_pool_max_connections";
+pub const JOBS: &str = "rivvo_jobs";
+pub const POSTS_CREATED: &str = "rivvo_posts_created_total";
+pub const VOTES_CAST: &str = "rivvo_votes_cast_total";
+pub const COMMENTS_CREATED: &str = "rivvo_comments_created_total";
+
+const DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// How often histogram samples are folded into their buckets. Without
+/// upkeep they pile up until the next scrape.
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Installs the global recorder and starts its upkeep on first call. Metrics
+/// recorded before that are dropped.
+pub fn metrics_handle() -> PrometheusHandle {
+    HANDLE
+        .get_or_init(|| {
+            let handle = PrometheusBuilder::new()
+                .set_buckets_for_metric(
+                    Matcher::Full(HTTP_REQUEST_DURATION.to_string()),
+                    DURATION_BUCKETS,
+                )
+                .expect("Invalid histogram buckets")
+                .install_recorder()
+                .expect("Failed to install metrics recorder");
+
+            // A plain thread, so upkeep outlives whichever runtime asked first
+            let upkeep = handle.clone();
+            std::thread::Builder::new()
+                .name("metrics-upkeep".to_string())
+                .spawn(move || {
+                    loop {
+                        std::thread::sleep(UPKEEP_INTERVAL);
+                        upkeep.run_upkeep();
+                    }
+                })
+                .expect("Failed to start metrics upkeep");
+            handle
+        })
+        .clone()
+}
+
+// ── Tracing ────────────────────────────────────────────────────────
+
+/// Keeps trace export running; dropping it sends spans still buffered.
+#[must_use]
+pub struct TracingGuard {
+    #[cfg(feature = "otel")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+/// Installs the global subscriber: logs to stdout filtered by `RUST_LOG` and,
+/// if configured, OTLP span export. The same filter applies to both.
+pub fn init_tracing(config: &Config) -> TracingGuard {
+    let subscriber = tracing_subscriber::registry()
+        .with(EnvFilter::from_default_env())
+        .with(tracing_subscriber::fmt::layer());
+
+    #[cfg(feature = "otel")]
+    {
+        use opentelemetry::trace::TracerProvider;
+
+        let provider =
+            config.telemetry.otel_endpoint.as_deref().map(|endpoint| {
+                otel::tracer_provider(endpoint, &config.telemetry.otel_service_name)
+            });
+        let layer = provider
+            .as_ref()
+            .map(|p| tracing_opentelemetry::layer().with_tracer(p.tracer("rivvo")));
+        subscriber.with(layer).init();
+        TracingGuard { provider }
+    }
+
+    #[cfg(not(feature = "otel"))]
+    {
+        subscriber.init();
+        if config.telemetry.otel_endpoint.is_some() {
+            tracing::warn!(
+                "OTEL_EXPORTER_OTLP_ENDPOINT is set, but this build lacks the otel feature"
+            );
+        }
+        TracingGuard {}
+    }
+}
+
+#[cfg(feature = "otel")]
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take()
+            && let Err(e) = provider.shutdown()
+        {
+            eprintln!("Failed to flush traces: {e}");
+        }
+    }
+}
+
+/// Makes `span` a server span and, if the request carries a W3C
+/// `traceparent`, continues that trace.
+#[cfg(feature = "otel")]
+pub fn start_request_span(span: &Span, headers: &HeaderMap) {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    span.record("otel.kind", "server");
+    let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.extract(&otel::HeaderExtractor(headers))
+    });
+    let _ = span.set_parent(parent);
+}
+
+#[cfg(not(feature = "otel"))]
+pub fn start_request_span(_span: &Span, _headers: &HeaderMap) {}
+
+/// Names `span` after the matched route, e.g. `GET /api/v1/posts/{post_id}`.
+#[cfg(feature = "otel")]
+pub fn finish_request_span(span: &Span, method: &str, route: &str, status: u16) {
+    use opentelemetry::trace::{Status, TraceContextExt};
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    // The route is only known once the span has started, so rename it directly
+    span.context()
+        .span()
+        .update_name(format!("{method} {route}"));
+    span.set_attribute("http.route", route.to_string());
+    span.set_attribute("http.response.status_code", i64::from(status));
+    if status >= 500 {
+        span.set_status(Status::error(""));
+    }
+}
+
+#[cfg(not(feature = "otel"))]
+pub fn finish_request_span(_span: &Span, _method: &str, _route: &str, _status: u16) {}
+
+#[cfg(feature = "otel")]
+mod otel {
+    use actix_web::http::header::HeaderMap;
+    use opentelemetry::propagation::Extractor;
+    use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::Resource;
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+
+    pub fn tracer_provider(endpoint: &str, service_name: &str) -> SdkTracerProvider {
+        opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+        let exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(format!("{endpoint}/v1/traces"))
+            .build()
+            .expect("Failed to build OTLP span exporter");
+        SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(
+                Resource::builder()
+                    .with_service_name(service_name.to_string())
+                    .build(),
+            )
+            .build()
+    }
+
+    pub struct HeaderExtractor<'a>(pub &'a HeaderMap);
+
+    impl Extractor for HeaderExtractor<'_> {
+        fn get(&self, key: &str) -> Option<&str> {
+            self.0.get(key).and_then(|v| v.to_str().ok())
+        }
+
+        fn keys(&self) -> Vec<&str> {
+            self.0.keys().map(|k| k.as_str()).collect()
+        }
+    }
+}
@@ -0,0 +1,41 @@
+/// Detect the MIME type of uploaded bytes from their magic numbers, ignoring
+/// whatever the client claimed. Returns `None` for anything unrecognised.
+pub fn sniff(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if data.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else if !data.is_empty() && !data.contains(&0) && std::str::from_utf8(data).is_ok() {
+        Some("text/plain")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_by_magic_bytes() {
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\nrest"), Some("image/png"));
+        assert_eq!(sniff(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("image/jpeg"));
+        assert_eq!(sniff(b"GIF89a..."), Some("image/gif"));
+        assert_eq!(sniff(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(sniff(b"%PDF-1.7"), Some("application/pdf"));
+        assert_eq!(sniff("plain text ✓".as_bytes()), Some("text/plain"));
+    }
+
+    #[test]
+    fn rejects_binary_and_empty() {
+        assert_eq!(sniff(b""), None);
+        assert_eq!(sniff(b"MZ\x90\0\x03"), None);
+        assert_eq!(sniff(&[0xC3, 0x28]), None);
+    }
+}
@@ -0,0 +1,227 @@
+//! Atom 1.0 and RSS 2.0 serialization for activity feeds.
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Atom,
+    Rss,
+}
+
+impl FeedFormat {
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext {
+            "atom" => Some(FeedFormat::Atom),
+            "rss" => Some(FeedFormat::Rss),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Feed {
+    /// Stable, globally unique id, e.g. `urn:uuid:...`
+    pub id: String,
+    pub title: String,
+    /// The page the feed mirrors
+    pub link: String,
+    /// Where the feed itself is served
+    pub self_link: String,
+    /// Newest item update; feeds without items fall back to a fixed time
+    pub updated: DateTime<Utc>,
+    pub items: Vec<FeedItem>,
+}
+
+#[derive(Debug)]
+pub struct FeedItem {
+    pub id: String,
+    pub title: String,
+    pub link: String,
+    /// Sanitized HTML
+    pub content_html: String,
+    pub author: Option<String>,
+    pub published: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+pub fn render(feed: &Feed, format: FeedFormat) -> String {
+    match format {
+        FeedFormat::Atom => to_atom(feed),
+        FeedFormat::Rss => to_rss(feed),
+    }
+}
+
+pub fn to_atom(feed: &Feed) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    let _ = writeln!(xml, "  <id>{}</id>", escape(&feed.id));
+    let _ = writeln!(xml, "  <title>{}</title>", escape(&feed.title));
+    let _ = writeln!(xml, "  <updated>{}</updated>", feed.updated.to_rfc3339());
+    let _ = writeln!(
+        xml,
+        "  <link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>",
+        escape(&feed.link)
+    );
+    let _ = writeln!(
+        xml,
+        "  <link rel=\"self\" type=\"application/atom+xml\" href=\"{}\"/>",
+        escape(&feed.self_link)
+    );
+    xml.push_str("  <generator>Rivvo</generator>\n");
+
+    for item in &feed.items {
+        xml.push_str("  <entry>\n");
+        let _ = writeln!(xml, "    <id>{}</id>", escape(&item.id));
+        let _ = writeln!(xml, "    <title>{}</title>", escape(&item.title));
+        let _ = writeln!(
+            xml,
+            "    <published>{}</published>",
+            item.published.to_rfc3339()
+        );
+        let _ = writeln!(xml, "    <updated>{}</updated>", item.updated.to_rfc3339());
+        let _ = writeln!(
+            xml,
+            "    <link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>",
+            escape(&item.link)
+        );
+        if let Some(author) = &item.author {
+            let _ = writeln!(xml, "    <author><name>{}</name></author>", escape(author));
+        }
+        let _ = writeln!(
+            xml,
+            "    <content type=\"html\">{}</content>",
+            escape(&item.content_html)
+        );
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+pub fn to_rss(feed: &Feed) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str(
+        "<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n",
+    );
+    xml.push_str("  <channel>\n");
+    let _ = writeln!(xml, "    <title>{}</title>", escape(&feed.title));
+    let _ = writeln!(xml, "    <link>{}</link>", escape(&feed.link));
+    let _ = writeln!(
+        xml,
+        "    <description>{}</description>",
+        escape(&feed.title)
+    );
+    let _ = writeln!(
+        xml,
+        "    <atom:link rel=\"self\" type=\"application/rss+xml\" href=\"{}\"/>",
+        escape(&feed.self_link)
+    );
+    let _ = writeln!(
+        xml,
+        "    <lastBuildDate>{}</lastBuildDate>",
+        feed.updated.to_rfc2822()
+    );
+    xml.push_str("    <generator>Rivvo</generator>\n");
+
+    for item in &feed.items {
+        xml.push_str("    <item>\n");
+        let _ = writeln!(xml, "      <title>{}</title>", escape(&item.title));
+        let _ = writeln!(xml, "      <link>{}</link>", escape(&item.link));
+        let _ = writeln!(
+            xml,
+            "      <guid isPermaLink=\"false\">{}</guid>",
+            escape(&item.id)
+        );
+        let _ = writeln!(
+            xml,
+            "      <pubDate>{}</pubDate>",
+            item.published.to_rfc2822()
+        );
+        if let Some(author) = &item.author {
+            // <author> must be an email address; dc:creator takes a name
+            let _ = writeln!(xml, "      <dc:creator>{}</dc:creator>", escape(author));
+        }
+        let _ = writeln!(
+            xml,
+            "      <description>{}</description>",
+            escape(&item.content_html)
+        );
+        xml.push_str("    </item>\n");
+    }
+
+    xml.push_str("  </channel>\n</rss>\n");
+    xml
+}
+
+/// Escape text for XML element content and attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than tab and newlines are not valid XML
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn sample() -> Feed {
+        let at = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        Feed {
+            id: "urn:uuid:00000000-0000-0000-0000-000000000001".to_string(),
+            title: "Acme & Co changelog".to_string(),
+            link: "https://example.com/changelog".to_string(),
+            self_link: "https://example.com/feed.atom".to_string(),
+            updated: at,
+            items: vec![FeedItem {
+                id: "urn:uuid:00000000-0000-0000-0000-000000000002".to_string(),
+                title: "<Dark> mode".to_string(),
+                link: "https://example.com/posts/2?a=1&b=2".to_string(),
+                content_html: "<p>Now \u{1}available</p>".to_string(),
+                author: Some("Ada".to_string()),
+                published: at,
+                updated: at,
+            }],
+        }
+    }
+
+    #[test]
+    fn atom_escapes_text_and_html_content() {
+        let xml = to_atom(&sample());
+        assert!(xml.contains("<title>Acme &amp; Co changelog</title>"));
+        assert!(xml.contains("<title>&lt;Dark&gt; mode</title>"));
+        assert!(xml.contains("href=\"https://example.com/posts/2?a=1&amp;b=2\""));
+        assert!(xml.contains("<content type=\"html\">&lt;p&gt;Now available&lt;/p&gt;</content>"));
+        assert!(xml.contains("<updated>2024-03-01T12:00:00+00:00</updated>"));
+    }
+
+    #[test]
+    fn rss_uses_rfc2822_dates() {
+        let xml = to_rss(&sample());
+        assert!(xml.contains("<pubDate>Fri, 1 Mar 2024 12:00:00 +0000</pubDate>"));
+        assert!(xml.contains("<dc:creator>Ada</dc:creator>"));
+        assert!(xml.contains("<guid isPermaLink=\"false\">urn:uuid:"));
+    }
+}
@@ -0,0 +1,260 @@
+//! Server-side processing for uploaded avatars and logos.
+//!
+//! Images are fully decoded and re-encoded, which drops EXIF and any other
+//! metadata. The EXIF orientation is applied to the pixels first so photos
+//! keep the right way up.
+
+use std::io::Cursor;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageReader, Limits};
+
+use crate::errors::AppError;
+
+/// Largest accepted width or height, to bound decode memory.
+const MAX_DIMENSION: u32 = 8192;
+const JPEG_QUALITY: u8 = 85;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageKind {
+    /// Square, center-cropped user avatars.
+    Avatar,
+    /// Organization logos, scaled to fit while keeping their aspect ratio.
+    Logo,
+}
+
+impl ImageKind {
+    /// Storage directory and URL segment for this kind.
+    pub fn dir(self) -> &'static str {
+        match self {
+            ImageKind::Avatar => "avatars",
+            ImageKind::Logo => "logos",
+        }
+    }
+
+    pub fn from_dir(dir: &str) -> Option<Self> {
+        match dir {
+            "avatars" => Some(ImageKind::Avatar),
+            "logos" => Some(ImageKind::Logo),
+            _ => None,
+        }
+    }
+
+    /// Rendition sizes in pixels, largest first.
+    pub fn sizes(self) -> &'static [u32] {
+        match self {
+            ImageKind::Avatar => &[256, 128, 64],
+            ImageKind::Logo => &[512, 128],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+}
+
+impl ImageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpg",
+        }
+    }
+
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext {
+            "png" => Some(ImageFormat::Png),
+            "jpg" => Some(ImageFormat::Jpeg),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg => "image/jpeg",
+        }
+    }
+}
+
+/// One re-encoded rendition of an uploaded image.
+#[derive(Debug)]
+pub struct Rendition {
+    pub size: u32,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct ProcessedImage {
+    pub format: ImageFormat,
+    /// In the same order as `ImageKind::sizes`.
+    pub renditions: Vec<Rendition>,
+}
+
+/// Decode an uploaded image, normalize its orientation and produce every
+/// rendition for `kind`. Images with transparency are encoded as PNG, all
+/// others as JPEG. Smaller images are never upscaled.
+///
+/// CPU-bound; call from `spawn_blocking`.
+pub fn process(data: &[u8], kind: ImageKind) -> Result<ProcessedImage, AppError> {
+    let image = decode(data)?;
+
+    let format = if has_transparency(&image) {
+        ImageFormat::Png
+    } else {
+        ImageFormat::Jpeg
+    };
+
+    let renditions = kind
+        .sizes()
+        .iter()
+        .map(|&size| {
+            let resized = resize(&image, kind, size);
+            encode(&resized, format).map(|data| Rendition { size, data })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ProcessedImage { format, renditions })
+}
+
+fn decode(data: &[u8]) -> Result<DynamicImage, AppError> {
+    let invalid = |e: image::ImageError| {
+        AppError::UnsupportedMediaType(format!(
+            "Could not read image (PNG, JPEG, GIF or WebP required): {e}"
+        ))
+    };
+
+    let mut reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|e| AppError::BadRequest(format!("Could not read image: {e}")))?;
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+    reader.limits(limits);
+
+    let mut decoder = reader.into_decoder().map_err(invalid)?;
+    let orientation = decoder.orientation().map_err(invalid)?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(invalid)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+fn has_transparency(image: &DynamicImage) -> bool {
+    image.color().has_alpha() && image.to_rgba8().pixels().any(|p| p[3] < u8::MAX)
+}
+
+fn resize(image: &DynamicImage, kind: ImageKind, size: u32) -> DynamicImage {
+    match kind {
+        ImageKind::Avatar => {
+            let side = size.min(image.width()).min(image.height());
+            image.resize_to_fill(side, side, FilterType::Lanczos3)
+        }
+        ImageKind::Logo => {
+            if image.width() > size || image.height() > size {
+                image.resize(size, size, FilterType::Lanczos3)
+            } else {
+                image.clone()
+            }
+        }
+    }
+}
+
+fn encode(image: &DynamicImage, format: ImageFormat) -> Result<Vec<u8>, AppError> {
+    let mut buf = Vec::new();
+    let result = match format {
+        ImageFormat::Png => {
+            DynamicImage::ImageRgba8(image.to_rgba8()).write_with_encoder(PngEncoder::new(&mut buf))
+        }
+        ImageFormat::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8())
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut buf, JPEG_QUALITY)),
+    };
+    result.map_err(|e| AppError::InternalError(format!("Image encoding failed: {e}")))?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgb, RgbImage, Rgba, RgbaImage};
+
+    use super::*;
+
+    fn png(image: DynamicImage) -> Vec<u8> {
+        let mut buf = Vec::new();
+        image.write_with_encoder(PngEncoder::new(&mut buf)).unwrap();
+        buf
+    }
+
+    fn dimensions(data: &[u8]) -> (u32, u32) {
+        let image = image::load_from_memory(data).unwrap();
+        (image.width(), image.height())
+    }
+
+    #[test]
+    fn avatars_are_square_and_not_upscaled() {
+        let input = png(DynamicImage::ImageRgb8(RgbImage::from_pixel(
+            400,
+            200,
+            Rgb([200, 10, 10]),
+        )));
+        let processed = process(&input, ImageKind::Avatar).unwrap();
+        assert_eq!(processed.format, ImageFormat::Jpeg);
+
+        let sizes: Vec<(u32, (u32, u32))> = processed
+            .renditions
+            .iter()
+            .map(|r| (r.size, dimensions(&r.data)))
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![(256, (200, 200)), (128, (128, 128)), (64, (64, 64))]
+        );
+    }
+
+    #[test]
+    fn logos_keep_aspect_ratio_and_transparency() {
+        let input = png(DynamicImage::ImageRgba8(RgbaImage::from_pixel(
+            1024,
+            256,
+            Rgba([0, 0, 0, 0]),
+        )));
+        let processed = process(&input, ImageKind::Logo).unwrap();
+        assert_eq!(processed.format, ImageFormat::Png);
+        assert_eq!(dimensions(&processed.renditions[0].data), (512, 128));
+        assert_eq!(dimensions(&processed.renditions[1].data), (128, 32));
+    }
+
+    #[test]
+    fn exif_orientation_is_applied_and_metadata_stripped() {
+        let mut jpeg = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::from_pixel(40, 20, Rgb([0, 120, 255])))
+            .write_with_encoder(JpegEncoder::new(&mut jpeg))
+            .unwrap();
+
+        // APP1 Exif segment with a single Orientation = 6 (rotate 90° CW) tag
+        let tiff: &[u8] = b"MM\x00\x2a\x00\x00\x00\x08\x00\x01\x01\x12\x00\x03\x00\x00\x00\x01\x00\x06\x00\x00\x00\x00\x00\x00";
+        let mut app1 = b"Exif\x00\x00".to_vec();
+        app1.extend_from_slice(tiff);
+        let mut with_exif = jpeg[..2].to_vec();
+        with_exif.extend_from_slice(&[0xFF, 0xE1]);
+        with_exif.extend_from_slice(&((app1.len() + 2) as u16).to_be_bytes());
+        with_exif.extend_from_slice(&app1);
+        with_exif.extend_from_slice(&jpeg[2..]);
+
+        let processed = process(&with_exif, ImageKind::Logo).unwrap();
+        let output = &processed.renditions[0].data;
+        assert_eq!(dimensions(output), (20, 40));
+        assert!(!output.windows(4).any(|w| w == b"Exif"));
+    }
+
+    #[test]
+    fn rejects_non_images() {
+        assert!(matches!(
+            process(b"definitely not an image", ImageKind::Avatar),
+            Err(AppError::UnsupportedMediaType(_))
+        ));
+    }
+}
@@ -3,17 +3,23 @@ use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode}
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::models::user::User;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: Uuid,
     pub exp: usize,
     pub iat: usize,
+    /// `users.token_version` when the token was issued
+    #[serde(default)]
+    pub ver: i32,
 }
 
-pub fn encode_token(user_id: Uuid, secret: &str) -> Result<String, jsonwebtoken::errors::Error> {
+pub fn encode_token(user: &User, secret: &str) -> Result<String, jsonwebtoken::errors::Error> {
     let now = Utc::now();
     let claims = Claims {
-        sub: user_id,
+        sub: user.id,
+        ver: user.token_version,
         iat: now.timestamp() as usize,
         exp: (now + Duration::hours(24)).timestamp() as usize,
     };
@@ -0,0 +1,147 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use ammonia::Builder;
+use pulldown_cmark::{Event, Options, Parser, TagEnd, html};
+
+/// Sanitizer applied to all rendered HTML. Anything not listed here (script,
+/// style, iframes, event handlers, inline styles...) is stripped.
+static SANITIZER: LazyLock<Builder<'static>> = LazyLock::new(|| {
+    let tags: HashSet<&str> = [
+        "a",
+        "blockquote",
+        "br",
+        "code",
+        "del",
+        "em",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "hr",
+        "img",
+        "li",
+        "ol",
+        "p",
+        "pre",
+        "strong",
+        "table",
+        "tbody",
+        "td",
+        "th",
+        "thead",
+        "tr",
+        "ul",
+    ]
+    .into_iter()
+    .collect();
+
+    let tag_attributes: HashMap<&str, HashSet<&str>> = [
+        ("a", ["href", "title"].into_iter().collect()),
+        ("img", ["src", "alt", "title"].into_iter().collect()),
+        ("ol", ["start"].into_iter().collect()),
+    ]
+    .into_iter()
+    .collect();
+
+    let mut builder = Builder::default();
+    builder
+        .tags(tags)
+        .tag_attributes(tag_attributes)
+        .generic_attributes(HashSet::new())
+        .url_schemes(["http", "https", "mailto"].into_iter().collect())
+        .link_rel(Some("nofollow noopener noreferrer"));
+    builder
+});
+
+fn parser_options() -> Options {
+    Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES
+}
+
+/// Render user-supplied CommonMark to sanitized HTML.
+pub fn render_html(markdown: &str) -> String {
+    let parser = Parser::new_ext(markdown, parser_options());
+    let mut unsafe_html = String::new();
+    html::push_html(&mut unsafe_html, parser);
+    SANITIZER.clean(&unsafe_html).to_string()
+}
+
+/// Strip markdown syntax (and any raw HTML) down to plain text, with block
+/// boundaries collapsed into single spaces.
+pub fn render_plain_text(markdown: &str) -> String {
+    let mut text = String::new();
+    for event in Parser::new_ext(markdown, parser_options()) {
+        match event {
+            Event::Text(t) | Event::Code(t) => text.push_str(&t),
+            Event::SoftBreak
+            | Event::HardBreak
+            | Event::Rule
+            | Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::Item
+                | TagEnd::CodeBlock
+                | TagEnd::TableCell,
+            ) => text.push(' '),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Plain-text preview of at most `max_chars` characters, with `...` appended
+/// when truncated.
+pub fn preview(markdown: &str, max_chars: usize) -> String {
+    let text = render_plain_text(markdown);
+    if text.chars().count() > max_chars {
+        let truncated: String = text.chars().take(max_chars).collect();
+        format!("{}...", truncated.trim_end())
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_commonmark() {
+        let html = render_html("# Title\n\nSome **bold** and `code`.");
+        assert!(html.contains("<h1>Title</h1>"));
+        assert!(html.contains("<strong>bold</strong>"));
+        assert!(html.contains("<code>code</code>"));
+    }
+
+    #[test]
+    fn strips_scripts_styles_and_handlers() {
+        let html =
+            render_html("<script>alert(1)</script><style>p{}</style><p onclick=\"x()\">hi</p>");
+        assert!(!html.contains("script"));
+        assert!(!html.contains("alert"));
+        assert!(!html.contains("style"));
+        assert!(!html.contains("onclick"));
+        assert!(html.contains("hi"));
+    }
+
+    #[test]
+    fn links_are_nofollow_and_unsafe_schemes_dropped() {
+        let html = render_html("[ok](https://example.com) [bad](javascript:alert(1))");
+        assert!(html.contains(
+            r#"<a href="https://example.com" rel="nofollow noopener noreferrer">ok</a>"#
+        ));
+        assert!(!html.contains("javascript"));
+    }
+
+    #[test]
+    fn plain_text_preview_strips_markup() {
+        assert_eq!(
+            render_plain_text("## Heading\n\n- one\n- *two*\n\n<b>raw</b>"),
+            "Heading one two raw"
+        );
+        assert_eq!(preview("**abcdef**", 3), "abc...");
+        assert_eq!(preview("short", 200), "short");
+    }
+}
@@ -1,2 +1,7 @@
+pub mod content_type;
+pub mod feed;
+pub mod imaging;
 pub mod jwt;
+pub mod markdown;
+pub mod multipart;
 pub mod slugify;
@@ -0,0 +1,54 @@
+use actix_multipart::Multipart;
+use futures_util::StreamExt;
+use utoipa::ToSchema;
+
+use crate::errors::AppError;
+
+/// Documents upload bodies read by `read_file_field`.
+#[derive(ToSchema)]
+pub struct FileUpload {
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+/// Read the `file` field of a multipart upload, enforcing `max_bytes` while
+/// streaming so oversized bodies are rejected without buffering them fully.
+pub async fn read_file_field(
+    mut payload: Multipart,
+    max_bytes: usize,
+) -> Result<(String, Vec<u8>), AppError> {
+    while let Some(field) = payload.next().await {
+        let mut field =
+            field.map_err(|e| AppError::BadRequest(format!("Invalid multipart body: {e}")))?;
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let filename = field
+            .content_disposition()
+            .and_then(|cd| cd.get_filename())
+            .unwrap_or("file")
+            .to_string();
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk =
+                chunk.map_err(|e| AppError::BadRequest(format!("Invalid multipart body: {e}")))?;
+            if data.len() + chunk.len() > max_bytes {
+                return Err(AppError::PayloadTooLarge(format!(
+                    "File must be at most {max_bytes} bytes"
+                )));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        if data.is_empty() {
+            return Err(AppError::BadRequest("File is empty".to_string()));
+        }
+        return Ok((filename, data));
+    }
+
+    Err(AppError::BadRequest(
+        "Missing multipart field \"file\"".to_string(),
+    ))
+}
@@ -0,0 +1,74 @@
+//! Secrets as files named after them in one directory, the way Docker
+//! (`/run/secrets`) and Kubernetes secret volumes mount them. A trailing
+//! newline is ignored.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+
+use super::{SecretError, SecretProvider};
+
+pub struct FileProvider {
+    dir: PathBuf,
+}
+
+impl FileProvider {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl SecretProvider for FileProvider {
+    fn name(&self) -> &'static str {
+        "secrets directory"
+    }
+
+    async fn fetch(&self, keys: &[String]) -> Result<HashMap<String, String>, SecretError> {
+        let mut values = HashMap::new();
+        for key in keys {
+            // Names come from configuration, but must still stay inside the directory
+            if key.is_empty() || key.contains(['/', '\\']) || key.starts_with('.') {
+                return Err(SecretError(format!("Invalid secret name: {key}")));
+            }
+            let path = self.dir.join(key);
+            match tokio::fs::read_to_string(&path).await {
+                Ok(value) => {
+                    let value = value.trim_end_matches(['\n', '\r']).to_string();
+                    values.insert(key.clone(), value);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(SecretError(format!("{}: {e}", path.display())));
+                }
+            }
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reads_present_files_without_trailing_newline() {
+        let dir = std::env::temp_dir().join(format!("rivvo-secrets-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("JWT_SECRET"), "s3cret\n").unwrap();
+        let provider = FileProvider::new(&dir);
+
+        let values = provider
+            .fetch(&["JWT_SECRET".to_string(), "DATABASE_URL".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values["JWT_SECRET"], "s3cret");
+
+        for key in ["../JWT_SECRET", ".hidden", ""] {
+            assert!(provider.fetch(&[key.to_string()]).await.is_err(), "{key}");
+        }
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
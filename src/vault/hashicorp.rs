@@ -0,0 +1,156 @@
+//! HashiCorp Vault KV version 2. All secrets are fields of one KV secret,
+//! read with a single `GET {VAULT_ADDR}/v1/{mount}/data/{path}`.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::{SecretError, SecretProvider};
+
+#[derive(Debug, Deserialize)]
+struct KvResponse {
+    data: KvData,
+}
+
+#[derive(Debug, Deserialize)]
+struct KvData {
+    data: HashMap<String, serde_json::Value>,
+}
+
+pub struct HashicorpProvider {
+    url: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+impl HashicorpProvider {
+    pub fn new(addr: &str, token: String, mount: &str, path: &str) -> Result<Self, String> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client for vault: {e}"))?;
+        Ok(Self {
+            url: format!(
+                "{}/v1/{}/data/{}",
+                addr.trim_end_matches('/'),
+                mount.trim_matches('/'),
+                path.trim_matches('/')
+            ),
+            token,
+            client,
+        })
+    }
+}
+
+#[async_trait]
+impl SecretProvider for HashicorpProvider {
+    fn name(&self) -> &'static str {
+        "HashiCorp Vault"
+    }
+
+    async fn fetch(&self, keys: &[String]) -> Result<HashMap<String, String>, SecretError> {
+        let response = self
+            .client
+            .get(&self.url)
+            .header("X-Vault-Token", &self.token)
+            .send()
+            .await
+            .map_err(|e| SecretError(format!("GET {}: {e}", self.url)))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(HashMap::new());
+        }
+        if !response.status().is_success() {
+            return Err(SecretError(format!(
+                "GET {}: HTTP {}",
+                self.url,
+                response.status()
+            )));
+        }
+        let mut secret: KvResponse = response
+            .json()
+            .await
+            .map_err(|e| SecretError(format!("GET {}: {e}", self.url)))?;
+
+        // Fields are usually strings; numbers and booleans are taken as written
+        Ok(keys
+            .iter()
+            .filter_map(|key| {
+                let value = match secret.data.data.remove(key)? {
+                    serde_json::Value::String(s) => s,
+                    serde_json::Value::Null => return None,
+                    other => other.to_string(),
+                };
+                Some((key.clone(), value))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{App, HttpRequest, HttpResponse, HttpServer, web};
+    use serde_json::json;
+
+    use super::*;
+
+    async fn kv_handler(req: HttpRequest) -> HttpResponse {
+        let authorized = req
+            .headers()
+            .get("x-vault-token")
+            .is_some_and(|v| v == "test-token");
+        if !authorized {
+            return HttpResponse::Forbidden().finish();
+        }
+        HttpResponse::Ok().json(json!({
+            "data": {
+                "data": {
+                    "DATABASE_URL": "postgres://vault/rivvo",
+                    "JOB_WORKERS": 4,
+                },
+                "metadata": { "version": 3 },
+            },
+        }))
+    }
+
+    fn start_mock_vault() -> String {
+        let server = HttpServer::new(|| {
+            App::new()
+                .route("/v1/kv/data/apps/rivvo", web::get().to(kv_handler))
+                .default_service(web::to(HttpResponse::NotFound))
+        })
+        .workers(1)
+        .bind("127.0.0.1:0")
+        .unwrap();
+        let addr = server.addrs()[0];
+        tokio::spawn(server.run());
+        format!("http://{addr}")
+    }
+
+    #[actix_web::test]
+    async fn reads_fields_of_a_kv2_secret_against_mock_server() {
+        let addr = start_mock_vault();
+        let keys = [
+            "DATABASE_URL".to_string(),
+            "JOB_WORKERS".to_string(),
+            "JWT_SECRET".to_string(),
+        ];
+
+        let provider =
+            HashicorpProvider::new(&addr, "test-token".to_string(), "kv", "/apps/rivvo").unwrap();
+        let values = provider.fetch(&keys).await.unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values["DATABASE_URL"], "postgres://vault/rivvo");
+        assert_eq!(values["JOB_WORKERS"], "4");
+
+        // A path without a secret holds nothing
+        let provider =
+            HashicorpProvider::new(&addr, "test-token".to_string(), "kv", "other").unwrap();
+        assert!(provider.fetch(&keys).await.unwrap().is_empty());
+
+        let provider =
+            HashicorpProvider::new(&addr, "wrong".to_string(), "kv", "apps/rivvo").unwrap();
+        let err = provider.fetch(&keys).await.unwrap_err();
+        assert!(err.0.contains("403"), "{err}");
+    }
+}
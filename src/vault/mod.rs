@@ -0,0 +1,322 @@
+//! Optional secret loading from an external store.
+//!
+//! `fetch_secrets` reads the secrets named in `secrets.keys` (`SECRETS_KEYS`,
+//! default `DATABASE_URL,JWT_SECRET`) from the store chosen by
+//! `secrets.provider` and injects them into the process environment (only
+//! for vars not already set), before `Config::load` reads it:
+//!
+//! - `vaultwarden`: a Vaultwarden-API instance. Needs the `vault` feature.
+//! - `hashicorp`: a field per secret in a HashiCorp Vault KV v2 secret.
+//!   Needs the `vault` feature.
+//! - `file`: one file per secret in a directory, as Docker and Kubernetes
+//!   mount them.
+//!
+//! `secrets.prefix` namespaces secrets in the store (e.g. prefix `RIVVO_`
+//! fetches `RIVVO_DATABASE_URL` from the vault but sets `DATABASE_URL`).
+//!
+//! With `secrets.refresh_secs` set, `Secrets::spawn_refresh` reads the loaded
+//! secrets again at that interval and reports the ones that were rotated.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::config::SecretsConfig;
+
+pub mod file;
+#[cfg(feature = "vault")]
+pub mod hashicorp;
+#[cfg(feature = "vault")]
+pub mod vaultwarden;
+
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct SecretError(pub String);
+
+/// A store secrets are read from.
+#[async_trait]
+pub trait SecretProvider: Send + Sync {
+    /// Shown in logs.
+    fn name(&self) -> &'static str;
+
+    /// Values of those of `keys` the store holds. Keys it doesn't hold are
+    /// left out rather than being an error.
+    async fn fetch(&self, keys: &[String]) -> Result<HashMap<String, String>, SecretError>;
+}
+
+/// The provider selected in `config`, or `None` if no store is configured.
+/// `Config` has already checked that its settings are present.
+pub fn provider_from_config(
+    config: &SecretsConfig,
+) -> Result<Option<Arc<dyn SecretProvider>>, String> {
+    let provider: Arc<dyn SecretProvider> = match config.provider.as_str() {
+        "" => return Ok(None),
+        "file" => Arc::new(file::FileProvider::new(&config.dir)),
+        #[cfg(feature = "vault")]
+        "vaultwarden" => Arc::new(vaultwarden::VaultwardenProvider::new(
+            &config.vaultwarden.api_url,
+            config.vaultwarden.api_key.clone(),
+        )?),
+        #[cfg(feature = "vault")]
+        "hashicorp" => Arc::new(hashicorp::HashicorpProvider::new(
+            &config.hashicorp.addr,
+            config.hashicorp.token.clone(),
+            &config.hashicorp.kv_mount,
+            &config.hashicorp.kv_path,
+        )?),
+        other => return Err(format!("Unsupported secrets provider: {other}")),
+    };
+    Ok(Some(provider))
+}
+
+#[derive(Debug, Clone)]
+struct SecretMapping {
+    env_key: String,
+    vault_key: String,
+}
+
+fn build_secret_mappings(keys: &[String], prefix: &str) -> Vec<SecretMapping> {
+    keys.iter()
+        .map(|key| SecretMapping {
+            env_key: key.clone(),
+            vault_key: format!("{prefix}{key}"),
+        })
+        .collect()
+}
+
+/// Secrets loaded from a store, kept to refresh them.
+pub struct Secrets {
+    provider: Arc<dyn SecretProvider>,
+    mappings: Vec<SecretMapping>,
+    /// Current value of each loaded secret, by env key
+    values: HashMap<String, String>,
+    refresh: Option<Duration>,
+}
+
+/// Fill unset environment variables from the store configured in `config`.
+/// A store that can't be read is logged and startup continues. Returns the
+/// loaded secrets for refreshing, if they could be loaded.
+pub async fn fetch_secrets(config: &SecretsConfig) -> Option<Secrets> {
+    let provider = match provider_from_config(config) {
+        Ok(Some(provider)) => provider,
+        Ok(None) => {
+            tracing::debug!("Secret store not configured, skipping");
+            return None;
+        }
+        Err(e) => {
+            tracing::warn!("Secret store unavailable, skipping: {e}");
+            return None;
+        }
+    };
+    let refresh = Some(config.refresh_secs)
+        .filter(|&secs| secs > 0)
+        .map(Duration::from_secs);
+
+    let mappings = build_secret_mappings(&config.keys, &config.prefix)
+        .into_iter()
+        .filter(|mapping| {
+            let set = env::var(&mapping.env_key).is_ok();
+            if set {
+                tracing::warn!(
+                    "Secret {} already set via env, skipping vault",
+                    mapping.env_key
+                );
+            }
+            !set
+        })
+        .collect();
+
+    tracing::info!("Fetching secrets from {}", provider.name());
+    let mut secrets = Secrets::new(provider, mappings, refresh);
+    if let Err(e) = secrets.load().await {
+        tracing::warn!(
+            "Failed to fetch secrets from {}: {e}",
+            secrets.provider.name()
+        );
+        return None;
+    }
+    for (key, value) in &secrets.values {
+        // SAFETY: Called during single-threaded startup before any
+        // worker threads or connection pools are created.
+        unsafe {
+            env::set_var(key, value);
+        }
+        tracing::info!("Loaded secret {key} from {}", secrets.provider.name());
+    }
+    Some(secrets)
+}
+
+impl Secrets {
+    fn new(
+        provider: Arc<dyn SecretProvider>,
+        mappings: Vec<SecretMapping>,
+        refresh: Option<Duration>,
+    ) -> Self {
+        Self {
+            provider,
+            mappings,
+            values: HashMap::new(),
+            refresh,
+        }
+    }
+
+    /// Initial fetch. Secrets the store lacks are dropped and never refreshed.
+    async fn load(&mut self) -> Result<(), SecretError> {
+        self.values = self.fetch().await?;
+        for mapping in &self.mappings {
+            if !self.values.contains_key(&mapping.env_key) {
+                tracing::warn!(
+                    "Secret {} not found in {}",
+                    mapping.vault_key,
+                    self.provider.name()
+                );
+            }
+        }
+        let values = &self.values;
+        self.mappings.retain(|m| values.contains_key(&m.env_key));
+        Ok(())
+    }
+
+    /// Current values by env key.
+    async fn fetch(&self) -> Result<HashMap<String, String>, SecretError> {
+        if self.mappings.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let vault_keys: Vec<String> = self.mappings.iter().map(|m| m.vault_key.clone()).collect();
+        let mut fetched = self.provider.fetch(&vault_keys).await?;
+        Ok(self
+            .mappings
+            .iter()
+            .filter_map(|m| Some((m.env_key.clone(), fetched.remove(&m.vault_key)?)))
+            .collect())
+    }
+
+    /// Fetch again and return the secrets whose value changed, as
+    /// `(env key, new value)`. A secret that disappeared keeps its value.
+    async fn refresh(&mut self) -> Result<Vec<(String, String)>, SecretError> {
+        let mut changed = Vec::new();
+        for (key, value) in self.fetch().await? {
+            if self.values.get(&key) != Some(&value) {
+                self.values.insert(key.clone(), value.clone());
+                changed.push((key, value));
+            }
+        }
+        changed.sort();
+        Ok(changed)
+    }
+
+    /// With `secrets.refresh_secs` set, re-read the loaded secrets at that
+    /// interval in the background and call `on_change` with the env key and
+    /// new value of each one that was rotated.
+    pub fn spawn_refresh(mut self, on_change: impl Fn(&str, &str) + Send + 'static) {
+        let Some(every) = self.refresh else {
+            return;
+        };
+        if self.mappings.is_empty() {
+            return;
+        }
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(every);
+            ticks.tick().await;
+            loop {
+                ticks.tick().await;
+                match self.refresh().await {
+                    Ok(changed) => {
+                        for (key, value) in changed {
+                            tracing::info!("Secret {key} was rotated in {}", self.provider.name());
+                            on_change(&key, &value);
+                        }
+                    }
+                    Err(e) => tracing::warn!(
+                        "Failed to refresh secrets from {}: {e}",
+                        self.provider.name()
+                    ),
+                }
+            }
+        });
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    fn default_keys() -> Vec<String> {
+        SecretsConfig::default().keys
+    }
+
+    #[test]
+    fn build_secret_mappings_with_prefix() {
+        let mappings = build_secret_mappings(&default_keys(), "RIVVO_");
+        assert_eq!(mappings.len(), 2);
+        assert_eq!(mappings[0].env_key, "DATABASE_URL");
+        assert_eq!(mappings[0].vault_key, "RIVVO_DATABASE_URL");
+        assert_eq!(mappings[1].env_key, "JWT_SECRET");
+        assert_eq!(mappings[1].vault_key, "RIVVO_JWT_SECRET");
+    }
+
+    #[test]
+    fn build_secret_mappings_empty_prefix() {
+        let mappings = build_secret_mappings(&default_keys(), "");
+        assert_eq!(mappings[0].vault_key, "DATABASE_URL");
+        assert_eq!(mappings[1].vault_key, "JWT_SECRET");
+    }
+
+    /// A store whose contents the test changes.
+    #[derive(Default)]
+    struct MemoryProvider(Mutex<HashMap<String, String>>);
+
+    #[async_trait]
+    impl SecretProvider for MemoryProvider {
+        fn name(&self) -> &'static str {
+            "memory"
+        }
+
+        async fn fetch(&self, keys: &[String]) -> Result<HashMap<String, String>, SecretError> {
+            let store = self.0.lock().unwrap();
+            Ok(keys
+                .iter()
+                .filter_map(|k| Some((k.clone(), store.get(k)?.clone())))
+                .collect())
+        }
+    }
+
+    impl MemoryProvider {
+        fn set(&self, key: &str, value: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_reports_rotated_secrets_only() {
+        let store = Arc::new(MemoryProvider::default());
+        store.set("RIVVO_DATABASE_URL", "postgres://old");
+        store.set("RIVVO_JWT_SECRET", "jwt");
+        let keys = ["DATABASE_URL", "JWT_SECRET", "METRICS_TOKEN"].map(String::from);
+        let mut secrets = Secrets::new(store.clone(), build_secret_mappings(&keys, "RIVVO_"), None);
+
+        secrets.load().await.unwrap();
+        assert_eq!(secrets.values["DATABASE_URL"], "postgres://old");
+        assert_eq!(secrets.values.len(), 2);
+        assert!(secrets.refresh().await.unwrap().is_empty());
+
+        store.set("RIVVO_DATABASE_URL", "postgres://new");
+        // Missing at startup, so never picked up later
+        store.set("RIVVO_METRICS_TOKEN", "token");
+        assert_eq!(
+            secrets.refresh().await.unwrap(),
+            [("DATABASE_URL".to_string(), "postgres://new".to_string())]
+        );
+        assert!(secrets.refresh().await.unwrap().is_empty());
+    }
+}
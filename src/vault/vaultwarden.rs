@@ -0,0 +1,148 @@
+//! [Vaultwarden-API](https://github.com/Turbootzz/vaultwarden-api): one
+//! `GET {VAULT_API_URL}/secret/{name}` per secret, with Bearer auth.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::{SecretError, SecretProvider};
+
+#[derive(Debug, Deserialize)]
+struct VaultResponse {
+    #[allow(dead_code)]
+    name: String,
+    value: String,
+}
+
+pub struct VaultwardenProvider {
+    api_url: String,
+    api_key: String,
+    client: reqwest::Client,
+}
+
+impl VaultwardenProvider {
+    pub fn new(api_url: &str, api_key: String) -> Result<Self, String> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client for vault: {e}"))?;
+        Ok(Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            api_key,
+            client,
+        })
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<String>, SecretError> {
+        let url = format!("{}/secret/{key}", self.api_url);
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(&self.api_key)
+            .send()
+            .await
+            .map_err(|e| SecretError(format!("GET secret {key}: {e}")))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(SecretError(format!(
+                "GET secret {key}: HTTP {}",
+                response.status()
+            )));
+        }
+        let secret: VaultResponse = response
+            .json()
+            .await
+            .map_err(|e| SecretError(format!("GET secret {key}: {e}")))?;
+        Ok(Some(secret.value))
+    }
+}
+
+#[async_trait]
+impl SecretProvider for VaultwardenProvider {
+    fn name(&self) -> &'static str {
+        "Vaultwarden"
+    }
+
+    async fn fetch(&self, keys: &[String]) -> Result<HashMap<String, String>, SecretError> {
+        let mut values = HashMap::new();
+        for key in keys {
+            if let Some(value) = self.get(key).await? {
+                values.insert(key.clone(), value);
+            }
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{App, HttpRequest, HttpResponse, HttpServer, web};
+    use serde_json::json;
+
+    use super::*;
+
+    async fn secret_handler(req: HttpRequest, name: web::Path<String>) -> HttpResponse {
+        let authorized = req
+            .headers()
+            .get("authorization")
+            .is_some_and(|v| v == "Bearer test-key");
+        if !authorized {
+            return HttpResponse::Unauthorized().finish();
+        }
+        match name.as_str() {
+            "RIVVO_JWT_SECRET" => HttpResponse::Ok().json(json!({
+                "name": "RIVVO_JWT_SECRET",
+                "value": "from-vaultwarden",
+            })),
+            _ => HttpResponse::NotFound().finish(),
+        }
+    }
+
+    fn start_mock_vaultwarden() -> String {
+        let server =
+            HttpServer::new(|| App::new().route("/secret/{name}", web::get().to(secret_handler)))
+                .workers(1)
+                .bind("127.0.0.1:0")
+                .unwrap();
+        let addr = server.addrs()[0];
+        tokio::spawn(server.run());
+        format!("http://{addr}/")
+    }
+
+    #[actix_web::test]
+    async fn fetches_present_secrets_against_mock_server() {
+        let url = start_mock_vaultwarden();
+        let provider = VaultwardenProvider::new(&url, "test-key".to_string()).unwrap();
+
+        let values = provider
+            .fetch(&["RIVVO_JWT_SECRET".to_string(), "RIVVO_MISSING".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values["RIVVO_JWT_SECRET"], "from-vaultwarden");
+
+        let denied = VaultwardenProvider::new(&url, "wrong".to_string()).unwrap();
+        let err = denied
+            .fetch(&["RIVVO_JWT_SECRET".to_string()])
+            .await
+            .unwrap_err();
+        assert!(err.0.contains("401"), "{err}");
+    }
+
+    #[test]
+    fn vault_response_deserialization() {
+        let json = r#"{"name": "MY_SECRET", "value": "secret_value_123"}"#;
+        let resp: VaultResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.name, "MY_SECRET");
+        assert_eq!(resp.value, "secret_value_123");
+    }
+
+    #[test]
+    fn vault_response_rejects_missing_value() {
+        let json = r#"{"name": "MY_SECRET"}"#;
+        assert!(serde_json::from_str::<VaultResponse>(json).is_err());
+    }
+}
@@ -213,6 +213,385 @@ async fn me_without_token_returns_401() {
     assert_eq!(resp.status(), 401);
 }
 
+// ============================================================
+// Account tests
+// ============================================================
+
+#[actix_web::test]
+async fn update_profile_changes_name_and_avatar() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, _org_id) = common::register_user(&pool).await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::put()
+        .uri("/api/auth/me")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({
+            "name": "Renamed User",
+            "avatar_url": "https://example.com/me.png"
+        }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["name"], "Renamed User");
+    assert_eq!(body["avatar_url"], "https://example.com/me.png");
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::put()
+        .uri("/api/auth/me")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({
+            "name": "Renamed User",
+            "avatar_url": "javascript:alert(1)"
+        }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 422);
+}
+
+#[actix_web::test]
+async fn change_password_requires_current_password() {
+    let pool = common::create_pool().await;
+    let (token, user_id, _org_id) = common::register_user(&pool).await;
+    let email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::put()
+        .uri("/api/auth/me/password")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({
+            "current_password": "not-my-password",
+            "new_password": "newpassword456"
+        }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::put()
+        .uri("/api/auth/me/password")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({
+            "current_password": "password123",
+            "new_password": "newpassword456"
+        }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    let new_token = body["token"].as_str().unwrap().to_string();
+
+    // Tokens issued before the change are revoked; the returned one works
+    for (token, status) in [(&token, 401), (&new_token, 200)] {
+        let app = actix_test::init_service(common::build_app(pool.clone())).await;
+        let req = actix_test::TestRequest::get()
+            .uri("/api/auth/me")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), status);
+    }
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri("/api/auth/login")
+        .set_json(serde_json::json!({ "email": &email, "password": "newpassword456" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn email_change_applies_only_after_confirmation() {
+    let pool = common::create_pool().await;
+    let (token, user_id, _org_id) = common::register_user(&pool).await;
+    let old_email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let new_email = format!("changed-{}@example.com", &Uuid::new_v4().to_string()[..8]);
+
+    // Someone else's address is taken whatever its case
+    let (_other_token, other_id, _other_org) = common::register_user(&pool).await;
+    let other_email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+        .bind(other_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri("/api/auth/me/email")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(
+            serde_json::json!({ "new_email": other_email.to_uppercase(), "password": "password123" }),
+        )
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri("/api/auth/me/email")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({ "new_email": &new_email, "password": "password123" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 202);
+
+    // Unchanged until confirmed
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri("/api/auth/me")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["email"], old_email.as_str());
+
+    // Queued, not sent inline
+    assert!(common::last_email_to(&new_email).is_none());
+    let email = common::deliver_email_to(&pool, &new_email)
+        .await
+        .expect("confirmation email sent");
+    let confirm_token = email
+        .body
+        .split("token=")
+        .nth(1)
+        .unwrap()
+        .split_whitespace()
+        .next()
+        .unwrap()
+        .to_string();
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri("/api/auth/email/confirm")
+        .set_json(serde_json::json!({ "token": &confirm_token }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["email"], new_email.as_str());
+    assert!(common::deliver_email_to(&pool, &old_email).await.is_some());
+
+    // Tokens are single-use
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri("/api/auth/email/confirm")
+        .set_json(serde_json::json!({ "token": &confirm_token }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+}
+
+#[actix_web::test]
+async fn delete_account_keeps_content_and_vote_counts_consistent() {
+    let pool = common::create_pool().await;
+    let (admin_token, _admin_id, org_id) = common::register_user(&pool).await;
+    let (member_token, member_id) = common::register_member(&pool, org_id).await;
+    let (board_id, _slug) =
+        common::create_board(&pool, &admin_token, org_id, "Leaving Board").await;
+    let post_id = common::create_post(&pool, &member_token, board_id, "Left Behind").await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/posts/{post_id}/vote"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .to_request();
+    actix_test::call_service(&app, req).await;
+
+    // The last admin of a shared organization cannot leave
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::delete()
+        .uri("/api/auth/me")
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .set_json(serde_json::json!({ "password": "password123" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::delete()
+        .uri("/api/auth/me")
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .set_json(serde_json::json!({ "password": "password123" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 202);
+
+    // Queued: the account is erased by a background job
+    let statuses: Vec<String> = sqlx::query_scalar(
+        "SELECT status FROM data_requests WHERE user_id = $1 AND kind = 'erasure'",
+    )
+    .bind(member_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(statuses, vec!["pending"]);
+
+    // Signed out before the job runs
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri("/api/auth/me")
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 401);
+
+    assert!(common::wait_for_erasure(&pool, member_id).await);
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/boards/{board_id}/posts/{post_id}"))
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert!(body["author"].is_null());
+    assert_eq!(body["vote_count"], 0);
+
+    // The erasure is logged, and the log outlives the account
+    let statuses: Vec<String> = sqlx::query_scalar(
+        "SELECT status FROM data_requests WHERE user_id = $1 AND kind = 'erasure'",
+    )
+    .bind(member_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(statuses, vec!["completed"]);
+}
+
+#[actix_web::test]
+async fn operator_queues_erasure_once() {
+    use rivvo::services::gdpr_service;
+
+    let pool = common::create_pool().await;
+    let (_token, user_id, _org_id) = common::register_user(&pool).await;
+
+    gdpr_service::request_erasure(&pool, user_id).await.unwrap();
+    // A second request while the first is pending changes nothing
+    gdpr_service::request_erasure(&pool, user_id).await.unwrap();
+    let queued: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM jobs WHERE kind = 'erase_user' AND payload->>'user_id' = $1",
+    )
+    .bind(user_id.to_string())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(queued, 1);
+
+    assert!(common::wait_for_erasure(&pool, user_id).await);
+    let status: String = sqlx::query_scalar(
+        "SELECT status FROM data_requests WHERE user_id = $1 AND kind = 'erasure'",
+    )
+    .bind(user_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(status, "completed");
+}
+
+#[actix_web::test]
+async fn operator_password_reset_and_promotion() {
+    let pool = common::create_pool().await;
+    let (_token, _user_id, org_id) = common::register_user(&pool).await;
+    let (_member_token, member_id) = common::register_member(&pool, org_id).await;
+    let email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+        .bind(member_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    let user = rivvo::services::auth_service::get_user_by_email(&pool, &email.to_uppercase())
+        .await
+        .unwrap();
+    assert_eq!(user.id, member_id);
+    rivvo::services::auth_service::set_password(&pool, member_id, "operator-set-1")
+        .await
+        .unwrap();
+    let org = rivvo::services::org_service::find_org(&pool, &org_id.to_string())
+        .await
+        .unwrap();
+    rivvo::services::org_service::set_member_role(&pool, org.id, member_id, "admin")
+        .await
+        .unwrap();
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri("/api/auth/login")
+        .set_json(serde_json::json!({ "email": email, "password": "operator-set-1" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    let member_token = body["token"].as_str().unwrap().to_string();
+
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/orgs/{org_id}/boards"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .set_json(serde_json::json!({ "name": format!("Promoted {}", Uuid::new_v4()) }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+}
+
+#[actix_web::test]
+async fn export_includes_authored_content_and_votes() {
+    let pool = common::create_pool().await;
+    let (token, user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Export Board").await;
+    let post_id = common::create_post(&pool, &token, board_id, "Exported Post").await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/posts/{post_id}/vote"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    actix_test::call_service(&app, req).await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri("/api/auth/me/export")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert!(
+        resp.headers()
+            .get("content-disposition")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .starts_with("attachment")
+    );
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["profile"]["id"], user_id.to_string());
+    assert_eq!(body["memberships"][0]["role"], "admin");
+    assert_eq!(body["posts"][0]["title"], "Exported Post");
+    assert_eq!(body["votes"][0]["post_id"], post_id.to_string());
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri("/api/auth/me/export?format=zip")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/zip"
+    );
+    let bytes = actix_test::read_body(resp).await;
+    assert!(bytes.starts_with(b"PK\x03\x04"));
+}
+
 // ============================================================
 // Board tests
 // ============================================================
@@ -348,94 +727,538 @@ async fn get_nonexistent_board_returns_404() {
     assert_eq!(resp.status(), 404);
 }
 
-// ============================================================
-// Post tests
-// ============================================================
-
 #[actix_web::test]
-async fn create_and_get_post() {
+async fn export_board_as_json_and_csv() {
     let pool = common::create_pool().await;
     let (token, _user_id, org_id) = common::register_user(&pool).await;
-    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Posts Board").await;
+    let (board_id, slug) = common::create_board(&pool, &token, org_id, "Export Board").await;
+    let post_id = common::create_post(&pool, &token, board_id, "Exported, with comma").await;
 
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
     let req = actix_test::TestRequest::post()
-        .uri(&format!("/api/boards/{board_id}/posts"))
+        .uri(&format!("/api/posts/{post_id}/comments"))
         .insert_header(("Authorization", format!("Bearer {token}")))
-        .set_json(serde_json::json!({
-            "title": "Dark mode please",
-            "description": "We need dark mode"
-        }))
+        .set_json(serde_json::json!({ "body": "First comment" }))
         .to_request();
+    actix_test::call_service(&app, req).await;
 
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/orgs/{org_id}/boards/{slug}/export"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
     let resp = actix_test::call_service(&app, req).await;
-    assert_eq!(resp.status(), 201);
+    assert_eq!(resp.status(), 200);
     let body: Value = actix_test::read_body_json(resp).await;
-    assert_eq!(body["title"], "Dark mode please");
-    assert_eq!(body["status"], "open");
-    assert_eq!(body["vote_count"], 0);
-    let post_id = body["id"].as_str().unwrap();
+    assert_eq!(body["board"]["slug"], slug);
+    assert_eq!(body["posts"][0]["title"], "Exported, with comma");
+    assert_eq!(body["posts"][0]["comments"][0]["body"], "First comment");
 
-    // Get the post
-    let app = actix_test::init_service(common::build_app(pool.clone())).await;
     let req = actix_test::TestRequest::get()
-        .uri(&format!("/api/boards/{board_id}/posts/{post_id}"))
+        .uri(&format!(
+            "/api/orgs/{org_id}/boards/{slug}/export?format=csv"
+        ))
         .insert_header(("Authorization", format!("Bearer {token}")))
         .to_request();
     let resp = actix_test::call_service(&app, req).await;
     assert_eq!(resp.status(), 200);
-    let body: Value = actix_test::read_body_json(resp).await;
-    assert_eq!(body["title"], "Dark mode please");
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "text/csv; charset=utf-8"
+    );
+    let csv = String::from_utf8(actix_test::read_body(resp).await.to_vec()).unwrap();
+    let mut lines = csv.lines();
+    assert!(
+        lines
+            .next()
+            .unwrap()
+            .starts_with("id,title,description,status,tags")
+    );
+    assert!(lines.next().unwrap().contains("\"Exported, with comma\""));
 }
 
 #[actix_web::test]
-async fn list_posts_with_sort_and_filter() {
+async fn export_and_import_require_admin() {
     let pool = common::create_pool().await;
     let (token, _user_id, org_id) = common::register_user(&pool).await;
-    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Sorted Board").await;
-
-    common::create_post(&pool, &token, board_id, "Post A").await;
-    common::create_post(&pool, &token, board_id, "Post B").await;
+    let (_board_id, slug) = common::create_board(&pool, &token, org_id, "Admin Only").await;
+    let (member_token, _member_id) = common::register_member(&pool, org_id).await;
 
-    // List with default sort
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
     let req = actix_test::TestRequest::get()
-        .uri(&format!("/api/boards/{board_id}/posts"))
-        .insert_header(("Authorization", format!("Bearer {token}")))
+        .uri(&format!("/api/orgs/{org_id}/boards/{slug}/export"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
         .to_request();
     let resp = actix_test::call_service(&app, req).await;
-    assert_eq!(resp.status(), 200);
-    let body: Value = actix_test::read_body_json(resp).await;
-    assert_eq!(body.as_array().unwrap().len(), 2);
+    assert_eq!(resp.status(), 403);
 
-    // List with status filter
-    let app = actix_test::init_service(common::build_app(pool.clone())).await;
-    let req = actix_test::TestRequest::get()
-        .uri(&format!("/api/boards/{board_id}/posts?status=planned"))
-        .insert_header(("Authorization", format!("Bearer {token}")))
-        .to_request();
+    let req = upload_request(
+        &format!("/api/orgs/{org_id}/boards/{slug}/import"),
+        &member_token,
+        "posts.csv",
+        b"title\nSneaky\n",
+    )
+    .to_request();
     let resp = actix_test::call_service(&app, req).await;
-    assert_eq!(resp.status(), 200);
-    let body: Value = actix_test::read_body_json(resp).await;
-    assert_eq!(body.as_array().unwrap().len(), 0);
+    assert_eq!(resp.status(), 403);
+}
 
-    // List with sort=recent
+#[actix_web::test]
+async fn import_csv_maps_statuses_tags_and_authors() {
+    let pool = common::create_pool().await;
+    let (token, user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, slug) = common::create_board(&pool, &token, org_id, "Import Board").await;
+    let email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    let csv = format!(
+        "Title,Description,Status,Tags,Author Email,Created At\n\
+         Dark mode,Please,Under Review,ui; themes,{email},2021-03-04 05:06:07\n\
+         Export to PDF,,Shipped,ui,stranger@example.com,2020-01-01\n"
+    );
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
-    let req = actix_test::TestRequest::get()
-        .uri(&format!("/api/boards/{board_id}/posts?sort=recent"))
-        .insert_header(("Authorization", format!("Bearer {token}")))
-        .to_request();
+    let req = upload_request(
+        &format!("/api/orgs/{org_id}/boards/{slug}/import"),
+        &token,
+        "canny.csv",
+        csv.as_bytes(),
+    )
+    .to_request();
     let resp = actix_test::call_service(&app, req).await;
     assert_eq!(resp.status(), 200);
+    let report: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(report["valid"], true);
+    assert_eq!(report["posts"], 2);
+    let mut created: Vec<&str> = report["tags_created"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t.as_str().unwrap())
+        .collect();
+    created.sort();
+    assert_eq!(created, vec!["themes", "ui"]);
+
+    let rows: Vec<(String, String, Option<Uuid>, chrono::DateTime<chrono::Utc>)> = sqlx::query_as(
+        "SELECT title, status, author_id, created_at FROM posts WHERE board_id = $1 ORDER BY created_at",
+    )
+    .bind(board_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].0, "Export to PDF");
+    assert_eq!(rows[0].1, "done");
+    assert_eq!(rows[0].2, None);
+    assert_eq!(rows[1].1, "open");
+    assert_eq!(rows[1].2, Some(user_id));
+    assert_eq!(rows[1].3.to_rfc3339(), "2021-03-04T05:06:07+00:00");
+
+    let tag_count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM post_tags pt JOIN posts p ON p.id = pt.post_id WHERE p.board_id = $1",
+    )
+    .bind(board_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(tag_count, 3);
 }
 
 #[actix_web::test]
-async fn update_post_by_author() {
+async fn import_json_dry_run_writes_nothing() {
     let pool = common::create_pool().await;
     let (token, _user_id, org_id) = common::register_user(&pool).await;
-    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Update Board").await;
-    let post_id = common::create_post(&pool, &token, board_id, "Original Title").await;
-
+    let (board_id, slug) = common::create_board(&pool, &token, org_id, "Dry Run Board").await;
+
+    let json = serde_json::json!({
+        "posts": [{
+            "title": "Imported idea",
+            "status": "planned",
+            "tags": ["api"],
+            "comments": [{ "body": "Agreed" }, { "body": "Me too", "is_admin_reply": true }]
+        }]
+    });
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(
+        &format!("/api/orgs/{org_id}/boards/{slug}/import?dry_run=true"),
+        &token,
+        "export.json",
+        json.to_string().as_bytes(),
+    )
+    .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let report: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["posts"], 1);
+    assert_eq!(report["comments"], 2);
+    assert_eq!(report["tags_created"][0], "api");
+
+    let posts: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM posts WHERE board_id = $1")
+        .bind(board_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let tags: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tags WHERE board_id = $1")
+        .bind(board_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!((posts, tags), (0, 0));
+
+    let req = upload_request(
+        &format!("/api/orgs/{org_id}/boards/{slug}/import"),
+        &token,
+        "export.json",
+        json.to_string().as_bytes(),
+    )
+    .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let comment_count: i32 =
+        sqlx::query_scalar("SELECT comment_count FROM posts WHERE board_id = $1")
+            .bind(board_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(comment_count, 2);
+}
+
+#[actix_web::test]
+async fn import_with_invalid_rows_returns_report_and_writes_nothing() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, slug) = common::create_board(&pool, &token, org_id, "Bad Import").await;
+
+    let csv = "title,status,created_at\nGood,open,\n,open,\nOdd,someday,\nLate,open,yesterday\n";
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(
+        &format!("/api/orgs/{org_id}/boards/{slug}/import"),
+        &token,
+        "posts.csv",
+        csv.as_bytes(),
+    )
+    .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 422);
+    let report: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(report["valid"], false);
+    let rows: Vec<i64> = report["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["row"].as_i64().unwrap())
+        .collect();
+    assert_eq!(rows, vec![2, 3, 4]);
+
+    let posts: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM posts WHERE board_id = $1")
+        .bind(board_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(posts, 0);
+}
+
+#[actix_web::test]
+async fn canny_import_creates_boards_placeholders_and_votes() {
+    let pool = common::create_pool().await;
+    let (token, user_id, org_id) = common::register_user(&pool).await;
+    let (existing_board, _slug) = common::create_board(&pool, &token, org_id, "Bugs").await;
+    let email: String = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let (_outsider_token, outsider_id, _outsider_org) = common::register_user(&pool).await;
+    let outsider: String = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+        .bind(outsider_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let voter = format!("voter-{}@example.com", Uuid::new_v4());
+
+    let export = serde_json::json!({
+        "posts": [
+            {
+                "id": "p1",
+                "title": "Dark mode",
+                "status": "under review",
+                "board": { "name": "Feature Requests" },
+                "author": { "name": "Ada", "email": email },
+                "score": 2,
+                "comments": [{ "value": "Internal", "internal": true }]
+            },
+            {
+                "id": "p2",
+                "title": "Crash on save",
+                "status": "complete",
+                "board": { "name": "bugs" },
+                "author": { "name": "Someone", "email": outsider }
+            }
+        ],
+        "votes": [
+            { "post": { "id": "p1" }, "voter": { "name": "Vee", "email": voter } },
+            { "post": { "id": "p2" }, "voter": { "name": "Vee", "email": voter } },
+            { "post": { "id": "p2" }, "voter": { "name": "Ada", "email": email } }
+        ],
+        "comments": [
+            { "post": { "id": "p1" }, "value": "+1", "author": { "name": "Vee", "email": voter } }
+        ]
+    });
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(
+        &format!("/api/orgs/{org_id}/import?source=canny"),
+        &token,
+        "canny.json",
+        export.to_string().as_bytes(),
+    )
+    .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let report: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(report["posts"], 2);
+    assert_eq!(report["votes"], 3);
+    assert_eq!(report["comments"], 1);
+    assert_eq!(report["placeholder_users"], 1);
+    assert_eq!(
+        report["boards_created"],
+        serde_json::json!(["Feature Requests"])
+    );
+    assert_eq!(
+        report["unmapped_fields"],
+        serde_json::json!(["posts.score"])
+    );
+    assert_eq!(report["warnings"].as_array().unwrap().len(), 2);
+
+    let (placeholder_id, password_hash): (Uuid, Option<String>) =
+        sqlx::query_as("SELECT id, password_hash FROM users WHERE email = $1")
+            .bind(&voter)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(password_hash.is_none());
+
+    let crash: (Uuid, Option<Uuid>, String, i32) = sqlx::query_as(
+        "SELECT board_id, author_id, status, vote_count FROM posts WHERE board_id = $1",
+    )
+    .bind(existing_board)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(crash.1, None);
+    assert_eq!(crash.2, "done");
+    assert_eq!(crash.3, 2);
+
+    let comment_author: Option<Uuid> = sqlx::query_scalar(
+        "SELECT c.author_id FROM comments c JOIN posts p ON p.id = c.post_id \
+         JOIN boards b ON b.id = p.board_id WHERE b.org_id = $1",
+    )
+    .bind(org_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(comment_author, Some(placeholder_id));
+}
+
+#[actix_web::test]
+async fn uservoice_csv_dry_run_reports_without_writing() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let supporter = format!("supporter-{}@example.com", Uuid::new_v4());
+
+    let csv = format!(
+        "Suggestion ID,Forum,Title,Description,Status,Creator Email,Supporters,Votes\n\
+         1,Mobile,Offline mode,For trains,Started,{supporter},{supporter},4\n\
+         2,Mobile,Widgets,,planned,,,1\n"
+    );
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(
+        &format!("/api/orgs/{org_id}/import?source=uservoice&dry_run=true"),
+        &token,
+        "suggestions.csv",
+        csv.as_bytes(),
+    )
+    .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let report: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["posts"], 2);
+    assert_eq!(report["votes"], 1);
+    assert_eq!(report["placeholder_users"], 1);
+    assert_eq!(report["boards_created"], serde_json::json!(["Mobile"]));
+    assert_eq!(
+        report["unmapped_fields"],
+        serde_json::json!(["suggestion_id", "votes"])
+    );
+
+    let boards: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM boards WHERE org_id = $1")
+        .bind(org_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE email = $1")
+        .bind(&supporter)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!((boards, users), (0, 0));
+
+    let req = upload_request(
+        &format!("/api/orgs/{org_id}/import?source=zendesk"),
+        &token,
+        "suggestions.csv",
+        csv.as_bytes(),
+    )
+    .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+}
+
+#[actix_web::test]
+async fn registering_claims_imported_placeholder() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let voter = format!("claim-{}@example.com", Uuid::new_v4());
+
+    let export = serde_json::json!([{
+        "title": "Keyboard shortcuts",
+        "board": "Ideas",
+        "voters": [{ "email": voter }]
+    }]);
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(
+        &format!("/api/orgs/{org_id}/import?source=canny&format=json"),
+        &token,
+        "export.txt",
+        export.to_string().as_bytes(),
+    )
+    .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let req = actix_test::TestRequest::post()
+        .uri("/api/auth/register")
+        .set_json(serde_json::json!({
+            "email": voter,
+            "name": format!("Claimed {}", Uuid::new_v4()),
+            "password": "password123"
+        }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+    let body: Value = actix_test::read_body_json(resp).await;
+    let claimed_id: Uuid = body["user"]["id"].as_str().unwrap().parse().unwrap();
+
+    let votes: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM votes WHERE user_id = $1")
+        .bind(claimed_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(votes, 1);
+
+    let req = actix_test::TestRequest::post()
+        .uri("/api/auth/register")
+        .set_json(serde_json::json!({
+            "email": voter,
+            "name": "Again",
+            "password": "password123"
+        }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+}
+
+// ============================================================
+// Post tests
+// ============================================================
+
+#[actix_web::test]
+async fn create_and_get_post() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Posts Board").await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/boards/{board_id}/posts"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({
+            "title": "Dark mode please",
+            "description": "We need dark mode"
+        }))
+        .to_request();
+
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["title"], "Dark mode please");
+    assert_eq!(body["status"], "open");
+    assert_eq!(body["vote_count"], 0);
+    let post_id = body["id"].as_str().unwrap();
+
+    // Get the post
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/boards/{board_id}/posts/{post_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["title"], "Dark mode please");
+}
+
+#[actix_web::test]
+async fn list_posts_with_sort_and_filter() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Sorted Board").await;
+
+    common::create_post(&pool, &token, board_id, "Post A").await;
+    common::create_post(&pool, &token, board_id, "Post B").await;
+
+    // List with default sort
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/boards/{board_id}/posts"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body.as_array().unwrap().len(), 2);
+
+    // List with status filter
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/boards/{board_id}/posts?status=planned"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body.as_array().unwrap().len(), 0);
+
+    // List with sort=recent
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/boards/{board_id}/posts?sort=recent"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn update_post_by_author() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Update Board").await;
+    let post_id = common::create_post(&pool, &token, board_id, "Original Title").await;
+
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
     let req = actix_test::TestRequest::put()
         .uri(&format!("/api/boards/{board_id}/posts/{post_id}"))
@@ -546,6 +1369,42 @@ async fn update_status_invalid_value_returns_400() {
     assert_eq!(resp.status(), 400);
 }
 
+#[actix_web::test]
+async fn post_description_rendered_as_sanitized_html() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Markdown Board").await;
+
+    let description = "**Bold** idea <script>alert('x')</script> [link](https://example.com)";
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/boards/{board_id}/posts"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({ "title": "Markdown", "description": description }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["description_markdown"], description);
+    let html = body["description_html"].as_str().unwrap();
+    assert!(html.contains("<strong>Bold</strong>"));
+    assert!(html.contains(r#"rel="nofollow noopener noreferrer""#));
+    assert!(!html.contains("script"));
+
+    // List preview is plain text, not raw markdown
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/boards/{board_id}/posts"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    let body: Value = actix_test::read_body_json(resp).await;
+    let preview = body[0]["description_preview"].as_str().unwrap();
+    assert!(preview.starts_with("Bold idea"));
+    assert!(!preview.contains("**"));
+    assert!(!preview.contains('<'));
+}
+
 // ============================================================
 // Vote tests
 // ============================================================
@@ -612,6 +1471,84 @@ async fn multiple_users_can_vote() {
     assert_eq!(body["vote_count"], 2);
 }
 
+#[actix_web::test]
+async fn counter_reconciliation_reports_and_repairs_drift() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (member_token, _member_id) = common::register_member(&pool, org_id).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Counters").await;
+    let drifted = common::create_post(&pool, &token, board_id, "Drifted").await;
+    let healthy = common::create_post(&pool, &token, board_id, "Healthy").await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    for post_id in [drifted, healthy] {
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/posts/{post_id}/vote"))
+            .insert_header(("Authorization", format!("Bearer {member_token}")))
+            .to_request();
+        actix_test::call_service(&app, req).await;
+    }
+    sqlx::query("UPDATE posts SET vote_count = 7, comment_count = 3 WHERE id = $1")
+        .bind(drifted)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/orgs/{org_id}/counters/reconcile"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 403);
+
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/orgs/{org_id}/counters/reconcile"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let report: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(report["posts_checked"], 2);
+    assert_eq!(report["fixed"], false);
+    assert_eq!(report["drifted"].as_array().unwrap().len(), 1);
+    assert_eq!(report["drifted"][0]["post_id"], drifted.to_string());
+    assert_eq!(report["drifted"][0]["stored_vote_count"], 7);
+    assert_eq!(report["drifted"][0]["actual_vote_count"], 1);
+    assert_eq!(report["drifted"][0]["actual_comment_count"], 0);
+
+    let counts = |post_id: Uuid| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query_as::<_, (i32, i32)>(
+                "SELECT vote_count, comment_count FROM posts WHERE id = $1",
+            )
+            .bind(post_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+        }
+    };
+    assert_eq!(counts(drifted).await, (7, 3));
+
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/orgs/{org_id}/counters/reconcile?fix=true"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    let report: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(report["fixed"], true);
+    assert_eq!(counts(drifted).await, (1, 0));
+    assert_eq!(counts(healthy).await, (1, 0));
+
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/orgs/{org_id}/counters/reconcile"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    let report: Value = actix_test::read_body_json(resp).await;
+    assert!(report["drifted"].as_array().unwrap().is_empty());
+}
+
 // ============================================================
 // Comment tests
 // ============================================================
@@ -633,7 +1570,8 @@ async fn create_and_list_comments() {
     let resp = actix_test::call_service(&app, req).await;
     assert_eq!(resp.status(), 201);
     let body: Value = actix_test::read_body_json(resp).await;
-    assert_eq!(body["body"], "Great idea!");
+    assert_eq!(body["body_markdown"], "Great idea!");
+    assert_eq!(body["body_html"], "<p>Great idea!</p>\n");
     assert_eq!(body["is_admin_reply"], true); // user is admin
 
     // List comments
@@ -755,112 +1693,284 @@ async fn comment_increments_post_count() {
 }
 
 // ============================================================
-// Tag tests
+// Mention tests
 // ============================================================
 
 #[actix_web::test]
-async fn create_and_list_tags() {
+async fn comment_mention_resolves_and_notifies() {
     let pool = common::create_pool().await;
-    let (token, _user_id, org_id) = common::register_user(&pool).await;
-    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Tag Board").await;
+    let (admin_token, _admin_id, org_id) = common::register_user(&pool).await;
+    let (member_token, member_id) = common::register_member(&pool, org_id).await;
+    let (board_id, _slug) =
+        common::create_board(&pool, &admin_token, org_id, "Mention Board").await;
+    let post_id = common::create_post(&pool, &admin_token, board_id, "Mention Post").await;
+
+    let member_name: String = sqlx::query_scalar("SELECT name FROM users WHERE id = $1")
+        .bind(member_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
 
-    // Create tag
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
     let req = actix_test::TestRequest::post()
-        .uri(&format!("/api/boards/{board_id}/tags"))
-        .insert_header(("Authorization", format!("Bearer {token}")))
-        .set_json(serde_json::json!({ "name": "UX", "color": "#8b5cf6" }))
+        .uri(&format!("/api/posts/{post_id}/comments"))
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .set_json(serde_json::json!({ "body": format!("Hey @{member_name}, thoughts?") }))
         .to_request();
     let resp = actix_test::call_service(&app, req).await;
     assert_eq!(resp.status(), 201);
     let body: Value = actix_test::read_body_json(resp).await;
-    assert_eq!(body["name"], "UX");
-    assert_eq!(body["color"], "#8b5cf6");
+    let mentions = body["mentions"].as_array().unwrap();
+    assert_eq!(mentions.len(), 1);
+    assert_eq!(mentions[0]["user_id"], member_id.to_string());
+    assert_eq!(mentions[0]["start"], 4);
+    assert_eq!(mentions[0]["end"], 5 + member_name.chars().count());
 
-    // List tags
+    // Mentioned user got a notification
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
     let req = actix_test::TestRequest::get()
-        .uri(&format!("/api/boards/{board_id}/tags"))
-        .insert_header(("Authorization", format!("Bearer {token}")))
+        .uri("/api/notifications")
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
         .to_request();
     let resp = actix_test::call_service(&app, req).await;
     assert_eq!(resp.status(), 200);
     let body: Value = actix_test::read_body_json(resp).await;
-    assert_eq!(body.as_array().unwrap().len(), 1);
+    let notifications = body.as_array().unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0]["kind"], "mention");
+    assert_eq!(notifications[0]["post_id"], post_id.to_string());
+    assert_eq!(notifications[0]["read"], false);
 }
 
 #[actix_web::test]
-async fn tag_default_color() {
+async fn post_description_mentions_returned_in_detail() {
     let pool = common::create_pool().await;
-    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (admin_token, _admin_id, org_id) = common::register_user(&pool).await;
+    let (_member_token, member_id) = common::register_member(&pool, org_id).await;
     let (board_id, _slug) =
-        common::create_board(&pool, &token, org_id, "Default Color Board").await;
+        common::create_board(&pool, &admin_token, org_id, "Post Mention Board").await;
+
+    let member_name: String = sqlx::query_scalar("SELECT name FROM users WHERE id = $1")
+        .bind(member_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
 
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
     let req = actix_test::TestRequest::post()
-        .uri(&format!("/api/boards/{board_id}/tags"))
-        .insert_header(("Authorization", format!("Bearer {token}")))
-        .set_json(serde_json::json!({ "name": "NoColor" }))
+        .uri(&format!("/api/boards/{board_id}/posts"))
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .set_json(serde_json::json!({
+            "title": "Mentioning",
+            "description": format!("@{} and @nobody-here", member_name.to_lowercase())
+        }))
         .to_request();
     let resp = actix_test::call_service(&app, req).await;
     assert_eq!(resp.status(), 201);
     let body: Value = actix_test::read_body_json(resp).await;
-    assert_eq!(body["color"], "#6366f1");
+    let mentions = body["mentions"].as_array().unwrap();
+    assert_eq!(mentions.len(), 1);
+    assert_eq!(mentions[0]["name"], member_name);
+    assert_eq!(mentions[0]["start"], 0);
 }
 
 #[actix_web::test]
-async fn delete_tag() {
+async fn member_autocomplete_scoped_to_org() {
     let pool = common::create_pool().await;
-    let (token, _user_id, org_id) = common::register_user(&pool).await;
-    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Del Tag Board").await;
+    let (admin_token, _admin_id, org_id) = common::register_user(&pool).await;
+    let (_member_token, member_id) = common::register_member(&pool, org_id).await;
+    let (outsider_token, _outsider_id, _other_org) = common::register_user(&pool).await;
 
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
-    let req = actix_test::TestRequest::post()
-        .uri(&format!("/api/boards/{board_id}/tags"))
-        .insert_header(("Authorization", format!("Bearer {token}")))
-        .set_json(serde_json::json!({ "name": "ToDelete" }))
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/orgs/{org_id}/members?q=memb"))
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
         .to_request();
     let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
     let body: Value = actix_test::read_body_json(resp).await;
-    let tag_id = body["id"].as_str().unwrap();
+    let members = body.as_array().unwrap();
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0]["id"], member_id.to_string());
 
+    // Non-members can't search the org
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
-    let req = actix_test::TestRequest::delete()
-        .uri(&format!("/api/tags/{tag_id}"))
-        .insert_header(("Authorization", format!("Bearer {token}")))
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/orgs/{org_id}/members?q=memb"))
+        .insert_header(("Authorization", format!("Bearer {outsider_token}")))
         .to_request();
     let resp = actix_test::call_service(&app, req).await;
-    assert_eq!(resp.status(), 204);
+    assert_eq!(resp.status(), 403);
 }
 
+// ============================================================
+// Reaction tests
+// ============================================================
+
 #[actix_web::test]
-async fn assign_and_remove_tag_from_post() {
+async fn toggle_post_reaction_on_and_off() {
     let pool = common::create_pool().await;
     let (token, _user_id, org_id) = common::register_user(&pool).await;
-    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Tag Assign Board").await;
-    let post_id = common::create_post(&pool, &token, board_id, "Tagged Post").await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Reaction Board").await;
+    let post_id = common::create_post(&pool, &token, board_id, "Reaction Post").await;
 
-    // Create tag
+    // React
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
     let req = actix_test::TestRequest::post()
-        .uri(&format!("/api/boards/{board_id}/tags"))
+        .uri(&format!("/api/posts/{post_id}/reactions"))
         .insert_header(("Authorization", format!("Bearer {token}")))
-        .set_json(serde_json::json!({ "name": "Backend" }))
+        .set_json(serde_json::json!({ "emoji": "🎉" }))
         .to_request();
     let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
     let body: Value = actix_test::read_body_json(resp).await;
-    let tag_id = body["id"].as_str().unwrap();
+    assert_eq!(body["reacted"], true);
+    assert_eq!(body["count"], 1);
 
-    // Assign tag to post
+    // Detail includes the aggregated reaction
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/boards/{board_id}/posts/{post_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    let body: Value = actix_test::read_body_json(resp).await;
+    let reactions = body["reactions"].as_array().unwrap();
+    assert_eq!(reactions.len(), 1);
+    assert_eq!(reactions[0]["emoji"], "🎉");
+    assert_eq!(reactions[0]["count"], 1);
+    assert_eq!(reactions[0]["reacted"], true);
+
+    // Un-react
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
     let req = actix_test::TestRequest::post()
-        .uri(&format!("/api/posts/{post_id}/tags/{tag_id}"))
+        .uri(&format!("/api/posts/{post_id}/reactions"))
         .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({ "emoji": "🎉" }))
         .to_request();
     let resp = actix_test::call_service(&app, req).await;
-    assert_eq!(resp.status(), 204);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["reacted"], false);
+    assert_eq!(body["count"], 0);
+}
 
-    // Verify tag shows on post
+#[actix_web::test]
+async fn reaction_with_unlisted_emoji_returns_400() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Bad Reaction Board").await;
+    let post_id = common::create_post(&pool, &token, board_id, "Bad Reaction Post").await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/posts/{post_id}/reactions"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({ "emoji": "<script>" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+}
+
+#[actix_web::test]
+async fn comment_reactions_listed_per_user() {
+    let pool = common::create_pool().await;
+    let (admin_token, _admin_id, org_id) = common::register_user(&pool).await;
+    let (member_token, _member_id) = common::register_member(&pool, org_id).await;
+    let (board_id, _slug) =
+        common::create_board(&pool, &admin_token, org_id, "Comment Reaction Board").await;
+    let post_id = common::create_post(&pool, &admin_token, board_id, "Comment Reaction Post").await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/posts/{post_id}/comments"))
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .set_json(serde_json::json!({ "body": "React to me" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    let body: Value = actix_test::read_body_json(resp).await;
+    let comment_id = body["id"].as_str().unwrap().to_string();
+
+    // Both users react with the same emoji
+    for token in [&admin_token, &member_token] {
+        let app = actix_test::init_service(common::build_app(pool.clone())).await;
+        let req = actix_test::TestRequest::post()
+            .uri(&format!("/api/comments/{comment_id}/reactions"))
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .set_json(serde_json::json!({ "emoji": "👍" }))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+    }
+
+    // Member removes theirs; admin should still see their own reaction
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/comments/{comment_id}/reactions"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .set_json(serde_json::json!({ "emoji": "👍" }))
+        .to_request();
+    actix_test::call_service(&app, req).await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/posts/{post_id}/comments"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    let body: Value = actix_test::read_body_json(resp).await;
+    let reactions = body[0]["reactions"].as_array().unwrap();
+    assert_eq!(reactions.len(), 1);
+    assert_eq!(reactions[0]["count"], 1);
+    assert_eq!(reactions[0]["reacted"], false);
+}
+
+// ============================================================
+// Attachment tests
+// ============================================================
+
+const PNG_BYTES: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+
+fn upload_request(uri: &str, token: &str, filename: &str, data: &[u8]) -> actix_test::TestRequest {
+    let (body, headers) = actix_multipart::test::create_form_data_payload_and_headers(
+        "file",
+        Some(filename.to_string()),
+        None,
+        actix_web::web::Bytes::copy_from_slice(data),
+    );
+    let mut req = actix_test::TestRequest::post()
+        .uri(uri)
+        .insert_header(("Authorization", format!("Bearer {token}")));
+    for (name, value) in headers.iter() {
+        req = req.insert_header((name.clone(), value.clone()));
+    }
+    req.set_payload(body)
+}
+
+#[actix_web::test]
+async fn upload_and_download_post_attachment() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Attachment Board").await;
+    let post_id = common::create_post(&pool, &token, board_id, "Attachment Post").await;
+
+    // Extension and client-declared type are ignored; the content is sniffed
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(
+        &format!("/api/posts/{post_id}/attachments"),
+        &token,
+        "../../screenshot.txt",
+        PNG_BYTES,
+    )
+    .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["filename"], "screenshot.txt");
+    assert_eq!(body["content_type"], "image/png");
+    assert_eq!(body["size_bytes"], PNG_BYTES.len());
+    let attachment_id = body["id"].as_str().unwrap().to_string();
+
+    // Detail lists the attachment
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
     let req = actix_test::TestRequest::get()
         .uri(&format!("/api/boards/{board_id}/posts/{post_id}"))
@@ -868,35 +1978,1765 @@ async fn assign_and_remove_tag_from_post() {
         .to_request();
     let resp = actix_test::call_service(&app, req).await;
     let body: Value = actix_test::read_body_json(resp).await;
-    let tags = body["tags"].as_array().unwrap();
-    assert_eq!(tags.len(), 1);
-    assert_eq!(tags[0]["name"], "Backend");
-
-    // Remove tag from post
+    let attachments = body["attachments"].as_array().unwrap();
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(
+        attachments[0]["url"],
+        format!("/api/attachments/{attachment_id}")
+    );
+
+    // Download returns the original bytes
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/attachments/{attachment_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("content-type").unwrap(), "image/png");
+    assert_eq!(
+        resp.headers().get("x-content-type-options").unwrap(),
+        "nosniff"
+    );
+    let bytes = actix_test::read_body(resp).await;
+    assert_eq!(bytes.as_ref(), PNG_BYTES);
+
+    // Delete, then it is gone
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
     let req = actix_test::TestRequest::delete()
-        .uri(&format!("/api/posts/{post_id}/tags/{tag_id}"))
+        .uri(&format!("/api/attachments/{attachment_id}"))
         .insert_header(("Authorization", format!("Bearer {token}")))
         .to_request();
     let resp = actix_test::call_service(&app, req).await;
     assert_eq!(resp.status(), 204);
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/attachments/{attachment_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
 }
 
 #[actix_web::test]
-async fn member_cannot_create_tag() {
+async fn upload_rejects_unsupported_type_and_oversized_file() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Bad Upload Board").await;
+    let post_id = common::create_post(&pool, &token, board_id, "Bad Upload Post").await;
+    let uri = format!("/api/posts/{post_id}/attachments");
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(&uri, &token, "evil.png", b"MZ\x90\x00\x03\x00").to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 415);
+
+    let too_big = vec![b'a'; common::test_config().storage.max_upload_bytes + 1];
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(&uri, &token, "big.txt", &too_big).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 413);
+
+    // A full quota is not a permissions problem
+    sqlx::query("UPDATE organizations SET storage_used_bytes = $2 WHERE id = $1")
+        .bind(org_id)
+        .bind(common::test_config().storage.org_quota_bytes)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(&uri, &token, "notes.txt", b"one more").to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 507);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["code"], "storage_quota_exceeded");
+}
+
+#[actix_web::test]
+async fn attachments_require_org_membership() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (outsider_token, _outsider_id, _other_org) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Private Files").await;
+    let post_id = common::create_post(&pool, &token, board_id, "Private Post").await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(
+        &format!("/api/posts/{post_id}/attachments"),
+        &token,
+        "notes.txt",
+        b"internal notes",
+    )
+    .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+    let body: Value = actix_test::read_body_json(resp).await;
+    let attachment_id = body["id"].as_str().unwrap().to_string();
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/attachments/{attachment_id}"))
+        .insert_header(("Authorization", format!("Bearer {outsider_token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 403);
+}
+
+// ============================================================
+// Image tests
+// ============================================================
+
+fn png_image(width: u32, height: u32) -> Vec<u8> {
+    let mut buf = std::io::Cursor::new(Vec::new());
+    image::RgbImage::from_pixel(width, height, image::Rgb([30, 144, 255]))
+        .write_to(&mut buf, image::ImageFormat::Png)
+        .unwrap();
+    buf.into_inner()
+}
+
+#[actix_web::test]
+async fn avatar_upload_resizes_and_replaces_previous() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, _org_id) = common::register_user(&pool).await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(
+        "/api/auth/me/avatar",
+        &token,
+        "me.png",
+        &png_image(600, 400),
+    )
+    .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    let first_url = body["avatar_url"].as_str().unwrap().to_string();
+    assert!(first_url.starts_with("/api/images/avatars/"));
+    assert!(first_url.ends_with("/256.jpg"));
+
+    // Served publicly as an immutable, square JPEG
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get().uri(&first_url).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("content-type").unwrap(), "image/jpeg");
+    assert!(
+        resp.headers()
+            .get("cache-control")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .contains("immutable")
+    );
+    let bytes = actix_test::read_body(resp).await;
+    let served = image::load_from_memory(&bytes).unwrap();
+    assert_eq!((served.width(), served.height()), (256, 256));
+
+    // Smaller renditions share the same version directory
+    let thumb_url = first_url.replace("/256.jpg", "/64.jpg");
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get().uri(&thumb_url).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    // Replacing the avatar removes the old files
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(
+        "/api/auth/me/avatar",
+        &token,
+        "me2.png",
+        &png_image(100, 100),
+    )
+    .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_ne!(body["avatar_url"], first_url.as_str());
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get().uri(&first_url).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+}
+
+#[actix_web::test]
+async fn avatar_upload_rejects_non_image() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, _org_id) = common::register_user(&pool).await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request("/api/auth/me/avatar", &token, "me.png", b"not an image").to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 415);
+}
+
+#[actix_web::test]
+async fn org_logo_upload_requires_admin() {
     let pool = common::create_pool().await;
     let (admin_token, _admin_id, org_id) = common::register_user(&pool).await;
     let (member_token, _member_id) = common::register_member(&pool, org_id).await;
-    let (board_id, _slug) = common::create_board(&pool, &admin_token, org_id, "No Tag Board").await;
+    let uri = format!("/api/orgs/{org_id}/logo");
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(&uri, &member_token, "logo.png", &png_image(64, 64)).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 403);
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = upload_request(&uri, &admin_token, "logo.png", &png_image(1024, 256)).to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert!(
+        body["logo_url"]
+            .as_str()
+            .unwrap()
+            .starts_with("/api/images/logos/")
+    );
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::delete()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {admin_token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 204);
+}
+
+// ============================================================
+// Tag tests
+// ============================================================
+
+#[actix_web::test]
+async fn create_and_list_tags() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Tag Board").await;
 
+    // Create tag
     let app = actix_test::init_service(common::build_app(pool.clone())).await;
     let req = actix_test::TestRequest::post()
         .uri(&format!("/api/boards/{board_id}/tags"))
-        .insert_header(("Authorization", format!("Bearer {member_token}")))
-        .set_json(serde_json::json!({ "name": "Nope" }))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({ "name": "UX", "color": "#8b5cf6" }))
         .to_request();
     let resp = actix_test::call_service(&app, req).await;
-    assert_eq!(resp.status(), 403);
+    assert_eq!(resp.status(), 201);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["name"], "UX");
+    assert_eq!(body["color"], "#8b5cf6");
+
+    // List tags
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/boards/{board_id}/tags"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body.as_array().unwrap().len(), 1);
+}
+
+#[actix_web::test]
+async fn tag_default_color() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) =
+        common::create_board(&pool, &token, org_id, "Default Color Board").await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/boards/{board_id}/tags"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({ "name": "NoColor" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["color"], "#6366f1");
+}
+
+#[actix_web::test]
+async fn delete_tag() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Del Tag Board").await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/boards/{board_id}/tags"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({ "name": "ToDelete" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    let body: Value = actix_test::read_body_json(resp).await;
+    let tag_id = body["id"].as_str().unwrap();
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::delete()
+        .uri(&format!("/api/tags/{tag_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 204);
+}
+
+#[actix_web::test]
+async fn assign_and_remove_tag_from_post() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Tag Assign Board").await;
+    let post_id = common::create_post(&pool, &token, board_id, "Tagged Post").await;
+
+    // Create tag
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/boards/{board_id}/tags"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({ "name": "Backend" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    let body: Value = actix_test::read_body_json(resp).await;
+    let tag_id = body["id"].as_str().unwrap();
+
+    // Assign tag to post
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/posts/{post_id}/tags/{tag_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 204);
+
+    // Verify tag shows on post
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/boards/{board_id}/posts/{post_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    let body: Value = actix_test::read_body_json(resp).await;
+    let tags = body["tags"].as_array().unwrap();
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0]["name"], "Backend");
+
+    // Remove tag from post
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::delete()
+        .uri(&format!("/api/posts/{post_id}/tags/{tag_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 204);
+}
+
+#[actix_web::test]
+async fn member_cannot_create_tag() {
+    let pool = common::create_pool().await;
+    let (admin_token, _admin_id, org_id) = common::register_user(&pool).await;
+    let (member_token, _member_id) = common::register_member(&pool, org_id).await;
+    let (board_id, _slug) = common::create_board(&pool, &admin_token, org_id, "No Tag Board").await;
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/boards/{board_id}/tags"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .set_json(serde_json::json!({ "name": "Nope" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 403);
+}
+
+// ============================================================
+// Changelog
+// ============================================================
+
+/// Create a changelog entry via the API and return the response body.
+async fn create_changelog_entry(
+    pool: &sqlx::PgPool,
+    token: &str,
+    org_id: Uuid,
+    body: Value,
+) -> Value {
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/orgs/{org_id}/changelog"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(body)
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+    actix_test::read_body_json(resp).await
+}
+
+#[actix_web::test]
+async fn changelog_drafts_and_scheduled_entries_are_admin_only() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (member_token, _member_id) = common::register_member(&pool, org_id).await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    let draft = create_changelog_entry(
+        &pool,
+        &token,
+        org_id,
+        serde_json::json!({ "title": "Dark mode", "body": "Coming **soon**" }),
+    )
+    .await;
+    assert_eq!(draft["status"], "draft");
+    assert_eq!(draft["body_markdown"], "Coming **soon**");
+    assert_eq!(draft["body_html"], "<p>Coming <strong>soon</strong></p>\n");
+
+    let in_a_week = chrono::Utc::now() + chrono::Duration::days(7);
+    let scheduled = create_changelog_entry(
+        &pool,
+        &token,
+        org_id,
+        serde_json::json!({ "title": "API v2", "body": "New API", "is_draft": false, "published_at": in_a_week }),
+    )
+    .await;
+    assert_eq!(scheduled["status"], "scheduled");
+
+    let published = create_changelog_entry(
+        &pool,
+        &token,
+        org_id,
+        serde_json::json!({ "title": "Exports", "body": "CSV exports", "is_draft": false }),
+    )
+    .await;
+    assert_eq!(published["status"], "published");
+    assert!(published["published_at"].is_string());
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/orgs/{org_id}/changelog"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .to_request();
+    let entries: Value = actix_test::call_and_read_body_json(&app, req).await;
+    let titles: Vec<&str> = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, vec!["Exports"]);
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/orgs/{org_id}/changelog"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let entries: Value = actix_test::call_and_read_body_json(&app, req).await;
+    assert_eq!(entries.as_array().unwrap().len(), 3);
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!(
+            "/api/orgs/{org_id}/changelog/{}",
+            scheduled["id"].as_str().unwrap()
+        ))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/orgs/{org_id}/changelog"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .set_json(serde_json::json!({ "title": "Sneaky", "body": "Nope" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 403);
+
+    // Users outside the organization can't read even published entries,
+    // which would leak the titles of linked posts
+    let (other_token, _other_id, other_org) = common::register_user(&pool).await;
+    for uri in [
+        format!("/api/orgs/{org_id}/changelog"),
+        format!(
+            "/api/orgs/{org_id}/changelog/{}",
+            published["id"].as_str().unwrap()
+        ),
+    ] {
+        let req = actix_test::TestRequest::get()
+            .uri(&uri)
+            .insert_header(("Authorization", format!("Bearer {other_token}")))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 403);
+    }
+
+    // Posts of other organizations can't be linked
+    let (other_board, _slug) =
+        common::create_board(&pool, &other_token, other_org, "Elsewhere").await;
+    let other_post = common::create_post(&pool, &other_token, other_board, "Not yours").await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/orgs/{org_id}/changelog"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({ "title": "Link", "body": "Body", "post_ids": [other_post] }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+}
+
+#[actix_web::test]
+async fn scheduled_changelog_completes_posts_and_notifies_voters() {
+    use rivvo::services::changelog_service;
+
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (member_token, _member_id) = common::register_member(&pool, org_id).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Ideas").await;
+    let post_id = common::create_post(&pool, &token, board_id, "Dark mode").await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/posts/{post_id}/vote"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .to_request();
+    actix_test::call_service(&app, req).await;
+
+    let in_an_hour = chrono::Utc::now() + chrono::Duration::hours(1);
+    let entry = create_changelog_entry(
+        &pool,
+        &token,
+        org_id,
+        serde_json::json!({
+            "title": "Dark mode is here",
+            "body": "Enjoy",
+            "is_draft": false,
+            "published_at": in_an_hour,
+            "complete_posts": true,
+            "post_ids": [post_id]
+        }),
+    )
+    .await;
+    let entry_id: Uuid = entry["id"].as_str().unwrap().parse().unwrap();
+    assert_eq!(entry["posts"][0]["id"], post_id.to_string());
+
+    let pending_publish_jobs = || async {
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/orgs/{org_id}/jobs?status=pending"))
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let jobs: Value = actix_test::call_and_read_body_json(&app, req).await;
+        jobs.as_array()
+            .unwrap()
+            .iter()
+            .filter(|j| j["kind"] == "publish_changelog")
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    let jobs = pending_publish_jobs().await;
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0]["run_at"], entry["published_at"]);
+
+    // Not due yet
+    assert_eq!(
+        changelog_service::announce(&pool, entry_id).await.unwrap(),
+        0
+    );
+
+    // Moving the date replaces the scheduled job
+    let a_minute_ago = chrono::Utc::now() - chrono::Duration::minutes(1);
+    let req = actix_test::TestRequest::put()
+        .uri(&format!("/api/orgs/{org_id}/changelog/{entry_id}"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({
+            "title": "Dark mode is here",
+            "body": "Enjoy",
+            "is_draft": false,
+            "published_at": a_minute_ago,
+            "complete_posts": true,
+            "post_ids": [post_id]
+        }))
+        .to_request();
+    let updated: Value = actix_test::call_and_read_body_json(&app, req).await;
+    assert_eq!(updated["status"], "published");
+    let jobs = pending_publish_jobs().await;
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0]["run_at"], updated["published_at"]);
+
+    assert_eq!(
+        changelog_service::announce(&pool, entry_id).await.unwrap(),
+        1
+    );
+    assert_eq!(
+        changelog_service::announce(&pool, entry_id).await.unwrap(),
+        0,
+        "announced only once"
+    );
+
+    let status: String = sqlx::query_scalar("SELECT status FROM posts WHERE id = $1")
+        .bind(post_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "done");
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/notifications")
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .to_request();
+    let notifications: Value = actix_test::call_and_read_body_json(&app, req).await;
+    let notification = &notifications[0];
+    assert_eq!(notification["kind"], "changelog");
+    assert_eq!(notification["changelog_id"], entry_id.to_string());
+    assert_eq!(notification["changelog_title"], "Dark mode is here");
+    assert_eq!(notification["post_id"], post_id.to_string());
+}
+
+// ============================================================
+// Feeds
+// ============================================================
+
+#[actix_web::test]
+async fn changelog_feed_lists_published_entries_and_honors_conditional_requests() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    create_changelog_entry(
+        &pool,
+        &token,
+        org_id,
+        serde_json::json!({ "title": "Secret draft", "body": "Not yet" }),
+    )
+    .await;
+    create_changelog_entry(
+        &pool,
+        &token,
+        org_id,
+        serde_json::json!({ "title": "Faster & smaller", "body": "Loads **twice** as fast", "is_draft": false }),
+    )
+    .await;
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/feeds/{org_id}/changelog.atom"))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/atom+xml; charset=utf-8"
+    );
+    let etag = resp.headers().get("etag").unwrap().clone();
+    let last_modified = resp.headers().get("last-modified").unwrap().clone();
+    let body = String::from_utf8(actix_test::read_body(resp).await.to_vec()).unwrap();
+    assert!(body.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+    assert!(body.contains("<title>Faster &amp; smaller</title>"));
+    assert!(body.contains("&lt;strong&gt;twice&lt;/strong&gt;"));
+    assert!(!body.contains("Secret draft"));
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/feeds/{org_id}/changelog.atom"))
+        .insert_header(("If-None-Match", etag.clone()))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 304);
+    assert_eq!(resp.headers().get("etag").unwrap(), &etag);
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/feeds/{org_id}/changelog.atom"))
+        .insert_header(("If-Modified-Since", last_modified.clone()))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 304);
+
+    // A mismatched ETag wins over a matching If-Modified-Since
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/feeds/{org_id}/changelog.rss"))
+        .insert_header(("If-None-Match", etag))
+        .insert_header(("If-Modified-Since", last_modified))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/rss+xml; charset=utf-8"
+    );
+    let body = String::from_utf8(actix_test::read_body(resp).await.to_vec()).unwrap();
+    assert!(body.contains("<rss version=\"2.0\""));
+    assert!(body.contains("<title>Faster &amp; smaller</title>"));
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/feeds/{org_id}/changelog.json"))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+}
+
+#[actix_web::test]
+async fn board_feeds_show_new_posts_and_status_changes() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, slug) = common::create_board(&pool, &token, org_id, "Feed Board").await;
+    let post_id = common::create_post(&pool, &token, board_id, "Offline mode").await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/feeds/{org_id}/boards/{slug}/posts.rss"))
+        .to_request();
+    let body = actix_test::call_and_read_body(&app, req).await;
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("<title>Offline mode</title>"));
+    assert!(body.contains(&format!("/posts/{post_id}</link>")));
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/feeds/{org_id}/boards/{slug}/status.atom"))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    // Nothing to date an empty feed by
+    assert!(resp.headers().get("last-modified").is_none());
+    let body = String::from_utf8(actix_test::read_body(resp).await.to_vec()).unwrap();
+    assert!(!body.contains("<entry>"));
+
+    let req = actix_test::TestRequest::put()
+        .uri(&format!("/api/boards/{board_id}/posts/{post_id}/status"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({ "status": "in_progress" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/feeds/{org_id}/boards/{slug}/status.atom"))
+        .to_request();
+    let body = actix_test::call_and_read_body(&app, req).await;
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("<title>Offline mode is now in progress</title>"));
+    assert!(body.contains("&lt;strong&gt;open&lt;/strong&gt; to &lt;strong&gt;in progress"));
+}
+
+#[actix_web::test]
+async fn private_board_feeds_return_404() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, slug) = common::create_board(&pool, &token, org_id, "Hidden Board").await;
+    common::create_post(&pool, &token, board_id, "Internal roadmap").await;
+    sqlx::query("UPDATE boards SET is_private = TRUE WHERE id = $1")
+        .bind(board_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    for feed in ["posts.atom", "status.rss"] {
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("/api/feeds/{org_id}/boards/{slug}/{feed}"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 404);
+    }
+}
+
+// ============================================================
+// Live events
+// ============================================================
+
+/// Read the SSE stream until a frame for `event` arrives and return its data.
+async fn next_event(body: &mut actix_web::body::BoxBody, event: &str) -> Value {
+    use actix_web::body::MessageBody;
+
+    let prefix = format!("event: {event}\ndata: ");
+    let mut buffer = String::new();
+    let read = async {
+        loop {
+            let chunk = std::future::poll_fn(|cx| std::pin::Pin::new(&mut *body).poll_next(cx))
+                .await
+                .expect("event stream ended")
+                .unwrap();
+            buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+            while let Some(end) = buffer.find("\n\n") {
+                let frame: String = buffer.drain(..end + 2).collect();
+                if let Some(data) = frame.strip_prefix(&prefix) {
+                    return serde_json::from_str(data.trim_end()).unwrap();
+                }
+            }
+        }
+    };
+    tokio::time::timeout(std::time::Duration::from_secs(10), read)
+        .await
+        .unwrap_or_else(|_| panic!("no {event} event received"))
+}
+
+#[actix_web::test]
+async fn board_event_stream_pushes_votes_statuses_and_comments() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (member_token, _member_id) = common::register_member(&pool, org_id).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Live Board").await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/boards/{board_id}/events"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "text/event-stream"
+    );
+    let mut body = actix_web::body::MessageBody::boxed(resp.into_body());
+
+    let post_id = common::create_post(&pool, &token, board_id, "Live post").await;
+    let event = next_event(&mut body, "post_created").await;
+    assert_eq!(event["post_id"], post_id.to_string());
+    assert_eq!(event["title"], "Live post");
+
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/posts/{post_id}/vote"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .to_request();
+    actix_test::call_service(&app, req).await;
+    let event = next_event(&mut body, "vote_count_changed").await;
+    assert_eq!(event["vote_count"], 1);
+    assert_eq!(event["board_id"], board_id.to_string());
+
+    let req = actix_test::TestRequest::put()
+        .uri(&format!("/api/boards/{board_id}/posts/{post_id}/status"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({ "status": "planned" }))
+        .to_request();
+    actix_test::call_service(&app, req).await;
+    let event = next_event(&mut body, "status_changed").await;
+    assert_eq!(event["status"], "planned");
+
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/posts/{post_id}/comments"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .set_json(serde_json::json!({ "body": "Can't wait" }))
+        .to_request();
+    actix_test::call_service(&app, req).await;
+    let event = next_event(&mut body, "comment_created").await;
+    assert_eq!(event["comment_count"], 1);
+
+    // Posts completed by publishing a changelog entry
+    let entry = create_changelog_entry(
+        &pool,
+        &token,
+        org_id,
+        serde_json::json!({
+            "title": "Shipped",
+            "body": "Live post is done",
+            "is_draft": false,
+            "complete_posts": true,
+            "post_ids": [post_id]
+        }),
+    )
+    .await;
+    let entry_id: Uuid = entry["id"].as_str().unwrap().parse().unwrap();
+    rivvo::services::changelog_service::announce(&pool, entry_id)
+        .await
+        .unwrap();
+    let event = next_event(&mut body, "status_changed").await;
+    assert_eq!(event["post_id"], post_id.to_string());
+    assert_eq!(event["status"], "done");
+}
+
+#[actix_web::test]
+async fn event_streams_require_org_membership() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (outsider_token, _outsider_id, _other_org) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Members Only").await;
+    let post_id = common::create_post(&pool, &token, board_id, "Members post").await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    for uri in [
+        format!("/api/boards/{board_id}/events"),
+        format!("/api/posts/{post_id}/events"),
+    ] {
+        let req = actix_test::TestRequest::get()
+            .uri(&uri)
+            .insert_header(("Authorization", format!("Bearer {outsider_token}")))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 403);
+
+        let req = actix_test::TestRequest::get().uri(&uri).to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 401);
+    }
+}
+
+// ============================================================
+// Background jobs
+// ============================================================
+
+#[actix_web::test]
+async fn failed_jobs_back_off_then_dead_letter_and_can_be_retried() {
+    use rivvo::models::job::NewJob;
+    use rivvo::services::job_service;
+
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (member_token, _member_id) = common::register_member(&pool, org_id).await;
+
+    // A kind no worker knows, so only this test claims it
+    let kind = format!("test_{}", Uuid::new_v4().simple());
+    let mut conn = pool.acquire().await.unwrap();
+    let job = job_service::enqueue(
+        &mut conn,
+        NewJob {
+            kind: kind.clone(),
+            org_id: Some(org_id),
+            max_attempts: 2,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    drop(conn);
+
+    let claimed = job_service::claim_next(&pool, &[&kind])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(claimed.id, job.id);
+    assert_eq!(claimed.attempts, 1);
+    let failed = job_service::record_failure(&pool, &claimed, "boom")
+        .await
+        .unwrap();
+    assert_eq!(failed.status, "pending");
+    assert!(failed.run_at > chrono::Utc::now() + chrono::Duration::seconds(5));
+    assert!(
+        job_service::claim_next(&pool, &[&kind])
+            .await
+            .unwrap()
+            .is_none(),
+        "backing off"
+    );
+
+    sqlx::query("UPDATE jobs SET run_at = now() WHERE id = $1")
+        .bind(job.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let claimed = job_service::claim_next(&pool, &[&kind])
+        .await
+        .unwrap()
+        .unwrap();
+    let dead = job_service::record_failure(&pool, &claimed, "boom again")
+        .await
+        .unwrap();
+    assert_eq!(dead.status, "dead");
+    assert_eq!(dead.attempts, 2);
+
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/orgs/{org_id}/jobs?status=dead"))
+        .insert_header(("Authorization", format!("Bearer {member_token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 403);
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/orgs/{org_id}/jobs?status=dead"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let jobs: Value = actix_test::read_body_json(resp).await;
+    let jobs = jobs.as_array().unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0]["id"], job.id.to_string());
+    assert_eq!(jobs[0]["last_error"], "boom again");
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/orgs/{org_id}/jobs?status=stuck"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/orgs/{org_id}/jobs/{}/retry", job.id))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let retried: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(retried["status"], "pending");
+    assert_eq!(retried["attempts"], 0);
+
+    let claimed = job_service::claim_next(&pool, &[&kind])
+        .await
+        .unwrap()
+        .unwrap();
+    job_service::complete(&pool, &claimed).await.unwrap();
+
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/orgs/{org_id}/jobs/{}/retry", job.id))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400, "completed jobs are not retried");
+
+    // Other organizations' jobs are not visible
+    let (_other_token, _other_id, other_org) = common::register_user(&pool).await;
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/orgs/{other_org}/jobs/{}", job.id))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 403);
+}
+
+#[actix_web::test]
+async fn stale_jobs_are_reclaimed_until_out_of_attempts() {
+    use rivvo::models::job::NewJob;
+    use rivvo::services::job_service;
+
+    let pool = common::create_pool().await;
+    let kind = format!("test_{}", Uuid::new_v4().simple());
+    let mut conn = pool.acquire().await.unwrap();
+    let job = job_service::enqueue(
+        &mut conn,
+        NewJob {
+            kind: kind.clone(),
+            max_attempts: 2,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    drop(conn);
+
+    // The worker dies without recording an outcome
+    let lose_worker = || {
+        sqlx::query("UPDATE jobs SET locked_at = now() - interval '1 hour' WHERE id = $1")
+            .bind(job.id)
+            .execute(&pool)
+    };
+    job_service::claim_next(&pool, &[&kind])
+        .await
+        .unwrap()
+        .unwrap();
+    lose_worker().await.unwrap();
+    let reclaimed = job_service::claim_next(&pool, &[&kind])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(reclaimed.id, job.id);
+    assert_eq!(reclaimed.attempts, 2);
+
+    lose_worker().await.unwrap();
+    assert!(
+        job_service::claim_next(&pool, &[&kind])
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let dead = job_service::get_job(&pool, None, job.id).await.unwrap();
+    assert_eq!(dead.status, "dead");
+    assert_eq!(dead.attempts, 2);
+    assert!(dead.last_error.is_some());
+}
+
+#[actix_web::test]
+async fn workers_deliver_queued_email() {
+    use rivvo::mailer::Email;
+    use rivvo::services::job_service;
+
+    let pool = common::create_pool().await;
+    let to = format!("queued-{}@example.com", Uuid::new_v4());
+    let mut conn = pool.acquire().await.unwrap();
+    job_service::enqueue_email(
+        &mut conn,
+        &Email {
+            to: to.clone(),
+            subject: "Queued".to_string(),
+            body: "Sent by a worker".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    drop(conn);
+
+    let delivered = common::deliver_email_to(&pool, &to).await;
+    assert_eq!(delivered.expect("email delivered").subject, "Queued");
+    let status: String = sqlx::query_scalar(
+        "SELECT status FROM jobs WHERE kind = 'send_email' AND payload->>'to' = $1",
+    )
+    .bind(&to)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(status, "completed");
+}
+
+// ============================================================
+// OpenAPI
+// ============================================================
+
+/// `(method, path)` of every `.route(...)` in the body of `fn {name}` in
+/// `source`, with `prefix` and scope prefixes applied and regex parameters
+/// like `{path:.*}` reduced to `{path}`.
+fn registered_routes(
+    source: &str,
+    name: &str,
+    prefix: &str,
+) -> std::collections::BTreeSet<(String, String)> {
+    let (_, signature) = source.split_once(&format!("fn {name}(")).unwrap();
+    let (_, body) = signature.split_once('{').unwrap();
+    let (source, _) = body.split_once("\n}\n").unwrap();
+
+    let mut routes = std::collections::BTreeSet::new();
+    // (paren depth the scope was opened at, prefix)
+    let mut scopes: Vec<(usize, String)> = vec![(0, prefix.to_string())];
+    let mut depth = 0;
+    let mut rest = source;
+
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("//") {
+            rest = rest.split_once('\n').map_or("", |(_, after)| after);
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("web::scope(\"") {
+            let (prefix, _) = after.split_once('"').unwrap();
+            scopes.push((depth, prefix.to_string()));
+        }
+        if let Some(after) = rest.strip_prefix(".route(") {
+            let after = after.trim_start().strip_prefix('"').unwrap();
+            let (path, after) = after.split_once('"').unwrap();
+            let after = after.trim_start_matches([',', ' ', '\n']);
+            let method = after
+                .strip_prefix("web::")
+                .and_then(|m| m.split_once('('))
+                .map(|(m, _)| m.to_uppercase())
+                .unwrap();
+            let prefix: String = scopes.iter().map(|(_, p)| p.as_str()).collect();
+            routes.insert((method, normalize_route(&format!("{prefix}{path}"))));
+        }
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                while scopes.last().is_some_and(|(opened, _)| *opened > depth) {
+                    scopes.pop();
+                }
+            }
+            '"' => {
+                // Skip string contents so parens inside them don't count
+                let (_, after) = rest[1..].split_once('"').unwrap();
+                rest = after;
+                continue;
+            }
+            _ => {}
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    routes
+}
+
+fn normalize_route(path: &str) -> String {
+    let mut normalized = String::new();
+    let (mut in_param, mut in_regex) = (false, false);
+    for c in path.chars() {
+        match c {
+            '{' => in_param = true,
+            '}' => (in_param, in_regex) = (false, false),
+            ':' if in_param => in_regex = true,
+            _ => {}
+        }
+        if !in_regex {
+            normalized.push(c);
+        }
+    }
+    normalized
+}
+
+#[actix_web::test]
+async fn every_route_is_documented_in_openapi() {
+    use utoipa::OpenApi;
+
+    let source = include_str!("../src/lib.rs");
+    let mut registered = registered_routes(source, "api_v1", "/api/v1");
+    registered.extend(registered_routes(source, "api_v2", "/api/v2"));
+    assert!(registered.len() > 50, "route parser found {registered:?}");
+
+    let spec = rivvo::openapi::ApiDoc::openapi();
+    let mut documented = std::collections::BTreeSet::new();
+    for (path, item) in &spec.paths.paths {
+        for (method, operation) in [
+            ("GET", &item.get),
+            ("POST", &item.post),
+            ("PUT", &item.put),
+            ("DELETE", &item.delete),
+            ("PATCH", &item.patch),
+        ] {
+            if operation.is_some() {
+                documented.insert((method.to_string(), path.clone()));
+            }
+        }
+    }
+
+    let undocumented: Vec<_> = registered.difference(&documented).collect();
+    assert!(
+        undocumented.is_empty(),
+        "routes missing from the OpenAPI spec: {undocumented:?}"
+    );
+    let stale: Vec<_> = documented.difference(&registered).collect();
+    assert!(
+        stale.is_empty(),
+        "documented routes that are not registered: {stale:?}"
+    );
+}
+
+#[actix_web::test]
+async fn openapi_spec_and_docs_ui_are_served() {
+    let pool = common::create_pool().await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/openapi.json")
+        .to_request();
+    let spec: Value = actix_test::call_and_read_body_json(&app, req).await;
+    assert!(spec["openapi"].as_str().unwrap().starts_with("3.1"));
+    assert_eq!(
+        spec["paths"]["/api/v1/posts/{post_id}/vote"]["post"]["responses"]["200"]["content"]["application/json"]
+            ["schema"]["$ref"],
+        "#/components/schemas/VoteResult"
+    );
+    assert_eq!(
+        spec["components"]["schemas"]["CreatePostRequest"]["required"],
+        serde_json::json!(["title"])
+    );
+    assert_eq!(
+        spec["paths"]["/api/v1/posts/{post_id}/vote"]["post"]["responses"]["404"]["content"]["application/problem+json"]
+            ["schema"]["$ref"],
+        "#/components/schemas/ErrorResponse"
+    );
+    // Public endpoints opt out of the bearer token
+    assert_eq!(
+        spec["paths"]["/api/v1/auth/login"]["post"]["security"],
+        serde_json::json!([{}])
+    );
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/docs/")
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body = actix_test::read_body(resp).await;
+    assert!(String::from_utf8_lossy(&body).contains("swagger-ui"));
+}
+
+#[actix_web::test]
+async fn cors_allows_configured_origin_only() {
+    let pool = common::create_pool().await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let origin = common::test_config().server.cors_origin;
+
+    let req = actix_test::TestRequest::default()
+        .method(actix_web::http::Method::OPTIONS)
+        .uri("/api/health")
+        .insert_header(("Origin", origin.as_str()))
+        .insert_header(("Access-Control-Request-Method", "GET"))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers().get("access-control-allow-origin").unwrap(),
+        origin.as_str()
+    );
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/health")
+        .insert_header(("Origin", "http://evil.example"))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert!(resp.headers().get("access-control-allow-origin").is_none());
+}
+
+// ============================================================
+// Error responses
+// ============================================================
+
+#[actix_web::test]
+async fn validation_errors_are_problem_details_per_field() {
+    let pool = common::create_pool().await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    let req = actix_test::TestRequest::post()
+        .uri("/api/auth/register")
+        .insert_header(("X-Request-Id", "client-req-42"))
+        .set_json(serde_json::json!({
+            "email": "not-an-email",
+            "name": "Valid Name",
+            "password": "short",
+        }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 422);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/problem+json"
+    );
+    assert_eq!(resp.headers().get("x-request-id").unwrap(), "client-req-42");
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["type"], "about:blank");
+    assert_eq!(body["title"], "Unprocessable Entity");
+    assert_eq!(body["status"], 422);
+    assert_eq!(body["code"], "validation_failed");
+    assert_eq!(body["request_id"], "client-req-42");
+    assert_eq!(
+        body["errors"],
+        serde_json::json!([
+            { "field": "email", "code": "email", "message": "Invalid email address" },
+            {
+                "field": "password",
+                "code": "length",
+                "message": "Password must be between 8 and 128 characters",
+            },
+        ])
+    );
+}
+
+#[actix_web::test]
+async fn extractor_failures_are_problem_details() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    // (request, status, code)
+    let cases = [
+        (
+            actix_test::TestRequest::get().uri("/api/auth/me"),
+            401,
+            "unauthorized",
+        ),
+        (
+            actix_test::TestRequest::get()
+                .uri("/api/auth/me")
+                .insert_header(("Authorization", "Bearer not-a-jwt")),
+            401,
+            "unauthorized",
+        ),
+        (
+            actix_test::TestRequest::post()
+                .uri("/api/auth/login")
+                .insert_header(("Content-Type", "application/json"))
+                .set_payload("{not json"),
+            400,
+            "bad_request",
+        ),
+        (
+            actix_test::TestRequest::post()
+                .uri("/api/auth/login")
+                .insert_header(("Content-Type", "text/plain"))
+                .set_payload("{}"),
+            415,
+            "unsupported_media_type",
+        ),
+        (
+            actix_test::TestRequest::get()
+                .uri("/api/posts/not-a-uuid")
+                .insert_header(("Authorization", format!("Bearer {token}"))),
+            404,
+            "not_found",
+        ),
+        (
+            actix_test::TestRequest::get()
+                .uri(&format!("/api/orgs/{org_id}/jobs?limit=lots"))
+                .insert_header(("Authorization", format!("Bearer {token}"))),
+            400,
+            "bad_request",
+        ),
+    ];
+
+    for (req, status, code) in cases {
+        let resp = actix_test::call_service(&app, req.to_request()).await;
+        assert_eq!(resp.status(), status, "{code}");
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "application/problem+json"
+        );
+        let request_id = resp
+            .headers()
+            .get("x-request-id")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body: Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body["status"], status);
+        assert_eq!(body["code"], code);
+        assert_eq!(body["request_id"], request_id.as_str());
+        assert_eq!(body["error"], body["detail"]);
+    }
+}
+
+// ============================================================
+// Observability
+// ============================================================
+
+async fn scrape_metrics(pool: &sqlx::PgPool) -> String {
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let req = actix_test::TestRequest::get().uri("/metrics").to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    String::from_utf8(actix_test::read_body(resp).await.to_vec()).unwrap()
+}
+
+/// Value of the first sample of `metric` whose labels include all of `labels`.
+fn sample(metrics: &str, metric: &str, labels: &[&str]) -> Option<f64> {
+    metrics.lines().find_map(|line| {
+        let rest = line.strip_prefix(metric)?;
+        if !(rest.starts_with('{') || rest.starts_with(' ')) {
+            return None;
+        }
+        if !labels.iter().all(|l| rest.contains(l)) {
+            return None;
+        }
+        rest.rsplit(' ').next()?.parse().ok()
+    })
+}
+
+#[actix_web::test]
+async fn metrics_cover_requests_pool_jobs_and_activity() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Metrics").await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+    let before = scrape_metrics(&pool).await;
+
+    let post_id = common::create_post(&pool, &token, board_id, "Counted").await;
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/posts/{post_id}/vote"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    assert_eq!(actix_test::call_service(&app, req).await.status(), 200);
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/posts/{post_id}/comments"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({ "body": "Counted too" }))
+        .to_request();
+    assert_eq!(actix_test::call_service(&app, req).await.status(), 201);
+
+    let after = scrape_metrics(&pool).await;
+    // Other tests run concurrently, so counters only have lower bounds
+    for metric in [
+        "rivvo_posts_created_total",
+        "rivvo_votes_cast_total",
+        "rivvo_comments_created_total",
+    ] {
+        let was = sample(&before, metric, &[]).unwrap_or(0.0);
+        assert!(
+            sample(&after, metric, &[]).unwrap() >= was + 1.0,
+            "{metric}"
+        );
+    }
+
+    // Labelled with the route pattern, not the concrete path
+    let created = [
+        "method=\"POST\"",
+        "route=\"/api/boards/{board_id}/posts\"",
+        "status=\"201\"",
+    ];
+    assert!(sample(&after, "http_requests_total", &created).unwrap() >= 1.0);
+    assert!(
+        sample(
+            &after,
+            "http_request_duration_seconds_count",
+            &["route=\"/api/posts/{post_id}/vote\""]
+        )
+        .unwrap()
+            >= 1.0
+    );
+    assert!(!after.contains(&post_id.to_string()));
+
+    assert!(sample(&after, "db_pool_max_connections", &[]).unwrap() >= 1.0);
+    assert!(sample(&after, "db_pool_connections", &["state=\"in_use\""]).is_some());
+    assert!(sample(&after, "rivvo_jobs", &["status=\"pending\""]).is_some());
+}
+
+#[actix_web::test]
+async fn metrics_token_is_required_when_configured() {
+    let pool = common::create_pool().await;
+    let mut config = common::test_config();
+    config.telemetry.metrics_token = Some("scrape-secret".to_string());
+    let storage = rivvo::storage::from_config(&config).unwrap();
+    let state = rivvo::AppState::new(
+        pool,
+        config,
+        storage,
+        std::sync::Arc::new(common::MemoryMailer::default()),
+        Default::default(),
+    );
+    let app = actix_test::init_service(rivvo::build_app(state)).await;
+
+    for (auth, status) in [
+        (None, 401),
+        (Some("Bearer wrong"), 401),
+        (Some("Bearer scrape-secret"), 200),
+    ] {
+        let mut req = actix_test::TestRequest::get().uri("/metrics");
+        if let Some(auth) = auth {
+            req = req.insert_header(("Authorization", auth));
+        }
+        let resp = actix_test::call_service(&app, req.to_request()).await;
+        assert_eq!(resp.status(), status, "{auth:?}");
+    }
+}
+
+#[actix_web::test]
+async fn request_ids_are_propagated_or_generated() {
+    let pool = common::create_pool().await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/health")
+        .insert_header(("X-Request-Id", "upstream-7f3a"))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.headers().get("x-request-id").unwrap(), "upstream-7f3a");
+
+    // Missing or unsafe ids are replaced with a fresh UUID
+    for sent in [None, Some("bad id\twith spaces")] {
+        let mut req = actix_test::TestRequest::get().uri("/api/health");
+        if let Some(sent) = sent {
+            req = req.insert_header(("X-Request-Id", sent));
+        }
+        let resp = actix_test::call_service(&app, req.to_request()).await;
+        let id = resp
+            .headers()
+            .get("x-request-id")
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert!(Uuid::parse_str(id).is_ok(), "{id}");
+    }
+}
+
+#[actix_web::test]
+async fn liveness_and_readiness_report_build_and_components() {
+    let pool = common::create_pool().await;
+    let app = actix_test::init_service(common::build_app(pool)).await;
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/health/live")
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["status"], "ok");
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/health/ready")
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["status"], "ok");
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    for component in ["database", "migrations", "mail", "storage"] {
+        assert_eq!(body["checks"][component]["status"], "ok", "{component}");
+        assert!(body["checks"][component]["latency_ms"].is_u64());
+    }
+    // The test app runs no job workers
+    assert_eq!(body["checks"]["jobs"]["status"], "disabled");
+}
+
+struct DownMailer;
+
+#[async_trait::async_trait]
+impl rivvo::mailer::Mailer for DownMailer {
+    async fn send(&self, _email: rivvo::mailer::Email) -> Result<(), rivvo::mailer::MailError> {
+        unreachable!()
+    }
+
+    async fn check(&self) -> Result<(), rivvo::mailer::MailError> {
+        Err(rivvo::mailer::MailError("connection refused".to_string()))
+    }
+}
+
+#[actix_web::test]
+async fn readiness_fails_when_a_component_is_down() {
+    let pool = common::create_pool().await;
+    let config = common::test_config();
+    let storage = rivvo::storage::from_config(&config).unwrap();
+    let state = rivvo::AppState::new(
+        pool,
+        config,
+        storage,
+        std::sync::Arc::new(DownMailer),
+        Default::default(),
+    );
+    let app = actix_test::init_service(rivvo::build_app(state)).await;
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/health/ready")
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 503);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["status"], "error");
+    assert_eq!(body["checks"]["mail"]["status"], "error");
+    assert_eq!(body["checks"]["mail"]["detail"], "connection refused");
+    assert_eq!(body["checks"]["database"]["status"], "ok");
+
+    // Liveness doesn't depend on other components
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/health/live")
+        .to_request();
+    assert_eq!(actix_test::call_service(&app, req).await.status(), 200);
+}
+
+#[actix_web::test]
+async fn readiness_fails_while_draining() {
+    let pool = common::create_pool().await;
+    let config = common::test_config();
+    let storage = rivvo::storage::from_config(&config).unwrap();
+    let state = rivvo::AppState::new(
+        pool,
+        config,
+        storage,
+        std::sync::Arc::new(common::MemoryMailer::default()),
+        Default::default(),
+    );
+    let drain = state.drain.clone();
+    let app = actix_test::init_service(rivvo::build_app(state)).await;
+
+    drain.start();
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/health/ready")
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 503);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert_eq!(body["status"], "draining");
+    assert_eq!(body["checks"]["database"]["status"], "ok");
+
+    // In-flight and kept-alive requests are still served
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/health/live")
+        .to_request();
+    assert_eq!(actix_test::call_service(&app, req).await.status(), 200);
+}
+
+// ============================================================
+// API versions
+// ============================================================
+
+#[actix_web::test]
+async fn unversioned_api_is_an_alias_of_v1() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, _org_id) = common::register_user(&pool).await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    for uri in ["/api/v1/auth/me", "/api/auth/me"] {
+        let req = actix_test::TestRequest::get()
+            .uri(uri)
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200, "{uri}");
+    }
+
+    // v2 only has the endpoints whose shape changed
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v2/auth/me")
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+}
+
+#[actix_web::test]
+async fn deprecated_endpoints_send_deprecation_header() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Deprecated").await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    for prefix in ["/api/v1", "/api"] {
+        let req = actix_test::TestRequest::get()
+            .uri(&format!("{prefix}/boards/{board_id}/posts"))
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        let deprecation = resp.headers().get("deprecation").unwrap().to_str().unwrap();
+        assert!(deprecation.starts_with('@'), "{deprecation}");
+        assert!(resp.headers().get("sunset").is_none());
+    }
+
+    // Other methods on the same path are not deprecated
+    let req = actix_test::TestRequest::post()
+        .uri(&format!("/api/v1/boards/{board_id}/posts"))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .set_json(serde_json::json!({ "title": "Still current" }))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+    assert!(resp.headers().get("deprecation").is_none());
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/openapi.json")
+        .to_request();
+    let spec: Value = actix_test::call_and_read_body_json(&app, req).await;
+    assert_eq!(
+        spec["paths"]["/api/v1/boards/{board_id}/posts"]["get"]["deprecated"],
+        true
+    );
+}
+
+#[actix_web::test]
+async fn v2_post_list_is_paginated() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Paged").await;
+    for title in ["First", "Second", "Third"] {
+        common::create_post(&pool, &token, board_id, title).await;
+    }
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!(
+            "/api/v2/boards/{board_id}/posts?sort=oldest&per_page=2&page=2"
+        ))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get("deprecation").is_none());
+    let body: Value = actix_test::read_body_json(resp).await;
+    let data = body["data"].as_array().unwrap();
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0]["title"], "Third");
+    assert_eq!(
+        body["pagination"],
+        serde_json::json!({ "page": 2, "per_page": 2, "total": 3, "total_pages": 2 })
+    );
+}
+
+#[actix_web::test]
+async fn v2_post_list_page_past_the_end_is_empty() {
+    let pool = common::create_pool().await;
+    let (token, _user_id, org_id) = common::register_user(&pool).await;
+    let (board_id, _slug) = common::create_board(&pool, &token, org_id, "Far Pages").await;
+    common::create_post(&pool, &token, board_id, "Only").await;
+    let app = actix_test::init_service(common::build_app(pool.clone())).await;
+
+    let req = actix_test::TestRequest::get()
+        .uri(&format!(
+            "/api/v2/boards/{board_id}/posts?page={}&per_page=100",
+            i64::MAX
+        ))
+        .insert_header(("Authorization", format!("Bearer {token}")))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = actix_test::read_body_json(resp).await;
+    assert!(body["data"].as_array().unwrap().is_empty());
+    assert_eq!(body["pagination"]["page"], i64::MAX);
+    assert_eq!(body["pagination"]["total"], 1);
 }
 
 // ============================================================
@@ -1,19 +1,126 @@
+use std::sync::{Arc, LazyLock, Mutex};
+
+use actix_web::body::MessageBody;
 use actix_web::dev::{ServiceFactory, ServiceRequest, ServiceResponse};
-use actix_web::{App, test as actix_test, web};
+use actix_web::{App, test as actix_test};
+use rivvo::mailer::{Email, MailError, Mailer};
 use serde_json::Value;
 use sqlx::PgPool;
 use uuid::Uuid;
 
 const JWT_SECRET: &str = "test-secret-that-is-at-least-32-characters-long";
 
+/// Collects outgoing email so tests can read confirmation links. Shared by
+/// all tests in the binary; look messages up by recipient.
+#[derive(Default)]
+pub struct MemoryMailer {
+    sent: Mutex<Vec<Email>>,
+}
+
+#[async_trait::async_trait]
+impl Mailer for MemoryMailer {
+    async fn send(&self, email: Email) -> Result<(), MailError> {
+        self.sent.lock().unwrap().push(email);
+        Ok(())
+    }
+}
+
+static OUTBOX: LazyLock<Arc<MemoryMailer>> = LazyLock::new(Default::default);
+
+/// The most recent email sent to `to`.
+pub fn last_email_to(to: &str) -> Option<Email> {
+    OUTBOX
+        .sent
+        .lock()
+        .unwrap()
+        .iter()
+        .rev()
+        .find(|e| e.to == to)
+        .cloned()
+}
+
+/// Run background workers until `done` holds, for up to five seconds.
+/// Returns whether it did.
+pub async fn run_jobs_until<F, Fut>(pool: &PgPool, mut done: F) -> bool
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    let workers = rivvo::jobs::spawn_workers(job_context(pool.clone()), 2);
+    let mut finished = false;
+    for _ in 0..50 {
+        finished = done().await;
+        if finished {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    workers.shutdown(std::time::Duration::from_secs(5)).await;
+    finished
+}
+
+/// Run background workers until an email to `to` has been delivered, for
+/// mail that handlers queue rather than send.
+pub async fn deliver_email_to(pool: &PgPool, to: &str) -> Option<Email> {
+    run_jobs_until(pool, || async { last_email_to(to).is_some() }).await;
+    last_email_to(to)
+}
+
+/// Run background workers until the account of `user_id` is gone.
+pub async fn wait_for_erasure(pool: &PgPool, user_id: Uuid) -> bool {
+    run_jobs_until(pool, || async {
+        !sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    })
+    .await
+}
+
 pub fn test_config() -> rivvo::config::Config {
-    rivvo::config::Config {
-        database_url: String::new(), // pool is shared directly
-        jwt_secret: JWT_SECRET.to_string(),
-        host: "127.0.0.1".to_string(),
-        port: 8080,
-        cors_origin: "http://localhost:5173".to_string(),
-        max_db_connections: 5,
+    use rivvo::config::*;
+
+    Config {
+        server: ServerConfig {
+            app_url: "http://localhost:5173".to_string(),
+            ..Default::default()
+        },
+        // The pool is shared directly, so database.url stays empty
+        database: DatabaseConfig {
+            max_connections: 5,
+            ..Default::default()
+        },
+        auth: AuthConfig {
+            jwt_secret: JWT_SECRET.to_string(),
+        },
+        storage: StorageConfig {
+            dir: std::env::temp_dir()
+                .join("rivvo-test-uploads")
+                .to_string_lossy()
+                .into_owned(),
+            max_upload_bytes: 1024 * 1024,
+            ..Default::default()
+        },
+        jobs: JobsConfig {
+            workers: 0,
+            counter_reconcile_interval_secs: 0,
+        },
+        telemetry: TelemetryConfig {
+            otel_service_name: "rivvo-test".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// What background workers get in tests: the shared outbox and test storage.
+pub fn job_context(pool: PgPool) -> rivvo::jobs::JobContext {
+    rivvo::jobs::JobContext {
+        pool,
+        mailer: OUTBOX.clone(),
+        storage: rivvo::storage::from_config(&test_config())
+            .expect("Failed to initialize test storage"),
     }
 }
 
@@ -31,100 +138,20 @@ pub fn build_app(
     impl ServiceFactory<
         ServiceRequest,
         Config = (),
-        Response = ServiceResponse,
+        Response = ServiceResponse<impl MessageBody>,
         Error = actix_web::Error,
         InitError = (),
     >,
 > {
     let config = test_config();
-    App::new()
-        .app_data(web::Data::new(pool))
-        .app_data(web::Data::new(config))
-        .service(
-            web::scope("/api")
-                .route(
-                    "/health",
-                    web::get().to(rivvo::handlers::health::health_check),
-                )
-                .service(
-                    web::scope("/auth")
-                        .route("/register", web::post().to(rivvo::handlers::auth::register))
-                        .route("/login", web::post().to(rivvo::handlers::auth::login))
-                        .route("/me", web::get().to(rivvo::handlers::auth::me)),
-                )
-                .route(
-                    "/orgs",
-                    web::get().to(rivvo::handlers::organizations::list_user_orgs),
-                )
-                .service(
-                    web::scope("/orgs/{org_id}/boards")
-                        .route("", web::get().to(rivvo::handlers::boards::list_boards))
-                        .route("", web::post().to(rivvo::handlers::boards::create_board))
-                        .route("/{slug}", web::get().to(rivvo::handlers::boards::get_board))
-                        .route(
-                            "/{slug}",
-                            web::put().to(rivvo::handlers::boards::update_board),
-                        )
-                        .route(
-                            "/{slug}",
-                            web::delete().to(rivvo::handlers::boards::delete_board),
-                        ),
-                )
-                .service(
-                    web::scope("/boards/{board_id}/posts")
-                        .route("", web::get().to(rivvo::handlers::posts::list_posts))
-                        .route("", web::post().to(rivvo::handlers::posts::create_post))
-                        .route(
-                            "/{post_id}",
-                            web::get().to(rivvo::handlers::posts::get_post),
-                        )
-                        .route(
-                            "/{post_id}",
-                            web::put().to(rivvo::handlers::posts::update_post),
-                        )
-                        .route(
-                            "/{post_id}",
-                            web::delete().to(rivvo::handlers::posts::delete_post),
-                        )
-                        .route(
-                            "/{post_id}/status",
-                            web::put().to(rivvo::handlers::posts::update_status),
-                        ),
-                )
-                .service(
-                    web::scope("/boards/{board_id}/tags")
-                        .route("", web::get().to(rivvo::handlers::tags::list_tags))
-                        .route("", web::post().to(rivvo::handlers::tags::create_tag)),
-                )
-                .route(
-                    "/posts/{post_id}/vote",
-                    web::post().to(rivvo::handlers::votes::toggle_vote),
-                )
-                .service(
-                    web::scope("/posts/{post_id}/comments")
-                        .route("", web::get().to(rivvo::handlers::comments::list_comments))
-                        .route(
-                            "",
-                            web::post().to(rivvo::handlers::comments::create_comment),
-                        ),
-                )
-                .route(
-                    "/comments/{comment_id}",
-                    web::delete().to(rivvo::handlers::comments::delete_comment),
-                )
-                .route(
-                    "/tags/{tag_id}",
-                    web::delete().to(rivvo::handlers::tags::delete_tag),
-                )
-                .route(
-                    "/posts/{post_id}/tags/{tag_id}",
-                    web::post().to(rivvo::handlers::tags::add_tag_to_post),
-                )
-                .route(
-                    "/posts/{post_id}/tags/{tag_id}",
-                    web::delete().to(rivvo::handlers::tags::remove_tag_from_post),
-                ),
-        )
+    let storage = rivvo::storage::from_config(&config).expect("Failed to initialize test storage");
+    rivvo::build_app(rivvo::AppState::new(
+        pool,
+        config,
+        storage,
+        OUTBOX.clone(),
+        Default::default(),
+    ))
 }
 
 /// Register a user with a unique email and return (token, user_id, org_id).
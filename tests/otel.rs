@@ -0,0 +1,96 @@
+//! Trace export against a stand-in OTLP collector. Run with
+//! `cargo test --features otel --test otel`.
+//!
+//! Kept out of `api_tests` because it installs the global subscriber.
+
+#![cfg(feature = "otel")]
+
+#[allow(dead_code)]
+mod common;
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use actix_web::test as actix_test;
+
+/// Accepts OTLP/HTTP exports on a free port and passes on `(path, body)`.
+fn start_collector() -> (String, mpsc::Receiver<(String, Vec<u8>)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let path = request_line
+                .split(' ')
+                .nth(1)
+                .unwrap_or_default()
+                .to_string();
+
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\n\
+                      content-type: application/x-protobuf\r\n\
+                      content-length: 0\r\n\r\n",
+                )
+                .unwrap();
+            let _ = tx.send((path, body));
+        }
+    });
+
+    (endpoint, rx)
+}
+
+#[actix_web::test]
+async fn request_spans_are_exported_over_otlp() {
+    let (endpoint, exports) = start_collector();
+    // Request spans are info, sqlx statements debug. No other threads yet.
+    unsafe { std::env::set_var("RUST_LOG", "info,sqlx::query=debug") };
+    let mut config = common::test_config();
+    config.telemetry.otel_endpoint = Some(endpoint);
+    let tracing = rivvo::telemetry::init_tracing(&config);
+
+    let pool = common::create_pool().await;
+    let app = actix_test::init_service(common::build_app(pool)).await;
+    let trace_id = "4bf92f3577b34da6a3ce929d0e0e4736";
+    let req = actix_test::TestRequest::get()
+        .uri("/api/health")
+        .insert_header(("traceparent", format!("00-{trace_id}-00f067aa0ba902b7-01")))
+        .to_request();
+    assert_eq!(actix_test::call_service(&app, req).await.status(), 200);
+
+    // Flushes the batch
+    drop(tracing);
+    let (path, body) = exports.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(path, "/v1/traces");
+
+    let contains = |needle: &[u8]| body.windows(needle.len()).any(|w| w == needle);
+    assert!(contains(b"rivvo-test"), "service name");
+    assert!(contains(b"GET /api/health"), "span named after the route");
+    assert!(
+        contains(&hex::decode(trace_id).unwrap()),
+        "continues the caller's trace"
+    );
+    assert!(contains(b"SELECT 1"), "sqlx statement recorded");
+}
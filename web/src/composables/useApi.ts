@@ -48,7 +48,7 @@ export function useApi() {
 
     if (!response.ok) {
       const error = await response.json().catch(() => ({ error: 'Request failed' }))
-      throw new Error(error.error || 'Request failed')
+      throw new Error(error.detail || error.error || 'Request failed')
     }
 
     if (response.status === 204 || response.headers.get('content-length') === '0') {
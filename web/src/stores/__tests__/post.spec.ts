@@ -35,7 +35,9 @@ const makePost = (overrides: Partial<Post> = {}): Post => ({
   id: '1',
   board_id: 'b1',
   title: 'Test',
-  description: null,
+  description_markdown: null,
+  description_html: null,
+  mentions: [],
   status: 'open',
   vote_count: 0,
   comment_count: 0,
@@ -43,6 +45,8 @@ const makePost = (overrides: Partial<Post> = {}): Post => ({
   author: null,
   has_voted: false,
   tags: [],
+  reactions: [],
+  attachments: [],
   created_at: '2025-01-01',
   updated_at: null,
   ...overrides,
@@ -128,7 +132,7 @@ describe('post store', () => {
 
   it('fetchComments sets comments', async () => {
     const comments: Comment[] = [
-      { id: 'c1', body: 'Nice', is_admin_reply: false, author: null, created_at: '' },
+      { id: 'c1', body_markdown: 'Nice', body_html: '<p>Nice</p>', mentions: [], is_admin_reply: false, author: null, reactions: [], attachments: [], created_at: '' },
     ]
     mockGet.mockResolvedValue(comments)
 
@@ -140,7 +144,7 @@ describe('post store', () => {
   })
 
   it('addComment pushes to list and increments counts', async () => {
-    const comment: Comment = { id: 'c2', body: 'Reply', is_admin_reply: false, author: null, created_at: '' }
+    const comment: Comment = { id: 'c2', body_markdown: 'Reply', body_html: '<p>Reply</p>', mentions: [], is_admin_reply: false, author: null, reactions: [], attachments: [], created_at: '' }
     mockPost.mockResolvedValue(comment)
 
     const store = usePostStore()
@@ -160,8 +164,8 @@ describe('post store', () => {
 
     const store = usePostStore()
     store.comments = [
-      { id: 'c1', body: 'Keep', is_admin_reply: false, author: null, created_at: '' },
-      { id: 'c2', body: 'Remove', is_admin_reply: false, author: null, created_at: '' },
+      { id: 'c1', body_markdown: 'Keep', body_html: '<p>Keep</p>', mentions: [], is_admin_reply: false, author: null, reactions: [], attachments: [], created_at: '' },
+      { id: 'c2', body_markdown: 'Remove', body_html: '<p>Remove</p>', mentions: [], is_admin_reply: false, author: null, reactions: [], attachments: [], created_at: '' },
     ]
     store.currentPost = makePost({ id: 'p1', comment_count: 2 })
     store.posts = [makePostListItem({ id: 'p1', comment_count: 2 })]
@@ -178,7 +182,7 @@ describe('post store', () => {
     mockDel.mockResolvedValue(null)
 
     const store = usePostStore()
-    store.comments = [{ id: 'c1', body: 'X', is_admin_reply: false, author: null, created_at: '' }]
+    store.comments = [{ id: 'c1', body_markdown: 'X', body_html: '<p>X</p>', mentions: [], is_admin_reply: false, author: null, reactions: [], attachments: [], created_at: '' }]
     store.currentPost = makePost({ id: 'p1', comment_count: 0 })
 
     await store.deleteComment('c1', 'p1')
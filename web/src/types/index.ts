@@ -53,7 +53,9 @@ export interface Post {
   id: string
   board_id: string
   title: string
-  description: string | null
+  description_markdown: string | null
+  description_html: string | null
+  mentions: MentionSpan[]
   status: PostStatus
   vote_count: number
   comment_count: number
@@ -61,15 +63,21 @@ export interface Post {
   author: PostAuthor | null
   has_voted: boolean
   tags: Tag[]
+  reactions: ReactionSummary[]
+  attachments: Attachment[]
   created_at: string
   updated_at: string | null
 }
 
 export interface Comment {
   id: string
-  body: string
+  body_markdown: string
+  body_html: string
+  mentions: MentionSpan[]
   is_admin_reply: boolean
   author: CommentAuthor | null
+  reactions: ReactionSummary[]
+  attachments: Attachment[]
   created_at: string
 }
 
@@ -89,3 +97,49 @@ export interface VoteResult {
   voted: boolean
   vote_count: number
 }
+
+export interface ReactionSummary {
+  emoji: string
+  count: number
+  reacted: boolean
+}
+
+export interface ReactionResult {
+  emoji: string
+  reacted: boolean
+  count: number
+}
+
+export interface Attachment {
+  id: string
+  filename: string
+  content_type: string
+  size_bytes: number
+  url: string
+  created_at: string
+}
+
+export interface MentionSpan {
+  user_id: string
+  name: string
+  start: number
+  end: number
+}
+
+export interface MemberSuggestion {
+  id: string
+  name: string
+  avatar_url: string | null
+}
+
+export interface Notification {
+  id: string
+  kind: string
+  actor_id: string | null
+  actor_name: string | null
+  post_id: string | null
+  post_title: string | null
+  comment_id: string | null
+  read: boolean
+  created_at: string
+}